@@ -0,0 +1,125 @@
+//! # Response Cache
+//!
+//! Opt-in cache of LLM responses for clients built by the [`registry`](super::registry).
+//!
+//! When a `ModelConfig` carries a [`ResponseCache`], each call is looked up by
+//! provider, model, prompt slug and the exact input (thread, generation
+//! parameters and tool names)
+//! before it reaches the provider. The cache implementation adds the prompt
+//! template version to the key; the project cache lives in `CatalystDb`
+//! (see `state::LlmCache`). Cache hits do not report token usage.
+
+use async_trait::async_trait;
+use radkit::errors::AgentResult;
+use radkit::models::{BaseLlm, LlmResponse, Thread};
+use radkit::tools::BaseToolset;
+use std::sync::Arc;
+
+use super::ModelConfig;
+
+/// A cacheable LLM call
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheRequest {
+    pub provider: String,
+    pub model: String,
+    /// Prompt template slug (the agent ID)
+    pub prompt_slug: String,
+    /// Serialized thread, generation parameters and tool names
+    pub input: String,
+}
+
+/// Stores LLM responses by request
+pub trait ResponseCache: Send + Sync + std::fmt::Debug {
+    /// Cached response for `request`, if present and fresh
+    fn get(&self, request: &CacheRequest) -> Option<LlmResponse>;
+    /// Store the response to `request`
+    fn put(&self, request: &CacheRequest, response: &LlmResponse);
+}
+
+/// LLM client wrapper that serves responses from a cache
+pub struct CachedLlm<L> {
+    inner: L,
+    cache: Arc<dyn ResponseCache>,
+    provider: String,
+    prompt_slug: String,
+    /// Serialized generation parameters (`None` when all are defaults)
+    generation: Option<String>,
+}
+
+impl<L: BaseLlm> CachedLlm<L> {
+    pub fn new(inner: L, cache: Arc<dyn ResponseCache>, config: &ModelConfig) -> Self {
+        Self {
+            inner,
+            cache,
+            provider: config.provider.as_str().to_string(),
+            prompt_slug: config
+                .agent_id
+                .clone()
+                .unwrap_or_else(|| "unknown".to_string()),
+            generation: (!config.generation.is_empty())
+                .then(|| serde_json::to_string(&config.generation).ok())
+                .flatten(),
+        }
+    }
+
+    async fn request(
+        &self,
+        thread: &Thread,
+        toolset: Option<&Arc<dyn BaseToolset>>,
+    ) -> Option<CacheRequest> {
+        let mut input = serde_json::to_string(thread).ok()?;
+        if let Some(generation) = &self.generation {
+            input.push('\n');
+            input.push_str(generation);
+        }
+        if let Some(toolset) = toolset {
+            let mut names: Vec<String> = toolset
+                .get_tools()
+                .await
+                .iter()
+                .map(|tool| tool.name().to_string())
+                .collect();
+            names.sort();
+            input.push('\n');
+            input.push_str(&names.join(","));
+        }
+
+        Some(CacheRequest {
+            provider: self.provider.clone(),
+            model: self.inner.model_name().to_string(),
+            prompt_slug: self.prompt_slug.clone(),
+            input,
+        })
+    }
+}
+
+#[async_trait]
+impl<L: BaseLlm> BaseLlm for CachedLlm<L> {
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+
+    async fn generate_content(
+        &self,
+        thread: Thread,
+        toolset: Option<Arc<dyn BaseToolset>>,
+    ) -> AgentResult<LlmResponse> {
+        let Some(request) = self.request(&thread, toolset.as_ref()).await else {
+            return self.inner.generate_content(thread, toolset).await;
+        };
+
+        if let Some(response) = self.cache.get(&request) {
+            tracing::debug!(
+                "LLM cache hit for {} ({}/{})",
+                request.prompt_slug,
+                request.provider,
+                request.model
+            );
+            return Ok(response);
+        }
+
+        let response = self.inner.generate_content(thread, toolset).await?;
+        self.cache.put(&request, &response);
+        Ok(response)
+    }
+}
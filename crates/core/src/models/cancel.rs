@@ -0,0 +1,100 @@
+//! # Cancellation
+//!
+//! LLM calls that stop as soon as their config's cancellation token fires.
+//!
+//! The [`registry`](super::registry) wraps every client it builds for a config
+//! with a `cancel` token, so a stopped run does not wait for a long completion
+//! (or a whole retry schedule) before noticing. A cancelled call fails with
+//! [`CANCELLED`] as its reason.
+
+use async_trait::async_trait;
+use radkit::errors::{AgentError, AgentResult};
+use radkit::models::{BaseLlm, LlmResponse, Thread};
+use radkit::tools::BaseToolset;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+
+/// Reason reported by calls that were cancelled
+pub const CANCELLED: &str = "Cancelled";
+
+/// LLM client wrapper that gives up when its token is cancelled
+pub struct CancellableLlm<L> {
+    inner: L,
+    token: CancellationToken,
+}
+
+impl<L: BaseLlm> CancellableLlm<L> {
+    pub fn new(inner: L, token: CancellationToken) -> Self {
+        Self { inner, token }
+    }
+}
+
+#[async_trait]
+impl<L: BaseLlm> BaseLlm for CancellableLlm<L> {
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+
+    async fn generate_content(
+        &self,
+        thread: Thread,
+        toolset: Option<Arc<dyn BaseToolset>>,
+    ) -> AgentResult<LlmResponse> {
+        tokio::select! {
+            biased;
+            _ = self.token.cancelled() => Err(AgentError::Internal {
+                component: "cancel".to_string(),
+                reason: CANCELLED.to_string(),
+            }),
+            response = self.inner.generate_content(thread, toolset) => response,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use radkit::models::{Content, TokenUsage};
+    use std::time::Duration;
+
+    struct SlowLlm;
+
+    #[async_trait]
+    impl BaseLlm for SlowLlm {
+        fn model_name(&self) -> &str {
+            "slow"
+        }
+
+        async fn generate_content(
+            &self,
+            _thread: Thread,
+            _toolset: Option<Arc<dyn BaseToolset>>,
+        ) -> AgentResult<LlmResponse> {
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            Ok(LlmResponse::new(
+                Content::from_text("late"),
+                TokenUsage::empty(),
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cancel_interrupts_call_in_flight() {
+        let token = CancellationToken::new();
+        let llm = CancellableLlm::new(SlowLlm, token.clone());
+
+        let thread = Thread::from_user("hello");
+        let call = tokio::spawn(async move { llm.generate_content(thread, None).await });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        token.cancel();
+
+        let result = tokio::time::timeout(Duration::from_millis(500), call)
+            .await
+            .expect("cancelled call should return promptly")
+            .unwrap();
+        assert!(matches!(
+            result,
+            Err(AgentError::Internal { reason, .. }) if reason == CANCELLED
+        ));
+    }
+}
@@ -0,0 +1,227 @@
+//! # Generation Parameters
+//!
+//! Sampling settings carried by a `ModelConfig` and applied when the
+//! [`registry`](super::registry) builds a client.
+//!
+//! Temperature and max tokens are passed to the provider's own client (see
+//! [`ApplyGeneration`]). Stop sequences are enforced on the response by
+//! [`StopSequenceLlm`], since the radkit clients do not send them. No provider
+//! client can send an extended-thinking budget yet, so configs that set one
+//! (or any other unknown field) are rejected when they are loaded.
+
+use async_trait::async_trait;
+use radkit::errors::AgentResult;
+use radkit::models::providers::{
+    AnthropicLlm, DeepSeekLlm, GeminiLlm, GrokLlm, OpenAILlm, OpenRouterLlm,
+};
+use radkit::models::{BaseLlm, Content, ContentPart, LlmResponse, Thread};
+use radkit::tools::BaseToolset;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Sampling settings for an agent's LLM calls (unset = provider default)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GenerationParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    /// Maximum output tokens per call
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    /// Output is cut at the first occurrence of any of these
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub stop_sequences: Vec<String>,
+}
+
+impl GenerationParams {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    pub fn with_temperature(mut self, temperature: f32) -> Self {
+        self.temperature = Some(temperature);
+        self
+    }
+
+    pub fn with_max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    pub fn with_stop_sequence(mut self, stop: impl Into<String>) -> Self {
+        self.stop_sequences.push(stop.into());
+        self
+    }
+
+    /// These params with every setting in `overrides` taking precedence
+    pub fn merged(&self, overrides: &GenerationParams) -> Self {
+        Self {
+            temperature: overrides.temperature.or(self.temperature),
+            max_tokens: overrides.max_tokens.or(self.max_tokens),
+            stop_sequences: if overrides.stop_sequences.is_empty() {
+                self.stop_sequences.clone()
+            } else {
+                overrides.stop_sequences.clone()
+            },
+        }
+    }
+}
+
+/// Provider clients that accept sampling settings
+pub trait ApplyGeneration: Sized {
+    /// The client with temperature and max tokens from `params` applied
+    fn apply_generation(self, params: &GenerationParams) -> Self;
+}
+
+macro_rules! impl_apply_generation {
+    ($($llm:ty),* $(,)?) => {
+        $(
+            impl ApplyGeneration for $llm {
+                fn apply_generation(self, params: &GenerationParams) -> Self {
+                    let llm = match params.temperature {
+                        Some(temperature) => self.with_temperature(temperature),
+                        None => self,
+                    };
+                    match params.max_tokens {
+                        Some(max_tokens) => llm.with_max_tokens(max_tokens),
+                        None => llm,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_apply_generation!(
+    AnthropicLlm,
+    OpenAILlm,
+    GeminiLlm,
+    OpenRouterLlm,
+    GrokLlm,
+    DeepSeekLlm,
+);
+
+/// LLM client wrapper that truncates responses at the first stop sequence
+pub struct StopSequenceLlm<L> {
+    inner: L,
+    stop_sequences: Vec<String>,
+}
+
+impl<L: BaseLlm> StopSequenceLlm<L> {
+    pub fn new(inner: L, stop_sequences: Vec<String>) -> Self {
+        Self {
+            inner,
+            stop_sequences,
+        }
+    }
+
+    /// Content up to the first stop sequence; later parts are dropped
+    fn truncate(&self, content: Content) -> Content {
+        let mut parts = Vec::new();
+        for part in content.into_parts() {
+            if let ContentPart::Text(text) = &part {
+                let cut = self
+                    .stop_sequences
+                    .iter()
+                    .filter(|stop| !stop.is_empty())
+                    .filter_map(|stop| text.find(stop.as_str()))
+                    .min();
+                if let Some(cut) = cut {
+                    parts.push(ContentPart::Text(text[..cut].to_string()));
+                    break;
+                }
+            }
+            parts.push(part);
+        }
+        Content::from_parts(parts)
+    }
+}
+
+#[async_trait]
+impl<L: BaseLlm> BaseLlm for StopSequenceLlm<L> {
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+
+    async fn generate_content(
+        &self,
+        thread: Thread,
+        toolset: Option<Arc<dyn BaseToolset>>,
+    ) -> AgentResult<LlmResponse> {
+        let (content, usage) = self
+            .inner
+            .generate_content(thread, toolset)
+            .await?
+            .into_parts();
+        Ok(LlmResponse::new(self.truncate(content), usage))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use radkit::models::TokenUsage;
+
+    struct FixedLlm(&'static str);
+
+    #[async_trait]
+    impl BaseLlm for FixedLlm {
+        fn model_name(&self) -> &str {
+            "fixed"
+        }
+
+        async fn generate_content(
+            &self,
+            _thread: Thread,
+            _toolset: Option<Arc<dyn BaseToolset>>,
+        ) -> AgentResult<LlmResponse> {
+            Ok(LlmResponse::new(
+                Content::from_text(self.0),
+                TokenUsage::empty(),
+            ))
+        }
+    }
+
+    #[test]
+    fn test_merged_overrides_only_set_fields() {
+        let base = GenerationParams::default()
+            .with_temperature(0.7)
+            .with_max_tokens(4096)
+            .with_stop_sequence("END");
+        let critic = GenerationParams::default().with_temperature(0.0);
+
+        let merged = base.merged(&critic);
+        assert_eq!(merged.temperature, Some(0.0));
+        assert_eq!(merged.max_tokens, Some(4096));
+        assert_eq!(merged.stop_sequences, vec!["END".to_string()]);
+        assert!(GenerationParams::default().is_empty());
+        assert_eq!(
+            serde_json::to_string(&GenerationParams::default()).unwrap(),
+            "{}"
+        );
+    }
+
+    #[test]
+    fn test_unsupported_settings_are_rejected() {
+        let params: GenerationParams =
+            serde_json::from_str(r#"{"temperature": 0.2, "stop_sequences": ["END"]}"#).unwrap();
+        assert_eq!(params.temperature, Some(0.2));
+
+        let err =
+            serde_json::from_str::<GenerationParams>(r#"{"reasoning_budget": 8000}"#).unwrap_err();
+        assert!(err.to_string().contains("reasoning_budget"));
+    }
+
+    #[tokio::test]
+    async fn test_stop_sequences_truncate_response() {
+        let llm = StopSequenceLlm::new(
+            FixedLlm("fn main() {}\n```\nExplanation follows"),
+            vec!["Explanation".to_string(), "```".to_string()],
+        );
+        let response = llm
+            .generate_content(Thread::from_user("draft"), None)
+            .await
+            .unwrap();
+        assert_eq!(response.content().first_text(), Some("fn main() {}\n"));
+    }
+}
@@ -0,0 +1,135 @@
+//! # Local Provider
+//!
+//! OpenAI-compatible provider for self-hosted model servers: Ollama, the
+//! llama.cpp server, vLLM, LM Studio, and anything else exposing
+//! `/v1/chat/completions`.
+//!
+//! Unlike [`LlmProvider::OpenAI`](super::LlmProvider::OpenAI), the API key is
+//! optional (`LOCAL_LLM_API_KEY`) and the base URL defaults to a local Ollama
+//! instance. Available models can be discovered with [`list_models`].
+
+use radkit::errors::AgentResult;
+use radkit::models::providers::OpenAILlm;
+use serde::Deserialize;
+
+use super::generation::ApplyGeneration;
+use super::ModelConfig;
+
+/// Default endpoint (Ollama's OpenAI-compatible API)
+pub const DEFAULT_LOCAL_BASE_URL: &str = "http://localhost:11434/v1";
+
+/// Optional API key for servers started with `--api-key` (vLLM, llama.cpp)
+pub const LOCAL_API_KEY_ENV: &str = "LOCAL_LLM_API_KEY";
+
+/// Placeholder sent when no key is configured (local servers ignore it)
+const NO_API_KEY: &str = "not-needed";
+
+/// Resolve the base URL for a local provider config
+pub fn base_url(config: &ModelConfig) -> &str {
+    config
+        .base_url
+        .as_deref()
+        .filter(|url| !url.is_empty())
+        .unwrap_or(DEFAULT_LOCAL_BASE_URL)
+}
+
+/// Optional API key from the environment
+pub fn api_key() -> Option<String> {
+    std::env::var(LOCAL_API_KEY_ENV)
+        .ok()
+        .filter(|key| !key.is_empty())
+}
+
+/// Create an OpenAI-compatible client for a local model server
+///
+/// Returns `AgentResult` like the hosted providers' `from_env()`.
+pub fn local_llm(config: &ModelConfig) -> AgentResult<OpenAILlm> {
+    let key = api_key().unwrap_or_else(|| NO_API_KEY.to_string());
+    Ok(OpenAILlm::new(&config.model, key)
+        .with_base_url(base_url(config))
+        .apply_generation(&config.generation))
+}
+
+/// Model listing in either OpenAI (`/v1/models`) or Ollama (`/api/tags`) shape
+#[derive(Debug, Deserialize)]
+struct ModelList {
+    #[serde(default)]
+    data: Vec<ModelEntry>,
+    #[serde(default)]
+    models: Vec<ModelEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ModelEntry {
+    #[serde(alias = "name")]
+    id: String,
+}
+
+/// Extract model IDs from a `/models` or `/api/tags` response body
+fn parse_models(body: &str) -> anyhow::Result<Vec<String>> {
+    let list: ModelList = serde_json::from_str(body)?;
+    let mut ids: Vec<String> = list
+        .data
+        .into_iter()
+        .chain(list.models)
+        .map(|m| m.id)
+        .collect();
+    ids.sort();
+    ids.dedup();
+    Ok(ids)
+}
+
+/// List the models served by an OpenAI-compatible endpoint
+///
+/// Tries `{base_url}/models` first, then falls back to Ollama's native
+/// `/api/tags` for older Ollama versions without the OpenAI listing.
+pub async fn list_models(base_url: &str, api_key: Option<&str>) -> anyhow::Result<Vec<String>> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()?;
+    let base = base_url.trim_end_matches('/');
+
+    let mut request = client.get(format!("{}/models", base));
+    if let Some(key) = api_key {
+        request = request.bearer_auth(key);
+    }
+
+    let openai_err = match request.send().await {
+        Ok(resp) if resp.status().is_success() => return parse_models(&resp.text().await?),
+        Ok(resp) => anyhow::anyhow!("{} returned {}", base, resp.status()),
+        Err(e) => anyhow::anyhow!("Failed to reach {}: {}", base, e),
+    };
+
+    let ollama_root = base.trim_end_matches("/v1");
+    match client.get(format!("{}/api/tags", ollama_root)).send().await {
+        Ok(resp) if resp.status().is_success() => parse_models(&resp.text().await?),
+        _ => Err(openai_err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::LlmProvider;
+
+    #[test]
+    fn test_base_url_defaults_to_ollama() {
+        let config = ModelConfig::with_provider(LlmProvider::Local, "llama3.2");
+        assert_eq!(base_url(&config), DEFAULT_LOCAL_BASE_URL);
+
+        let config = config.with_base_url("http://gpu-box:8000/v1");
+        assert_eq!(base_url(&config), "http://gpu-box:8000/v1");
+    }
+
+    #[test]
+    fn test_parse_models_openai_and_ollama_shapes() {
+        let openai = r#"{"object":"list","data":[{"id":"qwen2.5-coder"},{"id":"llama3.2"}]}"#;
+        assert_eq!(
+            parse_models(openai).unwrap(),
+            vec!["llama3.2", "qwen2.5-coder"]
+        );
+
+        let ollama = r#"{"models":[{"name":"mistral:latest","size":1}]}"#;
+        assert_eq!(parse_models(ollama).unwrap(), vec!["mistral:latest"]);
+    }
+}
@@ -0,0 +1,419 @@
+//! # Catalyst Models
+//!
+//! Centralized LLM configuration types for the Catalyst system.
+//! These types were extracted from the agents module to provide
+//! a clean dependency for both skills and swarm orchestration.
+//!
+//! ## Reference Documentation
+//! See `radkit_docs/docs/core-concepts/llm-providers.md` for Radkit LLM provider details.
+
+pub mod cache;
+pub mod cancel;
+pub mod generation;
+pub mod local;
+pub mod rate_limit;
+pub mod registry;
+pub mod replay;
+pub mod retry;
+pub mod structured;
+pub mod transcript;
+pub mod usage;
+
+use radkit::models::providers::AnthropicLlm;
+use radkit::models::BaseLlm;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+
+pub use cache::{CacheRequest, ResponseCache};
+pub use generation::GenerationParams;
+pub use rate_limit::{RateLimit, RateLimiterStats};
+pub use registry::{ProviderCapabilities, ProviderRegistry, ProviderSpec};
+pub use replay::{CassetteConfig, CassetteMode};
+pub use retry::{ErrorClass, FallbackModel, RetryEvent, RetryObserver, RetryPolicy};
+pub use structured::{RepairEvent, RepairObserver, ValidatedOutput};
+pub use transcript::{LlmExchange, TranscriptMessage, TranscriptSink};
+pub use usage::{LlmUsage, ModelPrice, PriceTable, UsageSink};
+
+/// Supported LLM providers
+///
+/// Maps to the providers documented in `radkit_docs/docs/core-concepts/llm-providers.md`:
+/// - Anthropic (Claude) - `ANTHROPIC_API_KEY`
+/// - OpenAI (GPT) - `OPENAI_API_KEY`
+/// - Gemini (Google) - `GEMINI_API_KEY`
+/// - OpenRouter (Gateway) - `OPENROUTER_API_KEY`
+/// - Grok (xAI) - `XAI_API_KEY`
+/// - DeepSeek - `DEEPSEEK_API_KEY`
+/// - Local (Ollama, llama.cpp, vLLM) - optional `LOCAL_LLM_API_KEY` (see [`local`])
+/// - Mock - replays a recorded cassette, no API key (see [`replay`])
+///
+/// Providers registered by embedders through [`registry::register`] are
+/// represented as `Custom(name)`. Serializes as the provider name string.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum LlmProvider {
+    #[default]
+    Anthropic,
+    OpenAI,
+    Gemini,
+    OpenRouter,
+    Grok,
+    DeepSeek,
+    Local,
+    Mock,
+    /// Provider registered at runtime, by registry name
+    Custom(String),
+}
+
+impl LlmProvider {
+    /// Get all registered providers
+    pub fn all() -> Vec<LlmProvider> {
+        registry::global()
+            .list()
+            .iter()
+            .map(|spec| LlmProvider::from(spec.name.as_str()))
+            .collect()
+    }
+
+    /// Registry name (e.g. "anthropic", "openai")
+    pub fn as_str(&self) -> &str {
+        match self {
+            LlmProvider::Anthropic => "anthropic",
+            LlmProvider::OpenAI => "openai",
+            LlmProvider::Gemini => "gemini",
+            LlmProvider::OpenRouter => "openrouter",
+            LlmProvider::Grok => "grok",
+            LlmProvider::DeepSeek => "deepseek",
+            LlmProvider::Local => "local",
+            LlmProvider::Mock => "mock",
+            LlmProvider::Custom(name) => name,
+        }
+    }
+
+    /// Registry entry for this provider, if registered
+    pub fn spec(&self) -> Option<Arc<ProviderSpec>> {
+        registry::global().get(self.as_str())
+    }
+
+    /// Display name for UI
+    pub fn display_name(&self) -> String {
+        self.spec()
+            .map(|spec| spec.display_name.clone())
+            .unwrap_or_else(|| self.as_str().to_string())
+    }
+
+    /// Whether this provider supports custom base URL
+    pub fn supports_base_url(&self) -> bool {
+        self.spec().is_some_and(|spec| spec.capabilities.base_url)
+    }
+}
+
+impl From<&str> for LlmProvider {
+    fn from(name: &str) -> Self {
+        match name {
+            "anthropic" => LlmProvider::Anthropic,
+            "openai" => LlmProvider::OpenAI,
+            "gemini" => LlmProvider::Gemini,
+            "openrouter" => LlmProvider::OpenRouter,
+            "grok" => LlmProvider::Grok,
+            "deepseek" => LlmProvider::DeepSeek,
+            "local" => LlmProvider::Local,
+            "mock" => LlmProvider::Mock,
+            other => LlmProvider::Custom(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for LlmProvider {
+    fn from(name: String) -> Self {
+        LlmProvider::from(name.as_str())
+    }
+}
+
+impl From<LlmProvider> for String {
+    fn from(provider: LlmProvider) -> Self {
+        provider.as_str().to_string()
+    }
+}
+
+impl std::fmt::Display for LlmProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Configuration for LLM model selection
+///
+/// Used throughout the Catalyst system to configure which LLM provider and model
+/// to use for agent operations. Supports per-agent configuration overrides.
+///
+/// ## Example
+/// ```rust,ignore
+/// use catalyst_core::models::{ModelConfig, LlmProvider};
+///
+/// // Default Anthropic
+/// let config = ModelConfig::default();
+///
+/// // Specific provider and model
+/// let config = ModelConfig::with_provider(LlmProvider::OpenAI, "gpt-4o");
+///
+/// // Create LLM client
+/// let llm = config.create_llm()?;
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelConfig {
+    /// LLM provider to use
+    #[serde(default)]
+    pub provider: LlmProvider,
+    /// Model name (e.g., "claude-sonnet-4-20250514", "gpt-4o")
+    pub model: String,
+    /// Optional base URL override for OpenAI-compatible APIs
+    pub base_url: Option<String>,
+    /// Temperature, max tokens, and stop sequences
+    #[serde(default, skip_serializing_if = "GenerationParams::is_empty")]
+    pub generation: GenerationParams,
+    /// Agent this config was resolved for (keys cassette entries)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_id: Option<String>,
+    /// Cassette to replay from (Mock provider) or record into
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cassette: Option<CassetteConfig>,
+    /// Feature this call is made for (attributes token usage)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feature_id: Option<String>,
+    /// Receives the token usage of every call (not persisted)
+    #[serde(skip)]
+    pub usage_sink: Option<Arc<dyn UsageSink>>,
+    /// Retry policy for transient provider errors
+    #[serde(default)]
+    pub retry: RetryPolicy,
+    /// Provider/model pairs tried in order once this one keeps failing
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallbacks: Vec<FallbackModel>,
+    /// Notified before every retry or fallback (not persisted)
+    #[serde(skip)]
+    pub retry_observer: Option<Arc<dyn RetryObserver>>,
+    /// Serves and stores responses (not persisted; caching is off when unset)
+    #[serde(skip)]
+    pub response_cache: Option<Arc<dyn ResponseCache>>,
+    /// Receives every prompt/response exchange (not persisted)
+    #[serde(skip)]
+    pub transcript_sink: Option<Arc<dyn TranscriptSink>>,
+    /// Re-prompts allowed for invalid structured output (`None` = default of 2)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_repairs: Option<u32>,
+    /// Notified before every repair re-prompt (not persisted)
+    #[serde(skip)]
+    pub repair_observer: Option<Arc<dyn RepairObserver>>,
+    /// Stops calls in flight once cancelled (not persisted)
+    #[serde(skip)]
+    pub cancel: Option<CancellationToken>,
+}
+
+impl Default for ModelConfig {
+    fn default() -> Self {
+        Self {
+            provider: LlmProvider::Anthropic,
+            model: "claude-sonnet-4-20250514".to_string(),
+            base_url: None,
+            generation: GenerationParams::default(),
+            agent_id: None,
+            cassette: None,
+            feature_id: None,
+            usage_sink: None,
+            retry: RetryPolicy::default(),
+            fallbacks: Vec::new(),
+            retry_observer: None,
+            response_cache: None,
+            transcript_sink: None,
+            max_repairs: None,
+            repair_observer: None,
+            cancel: None,
+        }
+    }
+}
+
+impl ModelConfig {
+    /// Create a new model config with default provider (Anthropic)
+    pub fn new(model: impl Into<String>) -> Self {
+        Self {
+            provider: LlmProvider::Anthropic,
+            model: model.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Create config for a specific provider
+    pub fn with_provider(provider: LlmProvider, model: impl Into<String>) -> Self {
+        Self {
+            provider,
+            model: model.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Set base URL (for OpenAI-compatible endpoints)
+    pub fn with_base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = Some(url.into());
+        self
+    }
+
+    /// Set the generation parameters
+    pub fn with_generation(mut self, generation: GenerationParams) -> Self {
+        self.generation = generation;
+        self
+    }
+
+    /// Set the agent ID this config belongs to
+    pub fn with_agent(mut self, agent_id: impl Into<String>) -> Self {
+        self.agent_id = Some(agent_id.into());
+        self
+    }
+
+    /// Attribute calls made with this config to a feature
+    pub fn with_feature(mut self, feature_id: impl Into<String>) -> Self {
+        self.feature_id = Some(feature_id.into());
+        self
+    }
+
+    /// Report token usage of every call to `sink`
+    pub fn with_usage_sink(mut self, sink: Arc<dyn UsageSink>) -> Self {
+        self.usage_sink = Some(sink);
+        self
+    }
+
+    /// Set the retry policy
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Add a fallback provider/model (tried in the order added)
+    pub fn with_fallback(mut self, fallback: FallbackModel) -> Self {
+        self.fallbacks.push(fallback);
+        self
+    }
+
+    /// Notify `observer` before every retry or fallback
+    pub fn with_retry_observer(mut self, observer: Arc<dyn RetryObserver>) -> Self {
+        self.retry_observer = Some(observer);
+        self
+    }
+
+    /// Serve responses from `cache` and store new ones in it
+    pub fn with_response_cache(mut self, cache: Arc<dyn ResponseCache>) -> Self {
+        self.response_cache = Some(cache);
+        self
+    }
+
+    /// Report every exchange (prompts, tool calls, responses) to `sink`
+    pub fn with_transcript_sink(mut self, sink: Arc<dyn TranscriptSink>) -> Self {
+        self.transcript_sink = Some(sink);
+        self
+    }
+
+    /// Limit re-prompts for structured output that fails validation
+    pub fn with_max_repairs(mut self, max_repairs: u32) -> Self {
+        self.max_repairs = Some(max_repairs);
+        self
+    }
+
+    /// Notify `observer` before every repair re-prompt
+    pub fn with_repair_observer(mut self, observer: Arc<dyn RepairObserver>) -> Self {
+        self.repair_observer = Some(observer);
+        self
+    }
+
+    /// Fail calls made with this config once `token` is cancelled
+    pub fn with_cancel(mut self, token: CancellationToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// Config for a fallback: same agent, feature and sinks, no further fallbacks
+    pub fn for_fallback(&self, fallback: &FallbackModel) -> Self {
+        Self {
+            provider: fallback.provider.clone(),
+            model: fallback.model.clone(),
+            base_url: fallback.base_url.clone(),
+            fallbacks: Vec::new(),
+            ..self.clone()
+        }
+    }
+
+    /// Replay from (Mock provider) or record into a cassette
+    pub fn with_cassette(mut self, cassette: CassetteConfig) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
+    /// Create an LLM client based on the configured provider
+    ///
+    /// Resolves the provider through the global [`registry`]; built-in providers
+    /// use `from_env()` to load API keys from environment variables.
+    pub fn create_llm(&self) -> anyhow::Result<Box<dyn BaseLlm + Send + Sync>> {
+        let llm = registry::resolve(self, ProviderCapabilities::default())?;
+        Ok(llm.into_inner())
+    }
+
+    /// Legacy: Create an Anthropic LLM client (for backward compatibility)
+    pub fn to_anthropic_llm(&self) -> anyhow::Result<AnthropicLlm> {
+        Ok(AnthropicLlm::from_env(&self.model)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config() {
+        let config = ModelConfig::default();
+        assert_eq!(config.provider, LlmProvider::Anthropic);
+        assert!(config.model.contains("claude"));
+    }
+
+    #[test]
+    fn test_provider_display_names() {
+        assert_eq!(LlmProvider::Anthropic.display_name(), "Anthropic");
+        assert_eq!(LlmProvider::OpenAI.display_name(), "OpenAI");
+    }
+
+    #[test]
+    fn test_base_url_support() {
+        assert!(LlmProvider::OpenAI.supports_base_url());
+        assert!(LlmProvider::Local.supports_base_url());
+        assert!(!LlmProvider::Anthropic.supports_base_url());
+    }
+
+    #[test]
+    fn test_mock_provider_requires_cassette() {
+        let config = ModelConfig::with_provider(LlmProvider::Mock, "replay");
+        assert!(config.create_llm().is_err());
+
+        let json = serde_json::to_string(&LlmProvider::Mock).unwrap();
+        assert_eq!(json, "\"mock\"");
+    }
+
+    #[test]
+    fn test_provider_name_roundtrip() {
+        for provider in LlmProvider::all() {
+            let json = serde_json::to_string(&provider).unwrap();
+            let back: LlmProvider = serde_json::from_str(&json).unwrap();
+            assert_eq!(back, provider);
+        }
+
+        let custom: LlmProvider = serde_json::from_str("\"acme\"").unwrap();
+        assert_eq!(custom, LlmProvider::Custom("acme".to_string()));
+        assert_eq!(serde_json::to_string(&custom).unwrap(), "\"acme\"");
+        assert!(!custom.supports_base_url());
+    }
+
+    #[test]
+    fn test_model_config_serialization() {
+        let config = ModelConfig::with_provider(LlmProvider::OpenAI, "gpt-4o");
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(json.contains("openai"));
+        assert!(json.contains("gpt-4o"));
+    }
+}
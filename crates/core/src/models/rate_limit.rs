@@ -0,0 +1,398 @@
+//! # Rate Limiting
+//!
+//! One limiter per provider, shared by every client the
+//! [`registry`](super::registry) builds in this process.
+//!
+//! Drafting, research and parallel feature builds all call the same providers
+//! at once. Each call waits in its provider's [`RateLimiter`] until it fits
+//! within the requests-per-minute, tokens-per-minute and in-flight limits set
+//! with [`configure`] or [`configure_all`]. Limits belong to the process, not
+//! to a run, so they are set from process-wide settings. Token usage is counted when a response arrives, so a
+//! burst can overshoot the token limit by the calls already in flight.
+
+use async_trait::async_trait;
+use radkit::errors::AgentResult;
+use radkit::models::{BaseLlm, LlmResponse, Thread};
+use radkit::tools::BaseToolset;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::time::Instant;
+
+/// Wait before re-checking when only the in-flight limit blocks (release wakes earlier)
+const IN_FLIGHT_POLL: Duration = Duration::from_secs(1);
+
+/// Limits for one provider (unset = unlimited)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RateLimit {
+    pub requests_per_minute: Option<u32>,
+    /// Input plus output tokens
+    pub tokens_per_minute: Option<u64>,
+    /// Concurrent calls
+    pub max_in_flight: Option<usize>,
+}
+
+/// Current load of a provider's limiter
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RateLimiterStats {
+    pub provider: String,
+    /// Calls waiting for a slot
+    pub queued: usize,
+    pub in_flight: usize,
+    pub requests_last_minute: usize,
+    pub tokens_last_minute: u64,
+    pub limit: RateLimit,
+}
+
+#[derive(Debug, Default)]
+struct LimiterState {
+    limit: RateLimit,
+    requests: VecDeque<Instant>,
+    tokens: VecDeque<(Instant, u64)>,
+    in_flight: usize,
+    queued: usize,
+}
+
+impl LimiterState {
+    fn prune(&mut self, now: Instant, window: Duration) {
+        while self
+            .requests
+            .front()
+            .is_some_and(|at| now.duration_since(*at) >= window)
+        {
+            self.requests.pop_front();
+        }
+        while self
+            .tokens
+            .front()
+            .is_some_and(|(at, _)| now.duration_since(*at) >= window)
+        {
+            self.tokens.pop_front();
+        }
+    }
+
+    fn tokens_in_window(&self) -> u64 {
+        self.tokens.iter().map(|(_, tokens)| tokens).sum()
+    }
+
+    /// How long to wait before another call may start (`None` = start now)
+    fn wait_time(&self, now: Instant, window: Duration) -> Option<Duration> {
+        let until_expired = |at: Instant| (at + window).saturating_duration_since(now);
+
+        if let Some(rpm) = self.limit.requests_per_minute {
+            if self.requests.len() >= rpm as usize {
+                return self.requests.front().map(|at| until_expired(*at));
+            }
+        }
+        if let Some(tpm) = self.limit.tokens_per_minute {
+            if self.tokens_in_window() >= tpm {
+                return self.tokens.front().map(|(at, _)| until_expired(*at));
+            }
+        }
+        if let Some(max) = self.limit.max_in_flight {
+            if self.in_flight >= max.max(1) {
+                return Some(IN_FLIGHT_POLL);
+            }
+        }
+        None
+    }
+}
+
+/// Request, token and concurrency limiter for one provider
+#[derive(Debug)]
+pub struct RateLimiter {
+    provider: String,
+    window: Duration,
+    state: Mutex<LimiterState>,
+    released: Notify,
+}
+
+impl RateLimiter {
+    pub fn new(provider: impl Into<String>, limit: RateLimit) -> Self {
+        Self {
+            provider: provider.into(),
+            window: Duration::from_secs(60),
+            state: Mutex::new(LimiterState {
+                limit,
+                ..LimiterState::default()
+            }),
+            released: Notify::new(),
+        }
+    }
+
+    /// Count requests and tokens over `window` instead of a minute
+    pub fn with_window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, LimiterState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Replace the limits (waiting calls re-check immediately)
+    pub fn set_limit(&self, limit: RateLimit) {
+        self.state().limit = limit;
+        self.released.notify_waiters();
+    }
+
+    /// Wait for a slot; the call counts as in flight until the permit is dropped
+    pub async fn acquire(self: &Arc<Self>) -> RatePermit {
+        let mut queue_slot = None;
+        loop {
+            let released = self.released.notified();
+            tokio::pin!(released);
+            released.as_mut().enable();
+
+            let wait = {
+                let mut state = self.state();
+                let now = Instant::now();
+                state.prune(now, self.window);
+                match state.wait_time(now, self.window) {
+                    Some(wait) => wait,
+                    None => {
+                        state.in_flight += 1;
+                        state.requests.push_back(now);
+                        drop(state);
+                        drop(queue_slot);
+                        return RatePermit {
+                            limiter: Arc::clone(self),
+                        };
+                    }
+                }
+            };
+
+            if queue_slot.is_none() {
+                self.state().queued += 1;
+                queue_slot = Some(QueueSlot(Arc::clone(self)));
+            }
+            tokio::select! {
+                _ = &mut released => {}
+                _ = tokio::time::sleep(wait) => {}
+            }
+        }
+    }
+
+    pub fn stats(&self) -> RateLimiterStats {
+        let mut state = self.state();
+        state.prune(Instant::now(), self.window);
+        RateLimiterStats {
+            provider: self.provider.clone(),
+            queued: state.queued,
+            in_flight: state.in_flight,
+            requests_last_minute: state.requests.len(),
+            tokens_last_minute: state.tokens_in_window(),
+            limit: state.limit.clone(),
+        }
+    }
+}
+
+/// Marks a call as waiting; leaves the queue when dropped (including on cancellation)
+struct QueueSlot(Arc<RateLimiter>);
+
+impl Drop for QueueSlot {
+    fn drop(&mut self) {
+        let mut state = self.0.state();
+        state.queued = state.queued.saturating_sub(1);
+    }
+}
+
+/// A started call; frees its in-flight slot when dropped
+pub struct RatePermit {
+    limiter: Arc<RateLimiter>,
+}
+
+impl RatePermit {
+    /// Count the tokens the call used against the token limit
+    pub fn record_tokens(&self, tokens: u64) {
+        if tokens > 0 {
+            self.limiter
+                .state()
+                .tokens
+                .push_back((Instant::now(), tokens));
+        }
+    }
+}
+
+impl Drop for RatePermit {
+    fn drop(&mut self) {
+        {
+            let mut state = self.limiter.state();
+            state.in_flight = state.in_flight.saturating_sub(1);
+        }
+        self.limiter.released.notify_waiters();
+    }
+}
+
+fn limiters() -> &'static Mutex<HashMap<String, Arc<RateLimiter>>> {
+    static LIMITERS: OnceLock<Mutex<HashMap<String, Arc<RateLimiter>>>> = OnceLock::new();
+    LIMITERS.get_or_init(Default::default)
+}
+
+/// Process-wide limiter for a provider (unlimited until configured)
+pub fn limiter(provider: &str) -> Arc<RateLimiter> {
+    let mut limiters = limiters().lock().unwrap_or_else(|e| e.into_inner());
+    Arc::clone(
+        limiters
+            .entry(provider.to_string())
+            .or_insert_with(|| Arc::new(RateLimiter::new(provider, RateLimit::default()))),
+    )
+}
+
+/// Set the limits for a provider
+pub fn configure(provider: &str, limit: RateLimit) {
+    limiter(provider).set_limit(limit);
+}
+
+/// Replace the limits of every provider
+///
+/// Providers missing from `limits` go back to unlimited.
+pub fn configure_all(limits: &HashMap<String, RateLimit>) {
+    let existing: Vec<Arc<RateLimiter>> = limiters()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .values()
+        .cloned()
+        .collect();
+    for limiter in existing {
+        if !limits.contains_key(&limiter.provider) {
+            limiter.set_limit(RateLimit::default());
+        }
+    }
+    for (provider, limit) in limits {
+        configure(provider, limit.clone());
+    }
+}
+
+/// Load of every provider used so far, by provider name
+pub fn stats() -> Vec<RateLimiterStats> {
+    let limiters: Vec<Arc<RateLimiter>> = limiters()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .values()
+        .cloned()
+        .collect();
+    let mut stats: Vec<RateLimiterStats> = limiters.iter().map(|l| l.stats()).collect();
+    stats.sort_by(|a, b| a.provider.cmp(&b.provider));
+    stats
+}
+
+/// LLM client wrapper that waits for its provider's rate limiter
+pub struct RateLimitedLlm<L> {
+    inner: L,
+    limiter: Arc<RateLimiter>,
+}
+
+impl<L: BaseLlm> RateLimitedLlm<L> {
+    pub fn new(inner: L, limiter: Arc<RateLimiter>) -> Self {
+        Self { inner, limiter }
+    }
+}
+
+#[async_trait]
+impl<L: BaseLlm> BaseLlm for RateLimitedLlm<L> {
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+
+    async fn generate_content(
+        &self,
+        thread: Thread,
+        toolset: Option<Arc<dyn BaseToolset>>,
+    ) -> AgentResult<LlmResponse> {
+        let permit = self.limiter.acquire().await;
+        let response = self.inner.generate_content(thread, toolset).await?;
+        let usage = response.usage();
+        permit.record_tokens(u64::from(usage.input_tokens()) + u64::from(usage.output_tokens()));
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_max_in_flight_queues_callers() {
+        let limiter = Arc::new(RateLimiter::new(
+            "test",
+            RateLimit {
+                max_in_flight: Some(1),
+                ..RateLimit::default()
+            },
+        ));
+
+        let first = limiter.acquire().await;
+        let waiting = tokio::spawn({
+            let limiter = Arc::clone(&limiter);
+            async move {
+                let _permit = limiter.acquire().await;
+            }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let stats = limiter.stats();
+        assert_eq!((stats.in_flight, stats.queued), (1, 1));
+
+        drop(first);
+        tokio::time::timeout(Duration::from_millis(500), waiting)
+            .await
+            .expect("released permit should wake the queued call")
+            .unwrap();
+        let stats = limiter.stats();
+        assert_eq!((stats.in_flight, stats.queued), (0, 0));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_request_and_token_windows() {
+        let limiter = Arc::new(
+            RateLimiter::new(
+                "test",
+                RateLimit {
+                    requests_per_minute: Some(2),
+                    tokens_per_minute: Some(1000),
+                    ..RateLimit::default()
+                },
+            )
+            .with_window(Duration::from_millis(200)),
+        );
+
+        let started = Instant::now();
+        drop(limiter.acquire().await);
+        drop(limiter.acquire().await);
+        assert_eq!(started.elapsed(), Duration::ZERO);
+        assert_eq!(limiter.stats().requests_last_minute, 2);
+
+        // Third request waits for the window to roll over
+        drop(limiter.acquire().await);
+        assert!(started.elapsed() >= Duration::from_millis(200));
+
+        // Token budget spent: the next call waits as well
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        limiter.acquire().await.record_tokens(1500);
+        let before = Instant::now();
+        drop(limiter.acquire().await);
+        assert!(before.elapsed() >= Duration::from_millis(200));
+    }
+
+    #[test]
+    fn test_configure_all_replaces_every_limit() {
+        let limit = |rpm| RateLimit {
+            requests_per_minute: Some(rpm),
+            ..RateLimit::default()
+        };
+        configure("test-configure-kept", limit(10));
+        configure("test-configure-dropped", limit(20));
+
+        let limits = HashMap::from([("test-configure-kept".to_string(), limit(30))]);
+        configure_all(&limits);
+
+        assert_eq!(limiter("test-configure-kept").stats().limit, limit(30));
+        assert_eq!(
+            limiter("test-configure-dropped").stats().limit,
+            RateLimit::default()
+        );
+    }
+}
@@ -0,0 +1,595 @@
+//! # Provider Registry
+//!
+//! Single place where LLM providers are registered and LLM clients are built.
+//!
+//! Each provider registers once with a name, a constructor and its capabilities.
+//! `ModelConfig::create_llm`, the `run_llm_function!`/`run_llm_worker!` macros and
+//! `BuilderSkill` all resolve clients through [`resolve`], so adding a provider
+//! no longer means editing every call site.
+//!
+//! ## Registering a custom provider
+//! ```rust,ignore
+//! use catalyst_core::models::registry::{self, ProviderCapabilities, ProviderSpec};
+//! use catalyst_core::models::{LlmProvider, ModelConfig};
+//!
+//! registry::register(
+//!     ProviderSpec::new("acme", |config: &ModelConfig| AcmeLlm::from_env(&config.model))
+//!         .with_display_name("Acme")
+//!         .with_default_model("acme-large")
+//!         .with_api_key_env("ACME_API_KEY")
+//!         .with_capabilities(ProviderCapabilities::all()),
+//! );
+//!
+//! let config = ModelConfig::with_provider(LlmProvider::from("acme"), "acme-large");
+//! let llm = config.create_llm()?;
+//! ```
+
+use async_trait::async_trait;
+use radkit::errors::{AgentError, AgentResult};
+use radkit::models::providers::{
+    AnthropicLlm, DeepSeekLlm, GeminiLlm, GrokLlm, OpenAILlm, OpenRouterLlm,
+};
+use radkit::models::{BaseLlm, LlmResponse, Thread};
+use radkit::tools::BaseToolset;
+use serde::Serialize;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use super::cache::CachedLlm;
+use super::cancel::CancellableLlm;
+use super::generation::{ApplyGeneration, StopSequenceLlm};
+use super::rate_limit::{self, RateLimitedLlm};
+use super::retry::RetryingLlm;
+use super::transcript::TranscribedLlm;
+use super::usage::MeteredLlm;
+use super::{local, replay, ModelConfig};
+
+/// Boxed LLM client as produced by a provider constructor
+pub type BoxedLlm = Box<dyn BaseLlm + Send + Sync>;
+
+type LlmConstructor = Arc<dyn Fn(&ModelConfig) -> AgentResult<BoxedLlm> + Send + Sync>;
+
+/// What a provider supports
+#[derive(Debug, Clone, Copy, Default, Serialize, PartialEq, Eq)]
+pub struct ProviderCapabilities {
+    /// Tool/function calling (required by `LlmWorker` skills)
+    pub tools: bool,
+    /// Custom endpoint via `ModelConfig::base_url`
+    pub base_url: bool,
+    /// Typed JSON output (required by `LlmFunction` skills)
+    pub structured_output: bool,
+}
+
+impl ProviderCapabilities {
+    /// Tools and structured output, no base URL
+    pub fn all() -> Self {
+        Self {
+            tools: true,
+            base_url: false,
+            structured_output: true,
+        }
+    }
+
+    /// Requirements of `LlmFunction` skills
+    pub fn structured() -> Self {
+        Self {
+            structured_output: true,
+            ..Self::default()
+        }
+    }
+
+    /// Requirements of `LlmWorker` skills
+    pub fn tool_calling() -> Self {
+        Self {
+            tools: true,
+            structured_output: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_base_url(mut self) -> Self {
+        self.base_url = true;
+        self
+    }
+
+    /// Names of the capabilities in `required` that this provider lacks
+    pub fn missing(&self, required: &ProviderCapabilities) -> Vec<&'static str> {
+        let mut missing = Vec::new();
+        if required.tools && !self.tools {
+            missing.push("tools");
+        }
+        if required.base_url && !self.base_url {
+            missing.push("base_url");
+        }
+        if required.structured_output && !self.structured_output {
+            missing.push("structured_output");
+        }
+        missing
+    }
+}
+
+/// A registered provider
+#[derive(Clone)]
+pub struct ProviderSpec {
+    /// Stable ID used in configs and the API (e.g. "anthropic")
+    pub name: String,
+    /// Name shown in the UI
+    pub display_name: String,
+    /// Model used when no model is configured
+    pub default_model: String,
+    /// Environment variable holding the API key, if any
+    pub api_key_env: Option<String>,
+    pub capabilities: ProviderCapabilities,
+    constructor: LlmConstructor,
+}
+
+impl std::fmt::Debug for ProviderSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProviderSpec")
+            .field("name", &self.name)
+            .field("display_name", &self.display_name)
+            .field("default_model", &self.default_model)
+            .field("api_key_env", &self.api_key_env)
+            .field("capabilities", &self.capabilities)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ProviderSpec {
+    /// Create a spec from a name and a constructor returning any `BaseLlm`
+    pub fn new<L, F>(name: impl Into<String>, constructor: F) -> Self
+    where
+        L: BaseLlm + Send + Sync + 'static,
+        F: Fn(&ModelConfig) -> AgentResult<L> + Send + Sync + 'static,
+    {
+        let name = name.into();
+        Self {
+            display_name: name.clone(),
+            name,
+            default_model: String::new(),
+            api_key_env: None,
+            capabilities: ProviderCapabilities::default(),
+            constructor: Arc::new(move |config| {
+                constructor(config).map(|llm| Box::new(llm) as BoxedLlm)
+            }),
+        }
+    }
+
+    pub fn with_display_name(mut self, display_name: impl Into<String>) -> Self {
+        self.display_name = display_name.into();
+        self
+    }
+
+    pub fn with_default_model(mut self, model: impl Into<String>) -> Self {
+        self.default_model = model.into();
+        self
+    }
+
+    pub fn with_api_key_env(mut self, env_var: impl Into<String>) -> Self {
+        self.api_key_env = Some(env_var.into());
+        self
+    }
+
+    pub fn with_capabilities(mut self, capabilities: ProviderCapabilities) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    /// Build a client for `config`
+    pub fn build(&self, config: &ModelConfig) -> AgentResult<BoxedLlm> {
+        (self.constructor)(config)
+    }
+}
+
+/// Ordered set of providers (registration order is the UI order)
+#[derive(Default)]
+pub struct ProviderRegistry {
+    providers: RwLock<Vec<Arc<ProviderSpec>>>,
+}
+
+impl ProviderRegistry {
+    /// Empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry pre-populated with the built-in providers
+    pub fn with_builtins() -> Self {
+        let registry = Self::new();
+        for spec in builtin_providers() {
+            registry.register(spec);
+        }
+        registry
+    }
+
+    /// Register a provider, replacing any existing provider with the same name
+    pub fn register(&self, spec: ProviderSpec) {
+        let mut providers = self.providers.write().unwrap_or_else(|e| e.into_inner());
+        let spec = Arc::new(spec);
+        match providers.iter_mut().find(|p| p.name == spec.name) {
+            Some(existing) => *existing = spec,
+            None => providers.push(spec),
+        }
+    }
+
+    /// Look up a provider by name
+    pub fn get(&self, name: &str) -> Option<Arc<ProviderSpec>> {
+        self.providers
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .find(|p| p.name == name)
+            .cloned()
+    }
+
+    /// All registered providers
+    pub fn list(&self) -> Vec<Arc<ProviderSpec>> {
+        self.providers
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Build a client for `config`, checking the provider has `required` capabilities
+    ///
+    /// The client records into the config's cassette when one is set in record mode,
+    /// and reports exchanges and token usage to the config's transcript and usage
+    /// sinks if any. Unless the retry
+    /// policy allows a single attempt and there are no fallbacks, the client is a
+    /// [`RetryingLlm`] over the primary and every fallback that could be built.
+    /// With a response cache, cached responses are served before any of that.
+    /// A config with a cancellation token gets a client that stops mid-call
+    /// when the token is cancelled.
+    pub fn resolve(
+        &self,
+        config: &ModelConfig,
+        required: ProviderCapabilities,
+    ) -> AgentResult<RegisteredLlm> {
+        let llm = self.resolve_with_fallbacks(config, required)?;
+        let llm = match &config.response_cache {
+            Some(cache) => RegisteredLlm(Box::new(CachedLlm::new(llm, Arc::clone(cache), config))),
+            None => llm,
+        };
+        match &config.cancel {
+            Some(token) => Ok(RegisteredLlm(Box::new(CancellableLlm::new(
+                llm,
+                token.clone(),
+            )))),
+            None => Ok(llm),
+        }
+    }
+
+    /// Primary client, wrapped with retries and fallbacks when configured
+    ///
+    /// A candidate that cannot be built (unknown provider, missing API key,
+    /// missing capabilities) is skipped with a warning, the primary included;
+    /// resolving fails only when no candidate can be built.
+    fn resolve_with_fallbacks(
+        &self,
+        config: &ModelConfig,
+        required: ProviderCapabilities,
+    ) -> AgentResult<RegisteredLlm> {
+        if config.retry.max_attempts <= 1 && config.fallbacks.is_empty() {
+            return self.build_one(config, required);
+        }
+
+        let mut retrying = RetryingLlm::new(
+            config.retry.clone(),
+            config.agent_id.as_deref().unwrap_or("unknown"),
+        )
+        .with_observer(config.retry_observer.clone());
+        let mut failures = Vec::new();
+
+        let candidates = std::iter::once((config.clone(), "primary")).chain(
+            config
+                .fallbacks
+                .iter()
+                .map(|fallback| (config.for_fallback(fallback), "fallback")),
+        );
+        for (candidate, role) in candidates {
+            match self.build_one(&candidate, required) {
+                Ok(llm) => {
+                    retrying =
+                        retrying.with_candidate(candidate.provider.as_str(), llm.into_inner())
+                }
+                Err(e) if config.fallbacks.is_empty() => return Err(e),
+                Err(e) => {
+                    tracing::warn!(
+                        "Skipping {} {}/{}: {}",
+                        role,
+                        candidate.provider,
+                        candidate.model,
+                        e
+                    );
+                    failures.push(format!("{}/{}: {}", candidate.provider, candidate.model, e));
+                }
+            }
+        }
+
+        if failures.len() > config.fallbacks.len() {
+            return Err(AgentError::Internal {
+                component: "provider_registry".to_string(),
+                reason: format!("No model could be built: {}", failures.join("; ")),
+            });
+        }
+        Ok(RegisteredLlm(Box::new(retrying)))
+    }
+
+    /// Build a single provider client (no retries or fallbacks)
+    ///
+    /// Every call waits for the provider's shared [`rate_limit::limiter`]. Stop
+    /// sequences from the config's generation parameters are enforced here; the
+    /// provider constructor applies the rest.
+    fn build_one(
+        &self,
+        config: &ModelConfig,
+        required: ProviderCapabilities,
+    ) -> AgentResult<RegisteredLlm> {
+        let name = config.provider.as_str();
+        let spec = self.get(name).ok_or_else(|| AgentError::Internal {
+            component: "provider_registry".to_string(),
+            reason: format!("Unknown LLM provider '{}' (not registered)", name),
+        })?;
+
+        let missing = spec.capabilities.missing(&required);
+        if !missing.is_empty() {
+            return Err(AgentError::Internal {
+                component: "provider_registry".to_string(),
+                reason: format!(
+                    "Provider '{}' does not support: {}",
+                    name,
+                    missing.join(", ")
+                ),
+            });
+        }
+
+        let llm = RegisteredLlm(Box::new(RateLimitedLlm::new(
+            RegisteredLlm(spec.build(config)?),
+            rate_limit::limiter(name),
+        )));
+        let llm = if config.generation.stop_sequences.is_empty() {
+            llm
+        } else {
+            RegisteredLlm(Box::new(StopSequenceLlm::new(
+                llm,
+                config.generation.stop_sequences.clone(),
+            )))
+        };
+        let llm = RegisteredLlm(Box::new(replay::record(llm, config)?));
+        let llm = match &config.transcript_sink {
+            Some(sink) => {
+                RegisteredLlm(Box::new(TranscribedLlm::new(llm, Arc::clone(sink), config)))
+            }
+            None => llm,
+        };
+        match &config.usage_sink {
+            Some(sink) => Ok(RegisteredLlm(Box::new(MeteredLlm::new(
+                llm,
+                Arc::clone(sink),
+                config,
+            )))),
+            None => Ok(llm),
+        }
+    }
+}
+
+/// Process-wide registry used by skills and `ModelConfig::create_llm`
+pub fn global() -> &'static ProviderRegistry {
+    static REGISTRY: OnceLock<ProviderRegistry> = OnceLock::new();
+    REGISTRY.get_or_init(ProviderRegistry::with_builtins)
+}
+
+/// Register a provider in the global registry
+pub fn register(spec: ProviderSpec) {
+    global().register(spec);
+}
+
+/// Resolve a client from the global registry
+pub fn resolve(config: &ModelConfig, required: ProviderCapabilities) -> AgentResult<RegisteredLlm> {
+    global().resolve(config, required)
+}
+
+/// Concrete `BaseLlm` around a registry-built client
+///
+/// `LlmFunction` and `LlmWorker` need a sized `BaseLlm`, not a trait object.
+pub struct RegisteredLlm(BoxedLlm);
+
+impl RegisteredLlm {
+    pub fn into_inner(self) -> BoxedLlm {
+        self.0
+    }
+}
+
+#[async_trait]
+impl BaseLlm for RegisteredLlm {
+    fn model_name(&self) -> &str {
+        self.0.model_name()
+    }
+
+    async fn generate_content(
+        &self,
+        thread: Thread,
+        toolset: Option<Arc<dyn BaseToolset>>,
+    ) -> AgentResult<LlmResponse> {
+        self.0.generate_content(thread, toolset).await
+    }
+}
+
+fn builtin_providers() -> Vec<ProviderSpec> {
+    vec![
+        ProviderSpec::new("anthropic", |c: &ModelConfig| {
+            Ok(AnthropicLlm::from_env(&c.model)?.apply_generation(&c.generation))
+        })
+        .with_display_name("Anthropic")
+        .with_default_model("claude-sonnet-4-20250514")
+        .with_api_key_env("ANTHROPIC_API_KEY")
+        .with_capabilities(ProviderCapabilities::all()),
+        ProviderSpec::new("openai", |c: &ModelConfig| {
+            let llm = OpenAILlm::from_env(&c.model)?.apply_generation(&c.generation);
+            Ok(match &c.base_url {
+                Some(base_url) => llm.with_base_url(base_url),
+                None => llm,
+            })
+        })
+        .with_display_name("OpenAI")
+        .with_default_model("gpt-4o")
+        .with_api_key_env("OPENAI_API_KEY")
+        .with_capabilities(ProviderCapabilities::all().with_base_url()),
+        ProviderSpec::new("gemini", |c: &ModelConfig| {
+            Ok(GeminiLlm::from_env(&c.model)?.apply_generation(&c.generation))
+        })
+        .with_display_name("Gemini")
+        .with_default_model("gemini-2.0-flash-exp")
+        .with_api_key_env("GEMINI_API_KEY")
+        .with_capabilities(ProviderCapabilities::all()),
+        ProviderSpec::new("openrouter", |c: &ModelConfig| {
+            Ok(OpenRouterLlm::from_env(&c.model)?.apply_generation(&c.generation))
+        })
+        .with_display_name("OpenRouter")
+        .with_default_model("anthropic/claude-3.5-sonnet")
+        .with_api_key_env("OPENROUTER_API_KEY")
+        .with_capabilities(ProviderCapabilities::all()),
+        ProviderSpec::new("grok", |c: &ModelConfig| {
+            Ok(GrokLlm::from_env(&c.model)?.apply_generation(&c.generation))
+        })
+        .with_display_name("Grok")
+        .with_default_model("grok-2")
+        .with_api_key_env("XAI_API_KEY")
+        .with_capabilities(ProviderCapabilities::all()),
+        ProviderSpec::new("deepseek", |c: &ModelConfig| {
+            Ok(DeepSeekLlm::from_env(&c.model)?.apply_generation(&c.generation))
+        })
+        .with_display_name("DeepSeek")
+        .with_default_model("deepseek-chat")
+        .with_api_key_env("DEEPSEEK_API_KEY")
+        .with_capabilities(ProviderCapabilities::all()),
+        ProviderSpec::new("local", local::local_llm)
+            .with_display_name("Local (OpenAI-compatible)")
+            .with_default_model("llama3.2")
+            .with_api_key_env(local::LOCAL_API_KEY_ENV)
+            .with_capabilities(ProviderCapabilities::all().with_base_url()),
+        ProviderSpec::new("mock", replay::ReplayLlm::from_config)
+            .with_display_name("Mock (Replay)")
+            .with_default_model("replay")
+            .with_capabilities(ProviderCapabilities::all()),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{FallbackModel, LlmProvider};
+    use radkit::models::{Content, TokenUsage};
+
+    struct EchoLlm {
+        model: String,
+    }
+
+    #[async_trait]
+    impl BaseLlm for EchoLlm {
+        fn model_name(&self) -> &str {
+            &self.model
+        }
+
+        async fn generate_content(
+            &self,
+            _thread: Thread,
+            _toolset: Option<Arc<dyn BaseToolset>>,
+        ) -> AgentResult<LlmResponse> {
+            Ok(LlmResponse::new(
+                Content::from_text("echo"),
+                TokenUsage::empty(),
+            ))
+        }
+    }
+
+    fn echo_spec(capabilities: ProviderCapabilities) -> ProviderSpec {
+        ProviderSpec::new("echo", |c: &ModelConfig| {
+            Ok(EchoLlm {
+                model: c.model.clone(),
+            })
+        })
+        .with_capabilities(capabilities)
+    }
+
+    #[test]
+    fn test_builtins_registered_in_order() {
+        let names: Vec<String> = ProviderRegistry::with_builtins()
+            .list()
+            .iter()
+            .map(|p| p.name.clone())
+            .collect();
+        assert_eq!(names[0], "anthropic");
+        assert!(names.contains(&"local".to_string()));
+        assert!(names.contains(&"mock".to_string()));
+    }
+
+    #[test]
+    fn test_custom_provider_resolves() {
+        let registry = ProviderRegistry::new();
+        registry.register(echo_spec(ProviderCapabilities::all()));
+
+        let config = ModelConfig::with_provider(LlmProvider::from("echo"), "echo-1");
+        let llm = registry
+            .resolve(&config, ProviderCapabilities::tool_calling())
+            .unwrap();
+        assert_eq!(llm.model_name(), "echo-1");
+    }
+
+    #[test]
+    fn test_missing_capabilities_rejected() {
+        let registry = ProviderRegistry::new();
+        registry.register(echo_spec(ProviderCapabilities::structured()));
+
+        let config = ModelConfig::with_provider(LlmProvider::from("echo"), "echo-1");
+        assert!(registry
+            .resolve(&config, ProviderCapabilities::structured())
+            .is_ok());
+        let err = registry
+            .resolve(&config, ProviderCapabilities::tool_calling())
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("tools"));
+
+        let unknown = ModelConfig::with_provider(LlmProvider::from("nope"), "x");
+        assert!(registry
+            .resolve(&unknown, ProviderCapabilities::default())
+            .is_err());
+    }
+
+    #[test]
+    fn test_unbuildable_candidates_are_skipped() {
+        let registry = ProviderRegistry::new();
+        registry.register(echo_spec(ProviderCapabilities::all()));
+
+        let config = ModelConfig::with_provider(LlmProvider::from("echo"), "echo-1")
+            .with_fallback(FallbackModel::new(LlmProvider::from("nope"), "x"));
+        let llm = registry
+            .resolve(&config, ProviderCapabilities::default())
+            .unwrap();
+        assert_eq!(llm.model_name(), "echo-1");
+
+        // A primary that cannot be built falls through to a working fallback
+        let config = ModelConfig::with_provider(LlmProvider::from("nope"), "x")
+            .with_fallback(FallbackModel::new(LlmProvider::from("echo"), "echo-1"));
+        let llm = registry
+            .resolve(&config, ProviderCapabilities::default())
+            .unwrap();
+        assert_eq!(llm.model_name(), "echo-1");
+
+        let config = ModelConfig::with_provider(LlmProvider::from("nope"), "x")
+            .with_fallback(FallbackModel::new(LlmProvider::from("gone"), "y"));
+        assert!(registry
+            .resolve(&config, ProviderCapabilities::default())
+            .is_err());
+    }
+
+    #[test]
+    fn test_register_replaces_by_name() {
+        let registry = ProviderRegistry::new();
+        registry.register(echo_spec(ProviderCapabilities::default()));
+        registry.register(echo_spec(ProviderCapabilities::all()));
+
+        assert_eq!(registry.list().len(), 1);
+        assert!(registry.get("echo").unwrap().capabilities.tools);
+    }
+}
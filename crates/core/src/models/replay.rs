@@ -0,0 +1,480 @@
+//! # Replay Provider
+//!
+//! Offline LLM provider backed by a cassette file under `.catalyst/cassettes/`.
+//!
+//! A cassette is recorded once against a real provider (any `ModelConfig` with a
+//! cassette in [`CassetteMode::Record`]) and replayed later with
+//! [`LlmProvider::Mock`](super::LlmProvider::Mock), so the whole swarm can run
+//! in CI without API keys.
+//!
+//! ## Keying
+//!
+//! Entries are keyed by agent ID, a hash of the initial prompt (system prompt plus
+//! first user message), and the turn within the conversation. Keying on the initial
+//! prompt rather than the full thread keeps tool-calling workers replayable even
+//! when tool results (web search, `cargo check`) differ between record and replay.
+//! Repeated identical calls (e.g. the architect re-running after a rejection) are
+//! distinguished by a per-key call counter.
+//!
+//! Hand-written (scripted) entries may use `"*"` as the prompt hash to match any
+//! prompt for that agent and turn.
+
+use async_trait::async_trait;
+use radkit::errors::{AgentError, AgentResult};
+use radkit::models::{BaseLlm, LlmResponse, Role, Thread};
+use radkit::tools::BaseToolset;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use super::ModelConfig;
+
+/// Prompt hash that matches any prompt in scripted cassettes
+pub const WILDCARD_HASH: &str = "*";
+
+/// Agent key used when a `ModelConfig` carries no agent ID
+const DEFAULT_AGENT: &str = "default";
+
+/// Whether a cassette is being replayed or recorded
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CassetteMode {
+    /// Serve responses from the cassette (used by `LlmProvider::Mock`)
+    #[default]
+    Replay,
+    /// Call the real provider and append its responses to the cassette
+    Record,
+}
+
+/// Cassette location and mode, carried on `ModelConfig`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CassetteConfig {
+    /// Path to the cassette JSON file
+    pub path: PathBuf,
+    /// Replay or record
+    #[serde(default)]
+    pub mode: CassetteMode,
+}
+
+impl CassetteConfig {
+    /// Cassette stored as `.catalyst/cassettes/<name>.json`
+    pub fn named(name: &str, mode: CassetteMode) -> Self {
+        Self {
+            path: crate::state::io::get_runtime_path()
+                .join("cassettes")
+                .join(format!("{}.json", name)),
+            mode,
+        }
+    }
+}
+
+/// A single recorded LLM response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CassetteEntry {
+    /// Agent that made the call (e.g. "architect")
+    pub agent: String,
+    /// Hash of the initial prompt, or `"*"` for scripted entries
+    pub prompt_hash: String,
+    /// Turn within the conversation (0 = first response, >0 = after tool calls)
+    #[serde(default)]
+    pub turn: usize,
+    /// Nth identical call for this agent/prompt/turn
+    #[serde(default)]
+    pub call: usize,
+    /// The provider response, including any tool calls
+    pub response: LlmResponse,
+}
+
+/// A recorded set of LLM responses
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct Cassette {
+    #[serde(default)]
+    pub entries: Vec<CassetteEntry>,
+}
+
+impl Cassette {
+    /// Load a cassette from disk (empty if the file does not exist)
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Write the cassette to disk
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Find the entry for a call, falling back to the last recorded call and
+    /// then to a wildcard entry
+    pub fn find(
+        &self,
+        agent: &str,
+        prompt_hash: &str,
+        turn: usize,
+        call: usize,
+    ) -> Option<&CassetteEntry> {
+        self.find_exact(agent, prompt_hash, turn, call)
+            .or_else(|| self.find_exact(agent, WILDCARD_HASH, turn, call))
+    }
+
+    fn find_exact(
+        &self,
+        agent: &str,
+        prompt_hash: &str,
+        turn: usize,
+        call: usize,
+    ) -> Option<&CassetteEntry> {
+        let matching = self
+            .entries
+            .iter()
+            .filter(|e| e.agent == agent && e.prompt_hash == prompt_hash && e.turn == turn);
+
+        let mut last: Option<&CassetteEntry> = None;
+        for entry in matching {
+            if entry.call == call {
+                return Some(entry);
+            }
+            if last.is_none_or(|l| entry.call > l.call) {
+                last = Some(entry);
+            }
+        }
+        last
+    }
+
+    /// Insert or replace an entry
+    pub fn record(&mut self, entry: CassetteEntry) {
+        self.entries.retain(|e| {
+            !(e.agent == entry.agent
+                && e.prompt_hash == entry.prompt_hash
+                && e.turn == entry.turn
+                && e.call == entry.call)
+        });
+        self.entries.push(entry);
+    }
+}
+
+/// A cassette shared by every LLM client in the process, plus call counters
+struct CassetteHandle {
+    path: PathBuf,
+    cassette: Mutex<Cassette>,
+    calls: Mutex<HashMap<(String, String, usize), usize>>,
+}
+
+impl CassetteHandle {
+    /// Next call index for a key (0 for the first call)
+    fn next_call(&self, agent: &str, prompt_hash: &str, turn: usize) -> usize {
+        let mut calls = self.calls.lock().unwrap_or_else(|e| e.into_inner());
+        let counter = calls
+            .entry((agent.to_string(), prompt_hash.to_string(), turn))
+            .or_insert(0);
+        let call = *counter;
+        *counter += 1;
+        call
+    }
+}
+
+/// Open cassettes are shared so parallel drafters record into one file
+fn open_cassette(path: &Path) -> anyhow::Result<Arc<CassetteHandle>> {
+    static OPEN: OnceLock<Mutex<HashMap<PathBuf, Arc<CassetteHandle>>>> = OnceLock::new();
+
+    let mut open = OPEN
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+
+    if let Some(handle) = open.get(path) {
+        return Ok(Arc::clone(handle));
+    }
+
+    let handle = Arc::new(CassetteHandle {
+        path: path.to_path_buf(),
+        cassette: Mutex::new(Cassette::load(path)?),
+        calls: Mutex::new(HashMap::new()),
+    });
+    open.insert(path.to_path_buf(), Arc::clone(&handle));
+    Ok(handle)
+}
+
+/// Stable hash of the initial prompt (system prompt + first user message)
+///
+/// Uses FNV-1a so cassettes stay valid across Rust releases.
+pub fn prompt_hash(thread: &Thread) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let mut feed = |bytes: &[u8]| {
+        for byte in bytes {
+            hash ^= u64::from(*byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    };
+
+    feed(thread.system().unwrap_or_default().as_bytes());
+    if let Some(first_user) = thread
+        .events()
+        .iter()
+        .find(|e| matches!(e.role(), Role::User))
+    {
+        feed(&[0]);
+        feed(
+            first_user
+                .content()
+                .joined_texts()
+                .unwrap_or_default()
+                .as_bytes(),
+        );
+    }
+
+    format!("{:016x}", hash)
+}
+
+/// Turn index = number of assistant responses already in the thread
+fn turn_of(thread: &Thread) -> usize {
+    thread
+        .events()
+        .iter()
+        .filter(|e| matches!(e.role(), Role::Assistant))
+        .count()
+}
+
+fn cassette_error(reason: impl std::fmt::Display) -> AgentError {
+    AgentError::Internal {
+        component: "replay_llm".to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+fn agent_key(config: &ModelConfig) -> String {
+    config
+        .agent_id
+        .clone()
+        .unwrap_or_else(|| DEFAULT_AGENT.to_string())
+}
+
+/// LLM client that serves responses from a cassette
+pub struct ReplayLlm {
+    model: String,
+    agent: String,
+    handle: Arc<CassetteHandle>,
+}
+
+impl ReplayLlm {
+    /// Create a replay client from a `ModelConfig` with `LlmProvider::Mock`
+    ///
+    /// Returns `AgentResult` like the real providers' `from_env()`.
+    pub fn from_config(config: &ModelConfig) -> AgentResult<Self> {
+        let cassette = config.cassette.as_ref().ok_or_else(|| {
+            cassette_error("Mock provider requires a cassette (set ModelConfig::with_cassette)")
+        })?;
+        if !cassette.path.exists() {
+            return Err(cassette_error(format!(
+                "Cassette not found: {}",
+                cassette.path.display()
+            )));
+        }
+
+        Ok(Self {
+            model: config.model.clone(),
+            agent: agent_key(config),
+            handle: open_cassette(&cassette.path).map_err(cassette_error)?,
+        })
+    }
+}
+
+#[async_trait]
+impl BaseLlm for ReplayLlm {
+    fn model_name(&self) -> &str {
+        &self.model
+    }
+
+    async fn generate_content(
+        &self,
+        thread: Thread,
+        _toolset: Option<Arc<dyn BaseToolset>>,
+    ) -> AgentResult<LlmResponse> {
+        let hash = prompt_hash(&thread);
+        let turn = turn_of(&thread);
+        let call = self.handle.next_call(&self.agent, &hash, turn);
+
+        let cassette = self
+            .handle
+            .cassette
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+
+        cassette
+            .find(&self.agent, &hash, turn, call)
+            .map(|entry| entry.response.clone())
+            .ok_or_else(|| {
+                cassette_error(format!(
+                    "No cassette entry for agent '{}' (prompt hash {}, turn {}) in {}",
+                    self.agent,
+                    hash,
+                    turn,
+                    self.handle.path.display()
+                ))
+            })
+    }
+}
+
+/// LLM client wrapper that records responses into a cassette
+///
+/// Passes straight through when the config has no cassette in record mode.
+pub struct RecordingLlm<L> {
+    inner: L,
+    agent: String,
+    handle: Option<Arc<CassetteHandle>>,
+}
+
+/// Wrap a real provider client so its responses are recorded if configured
+pub fn record<L: BaseLlm>(inner: L, config: &ModelConfig) -> AgentResult<RecordingLlm<L>> {
+    let handle = match &config.cassette {
+        Some(cassette) if cassette.mode == CassetteMode::Record => {
+            Some(open_cassette(&cassette.path).map_err(cassette_error)?)
+        }
+        _ => None,
+    };
+
+    Ok(RecordingLlm {
+        inner,
+        agent: agent_key(config),
+        handle,
+    })
+}
+
+#[async_trait]
+impl<L: BaseLlm> BaseLlm for RecordingLlm<L> {
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+
+    async fn generate_content(
+        &self,
+        thread: Thread,
+        toolset: Option<Arc<dyn BaseToolset>>,
+    ) -> AgentResult<LlmResponse> {
+        let Some(handle) = &self.handle else {
+            return self.inner.generate_content(thread, toolset).await;
+        };
+
+        let hash = prompt_hash(&thread);
+        let turn = turn_of(&thread);
+        let response = self.inner.generate_content(thread, toolset).await?;
+        let call = handle.next_call(&self.agent, &hash, turn);
+
+        let mut cassette = handle.cassette.lock().unwrap_or_else(|e| e.into_inner());
+        cassette.record(CassetteEntry {
+            agent: self.agent.clone(),
+            prompt_hash: hash,
+            turn,
+            call,
+            response: response.clone(),
+        });
+        if let Err(e) = cassette.save(&handle.path) {
+            tracing::warn!("Failed to save cassette {}: {}", handle.path.display(), e);
+        }
+
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use radkit::models::{Content, Event, TokenUsage};
+
+    fn text_response(text: &str) -> LlmResponse {
+        LlmResponse::new(Content::from_text(text), TokenUsage::empty())
+    }
+
+    #[test]
+    fn test_prompt_hash_ignores_later_turns() {
+        let thread = Thread::from_user("Build a stock tracker").with_system("You are a parser");
+        let continued = thread
+            .clone()
+            .add_event(Event::assistant(Content::from_text("thinking")))
+            .add_event(Event::user(Content::from_text("tool output")));
+
+        assert_eq!(prompt_hash(&thread), prompt_hash(&continued));
+        assert_eq!(turn_of(&thread), 0);
+        assert_eq!(turn_of(&continued), 1);
+    }
+
+    #[test]
+    fn test_cassette_find_falls_back_to_wildcard() {
+        let mut cassette = Cassette::default();
+        cassette.record(CassetteEntry {
+            agent: "critic".to_string(),
+            prompt_hash: WILDCARD_HASH.to_string(),
+            turn: 0,
+            call: 0,
+            response: text_response("{\"verdict\":\"approved\"}"),
+        });
+
+        assert!(cassette.find("critic", "abc", 0, 0).is_some());
+        assert!(cassette.find("critic", "abc", 0, 5).is_some());
+        assert!(cassette.find("architect", "abc", 0, 0).is_none());
+    }
+
+    #[test]
+    fn test_cassette_record_replaces_same_key() {
+        let mut cassette = Cassette::default();
+        for text in ["first", "second"] {
+            cassette.record(CassetteEntry {
+                agent: "architect".to_string(),
+                prompt_hash: "h".to_string(),
+                turn: 0,
+                call: 0,
+                response: text_response(text),
+            });
+        }
+
+        assert_eq!(cassette.entries.len(), 1);
+        let entry = cassette.find("architect", "h", 0, 0).unwrap();
+        assert_eq!(entry.response.content().first_text(), Some("second"));
+    }
+
+    #[tokio::test]
+    async fn test_replay_llm_serves_recorded_calls_in_order() {
+        let path = std::env::temp_dir().join("catalyst_test_replay_cassette.json");
+        let thread = Thread::from_user("goal").with_system("system");
+        let hash = prompt_hash(&thread);
+
+        let mut cassette = Cassette::default();
+        for (call, text) in ["one", "two"].iter().enumerate() {
+            cassette.record(CassetteEntry {
+                agent: "architect".to_string(),
+                prompt_hash: hash.clone(),
+                turn: 0,
+                call,
+                response: text_response(text),
+            });
+        }
+        cassette.save(&path).unwrap();
+
+        let config = ModelConfig::with_provider(super::super::LlmProvider::Mock, "replay")
+            .with_agent("architect")
+            .with_cassette(CassetteConfig {
+                path: path.clone(),
+                mode: CassetteMode::Replay,
+            });
+        let llm = ReplayLlm::from_config(&config).unwrap();
+
+        let first = llm.generate_content(thread.clone(), None).await.unwrap();
+        let second = llm.generate_content(thread.clone(), None).await.unwrap();
+        let third = llm.generate_content(thread, None).await.unwrap();
+
+        assert_eq!(first.content().first_text(), Some("one"));
+        assert_eq!(second.content().first_text(), Some("two"));
+        // Past the end of the recording, the last call is reused
+        assert_eq!(third.content().first_text(), Some("two"));
+
+        let _ = std::fs::remove_file(path);
+    }
+}
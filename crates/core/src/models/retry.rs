@@ -0,0 +1,657 @@
+//! # Retry & Fallback
+//!
+//! Resilience for LLM calls made through the [`registry`](super::registry).
+//!
+//! A `ModelConfig` carries a [`RetryPolicy`] (attempts, exponential backoff with
+//! jitter, which error classes are retried) and an ordered list of
+//! [`FallbackModel`]s. The registry builds one client per candidate and wraps
+//! them in a [`RetryingLlm`], which retries each candidate before moving to the
+//! next one. Every provider has a process-wide [`CircuitBreaker`]: after repeated
+//! failures the provider is skipped until its cooldown has passed, so skills
+//! stop hammering a provider that is down.
+//!
+//! ## Example
+//! ```rust,ignore
+//! use catalyst_core::models::{FallbackModel, LlmProvider, ModelConfig, RetryPolicy};
+//!
+//! let config = ModelConfig::with_provider(LlmProvider::Anthropic, "claude-sonnet-4-20250514")
+//!     .with_retry(RetryPolicy { max_attempts: 5, ..RetryPolicy::default() })
+//!     .with_fallback(FallbackModel::new(LlmProvider::OpenAI, "gpt-4o"));
+//! ```
+
+use async_trait::async_trait;
+use radkit::errors::{AgentError, AgentResult};
+use radkit::models::{BaseLlm, LlmResponse, Thread};
+use radkit::tools::BaseToolset;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use super::registry::BoxedLlm;
+use super::LlmProvider;
+
+/// Class of a failed LLM call
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorClass {
+    /// HTTP 429
+    RateLimited,
+    /// HTTP 529 / "overloaded"
+    Overloaded,
+    /// Other HTTP 5xx
+    Server,
+    /// Request timed out
+    Timeout,
+    /// Connection failure
+    Network,
+}
+
+impl ErrorClass {
+    /// Classify an error; `None` means it is not transient (auth, bad request, ...)
+    pub fn classify(error: &AgentError) -> Option<ErrorClass> {
+        match error {
+            AgentError::LlmRateLimit { .. } => Some(ErrorClass::RateLimited),
+            AgentError::Timeout { .. } => Some(ErrorClass::Timeout),
+            AgentError::Network { reason, .. } => {
+                let reason = reason.to_lowercase();
+                if reason.contains("timed out") || reason.contains("timeout") {
+                    Some(ErrorClass::Timeout)
+                } else {
+                    Some(ErrorClass::Network)
+                }
+            }
+            AgentError::LlmProvider { message, .. } => Self::from_message(message),
+            AgentError::LlmError { source } => Self::from_message(&source.to_string()),
+            _ => None,
+        }
+    }
+
+    /// Classify from a provider error message such as "HTTP 529 Overloaded: ..."
+    fn from_message(message: &str) -> Option<ErrorClass> {
+        let lower = message.to_lowercase();
+        let status = lower
+            .split_once("http ")
+            .and_then(|(_, rest)| rest.get(..3))
+            .and_then(|code| code.parse::<u16>().ok());
+
+        match status {
+            Some(429) => Some(ErrorClass::RateLimited),
+            Some(529) => Some(ErrorClass::Overloaded),
+            Some(408) | Some(504) => Some(ErrorClass::Timeout),
+            Some(500..=599) => Some(ErrorClass::Server),
+            Some(_) => None,
+            None if lower.contains("overloaded") => Some(ErrorClass::Overloaded),
+            None if lower.contains("rate limit") => Some(ErrorClass::RateLimited),
+            None => None,
+        }
+    }
+}
+
+/// How LLM calls are retried
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// Attempts per provider/model, including the first call
+    pub max_attempts: u32,
+    /// Delay before the first retry
+    pub initial_backoff_ms: u64,
+    /// Upper bound for a single delay
+    pub max_backoff_ms: u64,
+    /// Growth factor between retries
+    pub multiplier: f64,
+    /// Random spread applied to each delay (0.2 = ±20%)
+    pub jitter: f64,
+    /// Error classes worth retrying
+    pub retry_on: Vec<ErrorClass>,
+    /// Consecutive failures that open a provider's circuit breaker (0 = never)
+    pub breaker_threshold: u32,
+    /// How long an open breaker skips its provider
+    pub breaker_cooldown_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff_ms: 1_000,
+            max_backoff_ms: 30_000,
+            multiplier: 2.0,
+            jitter: 0.2,
+            retry_on: vec![
+                ErrorClass::RateLimited,
+                ErrorClass::Overloaded,
+                ErrorClass::Server,
+                ErrorClass::Timeout,
+                ErrorClass::Network,
+            ],
+            breaker_threshold: 5,
+            breaker_cooldown_ms: 30_000,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Single attempt, no breaker
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            breaker_threshold: 0,
+            ..Self::default()
+        }
+    }
+
+    /// Class of `error` if this policy retries it
+    pub fn retryable(&self, error: &AgentError) -> Option<ErrorClass> {
+        ErrorClass::classify(error).filter(|class| self.retry_on.contains(class))
+    }
+
+    /// Delay before retry number `retry` (1-based), with jitter applied
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let base = self.initial_backoff_ms as f64
+            * self
+                .multiplier
+                .max(1.0)
+                .powi(retry.saturating_sub(1) as i32);
+        let base = base.min(self.max_backoff_ms as f64);
+        let spread = self.jitter.clamp(0.0, 1.0);
+        // Uniform in [1 - spread, 1 + spread]
+        let factor = 1.0 + spread * (2.0 * unit_random() - 1.0);
+        Duration::from_millis((base * factor).max(0.0) as u64)
+    }
+
+    fn breaker_cooldown(&self) -> Duration {
+        Duration::from_millis(self.breaker_cooldown_ms)
+    }
+}
+
+/// Alternative provider/model tried when the primary keeps failing
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FallbackModel {
+    pub provider: LlmProvider,
+    pub model: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+}
+
+impl FallbackModel {
+    pub fn new(provider: LlmProvider, model: impl Into<String>) -> Self {
+        Self {
+            provider,
+            model: model.into(),
+            base_url: None,
+        }
+    }
+
+    /// Parse `"provider:model"` (e.g. `"openai:gpt-4o"`)
+    pub fn parse(value: &str) -> Option<Self> {
+        let (provider, model) = value.split_once(':')?;
+        let (provider, model) = (provider.trim(), model.trim());
+        if provider.is_empty() || model.is_empty() {
+            return None;
+        }
+        Some(Self::new(LlmProvider::from(provider), model))
+    }
+}
+
+/// A retry or fallback about to happen
+#[derive(Debug, Clone, Serialize)]
+pub struct RetryEvent {
+    pub agent_id: String,
+    pub provider: String,
+    pub model: String,
+    /// Attempt that failed (1-based)
+    pub attempt: u32,
+    pub max_attempts: u32,
+    pub error: String,
+    /// `None` when the provider was skipped by its circuit breaker
+    pub class: Option<ErrorClass>,
+    /// Delay before the next call
+    pub delay_ms: u64,
+    /// Provider/model of the next call
+    pub next_provider: String,
+    pub next_model: String,
+}
+
+/// Notified before every retry or fallback
+pub trait RetryObserver: Send + Sync + std::fmt::Debug {
+    fn on_retry(&self, event: RetryEvent);
+}
+
+/// Per-provider circuit breaker
+#[derive(Debug, Default)]
+pub struct CircuitBreaker {
+    state: Mutex<BreakerState>,
+}
+
+#[derive(Debug, Default)]
+struct BreakerState {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    /// Whether a call may go through (half-opens after the cooldown)
+    pub fn allow(&self, cooldown: Duration) -> bool {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state
+            .opened_at
+            .is_none_or(|opened| opened.elapsed() >= cooldown)
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .opened_at
+            .is_some()
+    }
+
+    pub fn record_success(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.consecutive_failures = 0;
+        state.opened_at = None;
+    }
+
+    /// Count a transient failure; opens (or re-opens) at `threshold`
+    pub fn record_failure(&self, threshold: u32) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.consecutive_failures += 1;
+        if threshold > 0 && state.consecutive_failures >= threshold {
+            state.opened_at = Some(Instant::now());
+        }
+    }
+}
+
+/// Process-wide breaker for a provider
+pub fn breaker(provider: &str) -> Arc<CircuitBreaker> {
+    static BREAKERS: OnceLock<Mutex<HashMap<String, Arc<CircuitBreaker>>>> = OnceLock::new();
+    let mut breakers = BREAKERS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    Arc::clone(breakers.entry(provider.to_string()).or_default())
+}
+
+struct Candidate {
+    provider: String,
+    llm: BoxedLlm,
+}
+
+/// LLM client that retries and falls back across candidates
+pub struct RetryingLlm {
+    candidates: Vec<Candidate>,
+    policy: RetryPolicy,
+    agent_id: String,
+    observer: Option<Arc<dyn RetryObserver>>,
+}
+
+impl RetryingLlm {
+    pub fn new(policy: RetryPolicy, agent_id: impl Into<String>) -> Self {
+        Self {
+            candidates: Vec::new(),
+            policy,
+            agent_id: agent_id.into(),
+            observer: None,
+        }
+    }
+
+    /// Append a candidate; the first one is the primary
+    pub fn with_candidate(mut self, provider: impl Into<String>, llm: BoxedLlm) -> Self {
+        self.candidates.push(Candidate {
+            provider: provider.into(),
+            llm,
+        });
+        self
+    }
+
+    pub fn with_observer(mut self, observer: Option<Arc<dyn RetryObserver>>) -> Self {
+        self.observer = observer;
+        self
+    }
+
+    fn notify(&self, event: RetryEvent) {
+        tracing::warn!(
+            "LLM call by {} failed on {}/{} (attempt {}/{}): {} - retrying with {}/{} in {}ms",
+            event.agent_id,
+            event.provider,
+            event.model,
+            event.attempt,
+            event.max_attempts,
+            event.error,
+            event.next_provider,
+            event.next_model,
+            event.delay_ms
+        );
+        if let Some(observer) = &self.observer {
+            observer.on_retry(event);
+        }
+    }
+
+    /// Provider/model of the first candidate after `index`
+    fn next_after(&self, index: usize) -> (String, String) {
+        self.candidates
+            .get(index + 1)
+            .map(|c| (c.provider.clone(), c.llm.model_name().to_string()))
+            .unwrap_or_default()
+    }
+}
+
+#[async_trait]
+impl BaseLlm for RetryingLlm {
+    fn model_name(&self) -> &str {
+        self.candidates
+            .first()
+            .map(|c| c.llm.model_name())
+            .unwrap_or_default()
+    }
+
+    async fn generate_content(
+        &self,
+        thread: Thread,
+        toolset: Option<Arc<dyn BaseToolset>>,
+    ) -> AgentResult<LlmResponse> {
+        let max_attempts = self.policy.max_attempts.max(1);
+        let cooldown = self.policy.breaker_cooldown();
+        let mut last_error = None;
+
+        for (index, candidate) in self.candidates.iter().enumerate() {
+            let is_last = index + 1 == self.candidates.len();
+            let breaker = breaker(&candidate.provider);
+
+            // An open breaker only skips providers while there is something to fall back to
+            if !is_last && !breaker.allow(cooldown) {
+                let (next_provider, next_model) = self.next_after(index);
+                self.notify(RetryEvent {
+                    agent_id: self.agent_id.clone(),
+                    provider: candidate.provider.clone(),
+                    model: candidate.llm.model_name().to_string(),
+                    attempt: 0,
+                    max_attempts,
+                    error: "circuit breaker open".to_string(),
+                    class: None,
+                    delay_ms: 0,
+                    next_provider,
+                    next_model,
+                });
+                continue;
+            }
+
+            for attempt in 1..=max_attempts {
+                let error = match candidate
+                    .llm
+                    .generate_content(thread.clone(), toolset.clone())
+                    .await
+                {
+                    Ok(response) => {
+                        breaker.record_success();
+                        return Ok(response);
+                    }
+                    Err(error) => error,
+                };
+
+                let Some(class) = self.policy.retryable(&error) else {
+                    return Err(error);
+                };
+                breaker.record_failure(self.policy.breaker_threshold);
+
+                let retry_here = attempt < max_attempts && !breaker.is_open();
+                if !retry_here && is_last {
+                    return Err(error);
+                }
+
+                let (delay, (next_provider, next_model)) = if retry_here {
+                    (
+                        self.policy.backoff(attempt),
+                        (
+                            candidate.provider.clone(),
+                            candidate.llm.model_name().to_string(),
+                        ),
+                    )
+                } else {
+                    (Duration::ZERO, self.next_after(index))
+                };
+
+                self.notify(RetryEvent {
+                    agent_id: self.agent_id.clone(),
+                    provider: candidate.provider.clone(),
+                    model: candidate.llm.model_name().to_string(),
+                    attempt,
+                    max_attempts,
+                    error: error.to_string(),
+                    class: Some(class),
+                    delay_ms: delay.as_millis() as u64,
+                    next_provider,
+                    next_model,
+                });
+                last_error = Some(error);
+
+                if !retry_here {
+                    break;
+                }
+                tokio::time::sleep(delay).await;
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| AgentError::Internal {
+            component: "retry".to_string(),
+            reason: "No LLM provider available".to_string(),
+        }))
+    }
+}
+
+/// Uniform random number in [0, 1) (not cryptographic)
+fn unit_random() -> f64 {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+    let bits = RandomState::new().build_hasher().finish() >> 11;
+    bits as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use radkit::models::{Content, TokenUsage};
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Fails with `error` for the first `failures` calls
+    struct FlakyLlm {
+        model: String,
+        failures: u32,
+        calls: Arc<AtomicU32>,
+        error: fn() -> AgentError,
+    }
+
+    #[async_trait]
+    impl BaseLlm for FlakyLlm {
+        fn model_name(&self) -> &str {
+            &self.model
+        }
+
+        async fn generate_content(
+            &self,
+            _thread: Thread,
+            _toolset: Option<Arc<dyn BaseToolset>>,
+        ) -> AgentResult<LlmResponse> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            if call < self.failures {
+                return Err((self.error)());
+            }
+            Ok(LlmResponse::new(
+                Content::from_text(self.model.clone()),
+                TokenUsage::empty(),
+            ))
+        }
+    }
+
+    fn flaky(model: &str, failures: u32, error: fn() -> AgentError) -> (BoxedLlm, Arc<AtomicU32>) {
+        let calls = Arc::new(AtomicU32::new(0));
+        let llm = FlakyLlm {
+            model: model.to_string(),
+            failures,
+            calls: Arc::clone(&calls),
+            error,
+        };
+        (Box::new(llm), calls)
+    }
+
+    fn rate_limited() -> AgentError {
+        AgentError::LlmRateLimit {
+            provider: "test".to_string(),
+        }
+    }
+
+    fn unauthorized() -> AgentError {
+        AgentError::LlmAuthentication {
+            provider: "test".to_string(),
+        }
+    }
+
+    fn fast_policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            initial_backoff_ms: 0,
+            jitter: 0.0,
+            ..RetryPolicy::default()
+        }
+    }
+
+    #[derive(Debug, Default)]
+    struct Collect(Mutex<Vec<RetryEvent>>);
+
+    impl RetryObserver for Collect {
+        fn on_retry(&self, event: RetryEvent) {
+            self.0.lock().unwrap().push(event);
+        }
+    }
+
+    fn text(response: &LlmResponse) -> String {
+        response.content().joined_texts().unwrap_or_default()
+    }
+
+    #[test]
+    fn test_classify_errors() {
+        let overloaded = AgentError::LlmProvider {
+            provider: "Anthropic".to_string(),
+            message: "HTTP 529 <unknown status code>: overloaded_error".to_string(),
+        };
+        let bad_request = AgentError::LlmProvider {
+            provider: "Anthropic".to_string(),
+            message: "HTTP 400 Bad Request: invalid model".to_string(),
+        };
+        assert_eq!(
+            ErrorClass::classify(&overloaded),
+            Some(ErrorClass::Overloaded)
+        );
+        assert_eq!(
+            ErrorClass::classify(&rate_limited()),
+            Some(ErrorClass::RateLimited)
+        );
+        assert_eq!(ErrorClass::classify(&bad_request), None);
+        assert_eq!(ErrorClass::classify(&unauthorized()), None);
+    }
+
+    #[test]
+    fn test_backoff_grows_and_caps() {
+        let policy = RetryPolicy {
+            initial_backoff_ms: 100,
+            max_backoff_ms: 250,
+            jitter: 0.0,
+            ..RetryPolicy::default()
+        };
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(250));
+
+        let jittered = RetryPolicy {
+            jitter: 0.5,
+            ..policy
+        };
+        let delay = jittered.backoff(1).as_millis();
+        assert!((50..=150).contains(&delay));
+    }
+
+    #[test]
+    fn test_fallback_model_parse() {
+        let fallback = FallbackModel::parse("openai:gpt-4o").unwrap();
+        assert_eq!(fallback.provider, LlmProvider::OpenAI);
+        assert_eq!(fallback.model, "gpt-4o");
+        assert!(FallbackModel::parse("gpt-4o").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_retries_transient_errors() {
+        let (llm, calls) = flaky("primary", 2, rate_limited);
+        let observer = Arc::new(Collect::default());
+        let retrying = RetryingLlm::new(fast_policy(3), "architect")
+            .with_candidate("retry-test-a", llm)
+            .with_observer(Some(observer.clone() as Arc<dyn RetryObserver>));
+
+        let response = retrying
+            .generate_content(Thread::from_user("hi"), None)
+            .await
+            .unwrap();
+        assert_eq!(text(&response), "primary");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        assert_eq!(observer.0.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_after_exhausting_attempts() {
+        let (primary, primary_calls) = flaky("primary", u32::MAX, rate_limited);
+        let (fallback, _) = flaky("fallback", 0, rate_limited);
+        let observer = Arc::new(Collect::default());
+        let retrying = RetryingLlm::new(fast_policy(2), "critic")
+            .with_candidate("retry-test-b", primary)
+            .with_candidate("retry-test-c", fallback)
+            .with_observer(Some(observer.clone() as Arc<dyn RetryObserver>));
+
+        let response = retrying
+            .generate_content(Thread::from_user("hi"), None)
+            .await
+            .unwrap();
+        assert_eq!(text(&response), "fallback");
+        assert_eq!(primary_calls.load(Ordering::SeqCst), 2);
+
+        let events = observer.0.lock().unwrap();
+        assert_eq!(events.last().unwrap().next_provider, "retry-test-c");
+    }
+
+    #[tokio::test]
+    async fn test_non_retryable_error_is_returned() {
+        let (primary, calls) = flaky("primary", 1, unauthorized);
+        let (fallback, fallback_calls) = flaky("fallback", 0, rate_limited);
+        let retrying = RetryingLlm::new(fast_policy(3), "builder")
+            .with_candidate("retry-test-d", primary)
+            .with_candidate("retry-test-e", fallback);
+
+        let result = retrying
+            .generate_content(Thread::from_user("hi"), None)
+            .await;
+        assert!(matches!(result, Err(AgentError::LlmAuthentication { .. })));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(fallback_calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_open_breaker_skips_provider() {
+        let policy = RetryPolicy {
+            breaker_threshold: 1,
+            ..fast_policy(3)
+        };
+        breaker("retry-test-f").record_failure(1);
+
+        let (primary, primary_calls) = flaky("primary", 0, rate_limited);
+        let (fallback, _) = flaky("fallback", 0, rate_limited);
+        let retrying = RetryingLlm::new(policy, "parser")
+            .with_candidate("retry-test-f", primary)
+            .with_candidate("retry-test-g", fallback);
+
+        let response = retrying
+            .generate_content(Thread::from_user("hi"), None)
+            .await
+            .unwrap();
+        assert_eq!(text(&response), "fallback");
+        assert_eq!(primary_calls.load(Ordering::SeqCst), 0);
+    }
+}
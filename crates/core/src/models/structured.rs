@@ -0,0 +1,561 @@
+//! # Structured Output
+//!
+//! Validation and repair of typed LLM output.
+//!
+//! `run_llm_function!` calls [`run_validated`]: the response is parsed into the
+//! output type, checked against the type's `schemars` schema and its
+//! [`ValidatedOutput::validate`] rules, and on any failure the model is shown
+//! its own answer together with the exact errors and asked again, up to
+//! `ModelConfig::max_repairs` times. Each repair is reported to the config's
+//! [`RepairObserver`].
+
+use radkit::agent::structured_parser::{
+    build_structured_output_instructions, extract_structured_output,
+};
+use radkit::errors::{AgentError, AgentResult};
+use radkit::models::{BaseLlm, Event, LLMOutputTrait, Thread};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::registry::{self, ProviderCapabilities};
+use super::ModelConfig;
+
+/// Repairs attempted when `ModelConfig::max_repairs` is unset
+pub const DEFAULT_MAX_REPAIRS: u32 = 2;
+
+/// LLM output type with validation rules beyond its JSON schema
+pub trait ValidatedOutput: LLMOutputTrait + JsonSchema + Serialize + Send + Sync + 'static {
+    /// Semantic checks the schema cannot express, one message per violation
+    fn validate(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// A rejected response about to be sent back to the model
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RepairEvent {
+    pub agent_id: String,
+    /// Output type name (e.g. "CriticOutput")
+    pub output_type: String,
+    /// Repair number, starting at 1
+    pub attempt: u32,
+    pub max_repairs: u32,
+    pub errors: Vec<String>,
+}
+
+/// Notified before every repair prompt
+pub trait RepairObserver: Send + Sync + std::fmt::Debug {
+    fn on_repair(&self, event: RepairEvent);
+}
+
+/// Check `output` against its JSON schema and semantic rules
+pub fn validate_output<T: ValidatedOutput>(output: &T) -> Vec<String> {
+    let schema = schemars::schema_for!(T);
+    let mut errors = match serde_json::to_value(output) {
+        Ok(value) => {
+            let mut errors = Vec::new();
+            check_schema(
+                schema.as_value(),
+                schema.as_value(),
+                &value,
+                "",
+                &mut errors,
+            );
+            errors
+        }
+        Err(e) => vec![format!("output cannot be serialized: {}", e)],
+    };
+    errors.extend(output.validate());
+    errors
+}
+
+/// Resolve a client for `config` and run a validated structured call
+pub async fn run_validated<T: ValidatedOutput>(
+    config: &ModelConfig,
+    system_prompt: &str,
+    input: impl Into<String>,
+) -> AgentResult<T> {
+    let llm = registry::resolve(config, ProviderCapabilities::structured())?;
+    generate_validated(&llm, config, system_prompt, input).await
+}
+
+/// Ask `llm` for a `T`, re-prompting with the validation errors until it is valid
+///
+/// Provider errors are returned immediately; only unparseable or invalid
+/// responses are repaired.
+pub async fn generate_validated<T: ValidatedOutput>(
+    llm: &dyn BaseLlm,
+    config: &ModelConfig,
+    system_prompt: &str,
+    input: impl Into<String>,
+) -> AgentResult<T> {
+    let max_repairs = config.max_repairs.unwrap_or(DEFAULT_MAX_REPAIRS);
+    let output_type = short_type_name::<T>();
+    let instructions = build_structured_output_instructions::<T>()?;
+    let mut thread = Thread::from_user(input.into())
+        .with_system(format!("{}\n\n{}", system_prompt, instructions));
+
+    let mut attempt = 0;
+    loop {
+        let content = llm
+            .generate_content(thread.clone(), None)
+            .await?
+            .into_content();
+        let errors = match extract_structured_output::<T>(&content) {
+            Ok(output) => {
+                let errors = validate_output(&output);
+                if errors.is_empty() {
+                    return Ok(output);
+                }
+                errors
+            }
+            Err(e) => vec![e.to_string()],
+        };
+
+        if attempt >= max_repairs {
+            return Err(AgentError::Validation {
+                field: output_type,
+                reason: format!(
+                    "invalid output after {} repair attempt(s): {}",
+                    max_repairs,
+                    errors.join("; ")
+                ),
+            });
+        }
+        attempt += 1;
+
+        tracing::warn!(
+            "{} failed validation, requesting repair {}/{}: {}",
+            output_type,
+            attempt,
+            max_repairs,
+            errors.join("; ")
+        );
+        if let Some(observer) = &config.repair_observer {
+            observer.on_repair(RepairEvent {
+                agent_id: config
+                    .agent_id
+                    .clone()
+                    .unwrap_or_else(|| "unknown".to_string()),
+                output_type: output_type.clone(),
+                attempt,
+                max_repairs,
+                errors: errors.clone(),
+            });
+        }
+
+        thread = thread
+            .add_event(Event::assistant(content))
+            .add_event(Event::user(repair_prompt(&errors)));
+    }
+}
+
+/// Follow-up message asking the model to fix its previous answer
+fn repair_prompt(errors: &[String]) -> String {
+    let list: Vec<String> = errors.iter().map(|e| format!("- {}", e)).collect();
+    format!(
+        "Your previous response was rejected:\n{}\n\n\
+         Reply again with the complete corrected JSON object only.",
+        list.join("\n")
+    )
+}
+
+fn short_type_name<T>() -> String {
+    let name = std::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name).to_string()
+}
+
+// ============================================================================
+// JSON Schema checks
+// ============================================================================
+
+/// Validate `value` against `schema`, resolving `$ref`s in `root`
+///
+/// Covers the keywords `schemars` emits for output types: `$ref`, `type`,
+/// `enum`, `const`, `properties`, `required`, `items`, numeric bounds,
+/// length bounds and `anyOf`/`oneOf`/`allOf`.
+fn check_schema(root: &Value, schema: &Value, value: &Value, path: &str, errors: &mut Vec<String>) {
+    let schema = match schema {
+        Value::Bool(true) => return,
+        Value::Bool(false) => {
+            errors.push(format!("{}: no value is allowed here", display_path(path)));
+            return;
+        }
+        Value::Object(schema) => schema,
+        _ => return,
+    };
+
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        match reference
+            .strip_prefix('#')
+            .and_then(|pointer| root.pointer(pointer))
+        {
+            Some(target) => check_schema(root, target, value, path, errors),
+            None => errors.push(format!(
+                "{}: unresolved schema reference {}",
+                display_path(path),
+                reference
+            )),
+        }
+    }
+
+    for key in ["anyOf", "oneOf"] {
+        if let Some(branches) = schema.get(key).and_then(Value::as_array) {
+            let matches = branches.iter().any(|branch| {
+                let mut branch_errors = Vec::new();
+                check_schema(root, branch, value, path, &mut branch_errors);
+                branch_errors.is_empty()
+            });
+            if !matches {
+                errors.push(format!(
+                    "{}: {} does not match any allowed shape",
+                    display_path(path),
+                    value
+                ));
+            }
+        }
+    }
+    if let Some(branches) = schema.get("allOf").and_then(Value::as_array) {
+        for branch in branches {
+            check_schema(root, branch, value, path, errors);
+        }
+    }
+
+    if let Some(expected) = schema.get("type") {
+        let allowed: Vec<&str> = match expected {
+            Value::String(name) => vec![name.as_str()],
+            Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !allowed.is_empty() && !allowed.iter().any(|name| has_type(value, name)) {
+            errors.push(format!(
+                "{}: expected {}, got {}",
+                display_path(path),
+                allowed.join(" or "),
+                value
+            ));
+            return;
+        }
+    }
+
+    if let Some(options) = schema.get("enum").and_then(Value::as_array) {
+        if !options.contains(value) {
+            let options: Vec<String> = options.iter().map(Value::to_string).collect();
+            errors.push(format!(
+                "{}: {} is not one of {}",
+                display_path(path),
+                value,
+                options.join(", ")
+            ));
+        }
+    }
+    if let Some(expected) = schema.get("const") {
+        if expected != value {
+            errors.push(format!(
+                "{}: must be {}, got {}",
+                display_path(path),
+                expected,
+                value
+            ));
+        }
+    }
+
+    match value {
+        Value::Number(number) => {
+            let n = number.as_f64().unwrap_or_default();
+            let bound = |key: &str| schema.get(key).and_then(Value::as_f64);
+            if let Some(min) = bound("minimum").filter(|min| n < *min) {
+                errors.push(format!(
+                    "{}: {} is below the minimum {}",
+                    display_path(path),
+                    number,
+                    min
+                ));
+            }
+            if let Some(max) = bound("maximum").filter(|max| n > *max) {
+                errors.push(format!(
+                    "{}: {} is above the maximum {}",
+                    display_path(path),
+                    number,
+                    max
+                ));
+            }
+            if let Some(min) = bound("exclusiveMinimum").filter(|min| n <= *min) {
+                errors.push(format!(
+                    "{}: {} must be greater than {}",
+                    display_path(path),
+                    number,
+                    min
+                ));
+            }
+            if let Some(max) = bound("exclusiveMaximum").filter(|max| n >= *max) {
+                errors.push(format!(
+                    "{}: {} must be less than {}",
+                    display_path(path),
+                    number,
+                    max
+                ));
+            }
+        }
+        Value::String(text) => {
+            let len = text.chars().count() as u64;
+            if let Some(min) = schema
+                .get("minLength")
+                .and_then(Value::as_u64)
+                .filter(|min| len < *min)
+            {
+                errors.push(format!(
+                    "{}: must be at least {} characters",
+                    display_path(path),
+                    min
+                ));
+            }
+            if let Some(max) = schema
+                .get("maxLength")
+                .and_then(Value::as_u64)
+                .filter(|max| len > *max)
+            {
+                errors.push(format!(
+                    "{}: must be at most {} characters",
+                    display_path(path),
+                    max
+                ));
+            }
+        }
+        Value::Array(items) => {
+            let len = items.len() as u64;
+            if let Some(min) = schema
+                .get("minItems")
+                .and_then(Value::as_u64)
+                .filter(|min| len < *min)
+            {
+                errors.push(format!(
+                    "{}: must have at least {} items",
+                    display_path(path),
+                    min
+                ));
+            }
+            if let Some(max) = schema
+                .get("maxItems")
+                .and_then(Value::as_u64)
+                .filter(|max| len > *max)
+            {
+                errors.push(format!(
+                    "{}: must have at most {} items",
+                    display_path(path),
+                    max
+                ));
+            }
+            if let Some(item_schema) = schema.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    check_schema(root, item_schema, item, &format!("{}[{}]", path, i), errors);
+                }
+            }
+        }
+        Value::Object(fields) => {
+            if let Some(required) = schema.get("required").and_then(Value::as_array) {
+                for name in required.iter().filter_map(Value::as_str) {
+                    if !fields.contains_key(name) {
+                        errors.push(format!(
+                            "{}: missing required field",
+                            display_path(&field_path(path, name))
+                        ));
+                    }
+                }
+            }
+            if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+                for (name, property) in properties {
+                    if let Some(field) = fields.get(name) {
+                        check_schema(root, property, field, &field_path(path, name), errors);
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn has_type(value: &Value, name: &str) -> bool {
+    match name {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => {
+            value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|n| n.fract() == 0.0)
+        }
+        _ => true,
+    }
+}
+
+fn field_path(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", path, name)
+    }
+}
+
+fn display_path(path: &str) -> &str {
+    if path.is_empty() {
+        "output"
+    } else {
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use radkit::macros::LLMOutput;
+    use radkit::models::{Content, LlmResponse, TokenUsage};
+    use radkit::tools::BaseToolset;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, LLMOutput)]
+    struct Verdict {
+        #[schemars(extend("enum" = ["approved", "rejected"]))]
+        verdict: String,
+        #[schemars(range(min = 0.0, max = 1.0))]
+        confidence: f32,
+        reasons: Vec<String>,
+    }
+
+    impl ValidatedOutput for Verdict {
+        fn validate(&self) -> Vec<String> {
+            if self.verdict == "rejected" && self.reasons.is_empty() {
+                vec!["reasons: a rejection needs at least one reason".to_string()]
+            } else {
+                Vec::new()
+            }
+        }
+    }
+
+    /// Replies with scripted answers and records the threads it was sent
+    struct ScriptedLlm {
+        replies: Mutex<Vec<&'static str>>,
+        threads: Mutex<Vec<Thread>>,
+    }
+
+    impl ScriptedLlm {
+        fn new(replies: &[&'static str]) -> Self {
+            Self {
+                replies: Mutex::new(replies.iter().rev().copied().collect()),
+                threads: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl BaseLlm for ScriptedLlm {
+        fn model_name(&self) -> &str {
+            "scripted"
+        }
+
+        async fn generate_content(
+            &self,
+            thread: Thread,
+            _toolset: Option<Arc<dyn BaseToolset>>,
+        ) -> AgentResult<LlmResponse> {
+            self.threads.lock().unwrap().push(thread);
+            let reply = self.replies.lock().unwrap().pop().unwrap_or("{}");
+            Ok(LlmResponse::new(
+                Content::from_text(reply),
+                TokenUsage::empty(),
+            ))
+        }
+    }
+
+    #[derive(Debug, Default)]
+    struct RecordingObserver {
+        events: Mutex<Vec<RepairEvent>>,
+    }
+
+    impl RepairObserver for RecordingObserver {
+        fn on_repair(&self, event: RepairEvent) {
+            self.events.lock().unwrap().push(event);
+        }
+    }
+
+    #[test]
+    fn test_schema_and_semantic_errors() {
+        let valid = Verdict {
+            verdict: "approved".to_string(),
+            confidence: 0.9,
+            reasons: Vec::new(),
+        };
+        assert!(validate_output(&valid).is_empty());
+
+        let invalid = Verdict {
+            verdict: "maybe".to_string(),
+            confidence: 1.5,
+            reasons: Vec::new(),
+        };
+        let errors = validate_output(&invalid);
+        assert_eq!(errors.len(), 2, "{:?}", errors);
+        assert!(errors.contains(&"confidence: 1.5 is above the maximum 1".to_string()));
+        assert!(errors
+            .contains(&"verdict: \"maybe\" is not one of \"approved\", \"rejected\"".to_string()));
+
+        let unexplained = Verdict {
+            verdict: "rejected".to_string(),
+            confidence: 0.5,
+            reasons: Vec::new(),
+        };
+        assert_eq!(
+            validate_output(&unexplained),
+            vec!["reasons: a rejection needs at least one reason".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_repair_loop_sends_errors_back() {
+        let llm = ScriptedLlm::new(&[
+            "not json at all",
+            r#"{"verdict": "approved", "confidence": 3.0, "reasons": []}"#,
+            r#"{"verdict": "approved", "confidence": 0.8, "reasons": []}"#,
+        ]);
+        let observer = Arc::new(RecordingObserver::default());
+        let mut config = ModelConfig::default().with_agent("critic");
+        config.repair_observer = Some(observer.clone());
+
+        let output: Verdict = generate_validated(&llm, &config, "Judge it.", "input")
+            .await
+            .unwrap();
+        assert_eq!(output.confidence, 0.8);
+
+        let events = observer.events.lock().unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].agent_id, "critic");
+        assert_eq!(events[0].output_type, "Verdict");
+        assert_eq!(events[1].attempt, 2);
+        assert!(events[1].errors[0].contains("confidence"));
+
+        // The last call saw both rejected answers and the exact errors
+        let threads = llm.threads.lock().unwrap();
+        let last = threads.last().unwrap();
+        assert_eq!(last.events().len(), 5);
+        let feedback = last.events()[4].content().joined_texts().unwrap();
+        assert!(feedback.contains("confidence: 3.0 is above the maximum 1"));
+    }
+
+    #[tokio::test]
+    async fn test_repair_loop_gives_up() {
+        let llm = ScriptedLlm::new(&[
+            r#"{"verdict": "maybe", "confidence": 0.5, "reasons": []}"#,
+            r#"{"verdict": "maybe", "confidence": 0.5, "reasons": []}"#,
+        ]);
+        let config = ModelConfig::default().with_max_repairs(1);
+
+        let err = generate_validated::<Verdict>(&llm, &config, "Judge it.", "input")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AgentError::Validation { .. }));
+        assert!(err.to_string().contains("\"maybe\" is not one of"));
+        assert_eq!(llm.threads.lock().unwrap().len(), 2);
+    }
+}
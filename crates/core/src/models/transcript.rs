@@ -0,0 +1,195 @@
+//! # Transcripts
+//!
+//! Captures every exchange of LLM clients built by the [`registry`](super::registry).
+//!
+//! When a `ModelConfig` carries a [`TranscriptSink`], each call's system prompt,
+//! messages, tool calls/results and the raw model response (or error) are handed
+//! to it as an [`LlmExchange`]. The coordinator's sink redacts secrets and stores
+//! them in the `llm_transcripts` table (see `state::TranscriptManager`).
+
+use async_trait::async_trait;
+use radkit::errors::AgentResult;
+use radkit::models::{BaseLlm, Content, LlmResponse, Role, Thread};
+use radkit::tools::BaseToolset;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Instant;
+
+use super::ModelConfig;
+
+/// A tool call requested by the model
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TranscriptToolCall {
+    pub id: String,
+    pub name: String,
+    pub arguments: serde_json::Value,
+}
+
+/// The result of a tool call sent back to the model
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TranscriptToolResult {
+    pub tool_call_id: String,
+    pub success: bool,
+    pub output: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// One message of a conversation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TranscriptMessage {
+    /// "system", "user", "assistant" or "tool"
+    pub role: String,
+    #[serde(default)]
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_calls: Vec<TranscriptToolCall>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_results: Vec<TranscriptToolResult>,
+}
+
+impl TranscriptMessage {
+    pub fn from_content(role: &Role, content: &Content) -> Self {
+        let role = match role {
+            Role::System => "system",
+            Role::User => "user",
+            Role::Assistant => "assistant",
+            Role::Tool => "tool",
+            _ => "other",
+        };
+
+        Self {
+            role: role.to_string(),
+            text: content.joined_texts().unwrap_or_default(),
+            tool_calls: content
+                .tool_calls()
+                .into_iter()
+                .map(|call| TranscriptToolCall {
+                    id: call.id().to_string(),
+                    name: call.name().to_string(),
+                    arguments: call.arguments().clone(),
+                })
+                .collect(),
+            tool_results: content
+                .tool_responses()
+                .into_iter()
+                .map(|response| TranscriptToolResult {
+                    tool_call_id: response.tool_call_id().to_string(),
+                    success: response.result().is_success(),
+                    output: response.result().data().clone(),
+                    error: response.result().error_message().map(str::to_string),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A single LLM call: everything the model saw and what it answered
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LlmExchange {
+    pub agent_id: String,
+    pub feature_id: Option<String>,
+    pub provider: String,
+    pub model: String,
+    pub system_prompt: Option<String>,
+    pub messages: Vec<TranscriptMessage>,
+    /// Names of the tools offered to the model
+    pub tools: Vec<String>,
+    /// Model response (`None` when the call failed)
+    pub response: Option<TranscriptMessage>,
+    pub error: Option<String>,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub duration_ms: u64,
+}
+
+/// Receives every LLM exchange made with a config
+pub trait TranscriptSink: Send + Sync + std::fmt::Debug {
+    fn record(&self, exchange: LlmExchange);
+}
+
+/// LLM client wrapper that reports each exchange to a transcript sink
+pub struct TranscribedLlm<L> {
+    inner: L,
+    sink: Arc<dyn TranscriptSink>,
+    agent_id: String,
+    feature_id: Option<String>,
+    provider: String,
+}
+
+impl<L: BaseLlm> TranscribedLlm<L> {
+    pub fn new(inner: L, sink: Arc<dyn TranscriptSink>, config: &ModelConfig) -> Self {
+        Self {
+            inner,
+            sink,
+            agent_id: config
+                .agent_id
+                .clone()
+                .unwrap_or_else(|| "unknown".to_string()),
+            feature_id: config.feature_id.clone(),
+            provider: config.provider.as_str().to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl<L: BaseLlm> BaseLlm for TranscribedLlm<L> {
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+
+    async fn generate_content(
+        &self,
+        thread: Thread,
+        toolset: Option<Arc<dyn BaseToolset>>,
+    ) -> AgentResult<LlmResponse> {
+        let system_prompt = thread.system().map(str::to_string);
+        let messages = thread
+            .events()
+            .iter()
+            .map(|event| TranscriptMessage::from_content(event.role(), event.content()))
+            .collect();
+        let tools = match &toolset {
+            Some(toolset) => toolset
+                .get_tools()
+                .await
+                .iter()
+                .map(|tool| tool.name().to_string())
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let started = Instant::now();
+        let result = self.inner.generate_content(thread, toolset).await;
+
+        let (response, error, input_tokens, output_tokens) = match &result {
+            Ok(response) => (
+                Some(TranscriptMessage::from_content(
+                    &Role::Assistant,
+                    response.content(),
+                )),
+                None,
+                u64::from(response.usage().input_tokens()),
+                u64::from(response.usage().output_tokens()),
+            ),
+            Err(e) => (None, Some(e.to_string()), 0, 0),
+        };
+
+        self.sink.record(LlmExchange {
+            agent_id: self.agent_id.clone(),
+            feature_id: self.feature_id.clone(),
+            provider: self.provider.clone(),
+            model: self.inner.model_name().to_string(),
+            system_prompt,
+            messages,
+            tools,
+            response,
+            error,
+            input_tokens,
+            output_tokens,
+            duration_ms: started.elapsed().as_millis() as u64,
+        });
+
+        result
+    }
+}
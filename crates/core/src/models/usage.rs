@@ -0,0 +1,240 @@
+//! # Token Usage
+//!
+//! Per-call token accounting for LLM clients built by the [`registry`](super::registry).
+//!
+//! When a `ModelConfig` carries a [`UsageSink`], every response's token usage is
+//! reported to it together with the agent, feature, provider and model. The
+//! coordinator's sink writes these to the `llm_usage` table and enforces budgets.
+//! [`PriceTable`] turns token counts into an estimated USD cost.
+
+use async_trait::async_trait;
+use radkit::errors::AgentResult;
+use radkit::models::{BaseLlm, LlmResponse, Thread};
+use radkit::tools::BaseToolset;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::ModelConfig;
+
+/// Token usage of a single LLM call
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LlmUsage {
+    pub agent_id: String,
+    pub feature_id: Option<String>,
+    pub provider: String,
+    pub model: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+}
+
+impl LlmUsage {
+    pub fn total_tokens(&self) -> u64 {
+        self.input_tokens + self.output_tokens
+    }
+}
+
+/// Receives the usage of every LLM call made with a config
+pub trait UsageSink: Send + Sync + std::fmt::Debug {
+    fn record(&self, usage: LlmUsage);
+}
+
+/// Price of a model in USD per million tokens
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct ModelPrice {
+    pub input_per_mtok: f64,
+    pub output_per_mtok: f64,
+}
+
+impl ModelPrice {
+    pub const fn new(input_per_mtok: f64, output_per_mtok: f64) -> Self {
+        Self {
+            input_per_mtok,
+            output_per_mtok,
+        }
+    }
+
+    /// Free (local and replayed models)
+    pub const FREE: ModelPrice = ModelPrice::new(0.0, 0.0);
+}
+
+/// Model prices used to estimate cost
+///
+/// Keys are matched against `"provider/model"`, then the model name, then the
+/// longest key that prefixes the model name (so `claude-sonnet-4` covers dated
+/// releases), then the provider name.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PriceTable {
+    prices: HashMap<String, ModelPrice>,
+}
+
+impl Default for PriceTable {
+    fn default() -> Self {
+        let prices = [
+            ("claude-opus-4", ModelPrice::new(15.0, 75.0)),
+            ("claude-sonnet-4", ModelPrice::new(3.0, 15.0)),
+            ("claude-3-5-sonnet", ModelPrice::new(3.0, 15.0)),
+            ("claude-3-haiku", ModelPrice::new(0.25, 1.25)),
+            ("gpt-4o-mini", ModelPrice::new(0.15, 0.6)),
+            ("gpt-4o", ModelPrice::new(2.5, 10.0)),
+            ("gemini-2.0-flash", ModelPrice::new(0.1, 0.4)),
+            ("grok-2", ModelPrice::new(2.0, 10.0)),
+            ("deepseek-chat", ModelPrice::new(0.27, 1.1)),
+            (
+                "openrouter/anthropic/claude-3.5-sonnet",
+                ModelPrice::new(3.0, 15.0),
+            ),
+            ("local", ModelPrice::FREE),
+            ("mock", ModelPrice::FREE),
+        ];
+
+        Self {
+            prices: prices
+                .into_iter()
+                .map(|(key, price)| (key.to_string(), price))
+                .collect(),
+        }
+    }
+}
+
+impl PriceTable {
+    /// Empty table (every model costs nothing)
+    pub fn empty() -> Self {
+        Self {
+            prices: HashMap::new(),
+        }
+    }
+
+    /// Add or replace prices
+    pub fn with_overrides(mut self, overrides: &HashMap<String, ModelPrice>) -> Self {
+        for (key, price) in overrides {
+            self.prices.insert(key.clone(), *price);
+        }
+        self
+    }
+
+    /// Set the price for a key
+    pub fn set(&mut self, key: impl Into<String>, price: ModelPrice) {
+        self.prices.insert(key.into(), price);
+    }
+
+    /// Find the price for a provider/model pair
+    pub fn lookup(&self, provider: &str, model: &str) -> Option<ModelPrice> {
+        let qualified = format!("{}/{}", provider, model);
+        if let Some(price) = self.prices.get(&qualified).or(self.prices.get(model)) {
+            return Some(*price);
+        }
+
+        self.prices
+            .iter()
+            .filter(|(key, _)| model.starts_with(key.as_str()))
+            .max_by_key(|(key, _)| key.len())
+            .map(|(_, price)| *price)
+            .or_else(|| self.prices.get(provider).copied())
+    }
+
+    /// Estimated cost of a call in USD (0 for unknown models)
+    pub fn cost(&self, usage: &LlmUsage) -> f64 {
+        self.lookup(&usage.provider, &usage.model)
+            .map(|price| {
+                (usage.input_tokens as f64 * price.input_per_mtok
+                    + usage.output_tokens as f64 * price.output_per_mtok)
+                    / 1_000_000.0
+            })
+            .unwrap_or(0.0)
+    }
+}
+
+/// LLM client wrapper that reports token usage to a sink
+pub struct MeteredLlm<L> {
+    inner: L,
+    sink: Arc<dyn UsageSink>,
+    agent_id: String,
+    feature_id: Option<String>,
+    provider: String,
+}
+
+impl<L: BaseLlm> MeteredLlm<L> {
+    pub fn new(inner: L, sink: Arc<dyn UsageSink>, config: &ModelConfig) -> Self {
+        Self {
+            inner,
+            sink,
+            agent_id: config
+                .agent_id
+                .clone()
+                .unwrap_or_else(|| "unknown".to_string()),
+            feature_id: config.feature_id.clone(),
+            provider: config.provider.as_str().to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl<L: BaseLlm> BaseLlm for MeteredLlm<L> {
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+
+    async fn generate_content(
+        &self,
+        thread: Thread,
+        toolset: Option<Arc<dyn BaseToolset>>,
+    ) -> AgentResult<LlmResponse> {
+        let response = self.inner.generate_content(thread, toolset).await?;
+        let usage = response.usage();
+        self.sink.record(LlmUsage {
+            agent_id: self.agent_id.clone(),
+            feature_id: self.feature_id.clone(),
+            provider: self.provider.clone(),
+            model: self.inner.model_name().to_string(),
+            input_tokens: u64::from(usage.input_tokens()),
+            output_tokens: u64::from(usage.output_tokens()),
+        });
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(provider: &str, model: &str, input: u64, output: u64) -> LlmUsage {
+        LlmUsage {
+            agent_id: "architect".to_string(),
+            feature_id: None,
+            provider: provider.to_string(),
+            model: model.to_string(),
+            input_tokens: input,
+            output_tokens: output,
+        }
+    }
+
+    #[test]
+    fn test_price_lookup_prefers_most_specific_key() {
+        let table = PriceTable::default();
+        assert_eq!(
+            table.lookup("anthropic", "claude-sonnet-4-20250514"),
+            Some(ModelPrice::new(3.0, 15.0))
+        );
+        // gpt-4o-mini must not pick up the gpt-4o price
+        assert_eq!(
+            table.lookup("openai", "gpt-4o-mini"),
+            Some(ModelPrice::new(0.15, 0.6))
+        );
+        assert_eq!(table.lookup("local", "llama3.2"), Some(ModelPrice::FREE));
+        assert_eq!(table.lookup("acme", "mystery"), None);
+    }
+
+    #[test]
+    fn test_cost_per_million_tokens() {
+        let table = PriceTable::default();
+        let cost = table.cost(&usage("openai", "gpt-4o", 1_000_000, 100_000));
+        assert!((cost - 3.5).abs() < 1e-9);
+
+        let mut overrides = HashMap::new();
+        overrides.insert("openai/gpt-4o".to_string(), ModelPrice::new(1.0, 1.0));
+        let table = table.with_overrides(&overrides);
+        let cost = table.cost(&usage("openai", "gpt-4o", 500_000, 500_000));
+        assert!((cost - 1.0).abs() < 1e-9);
+    }
+}
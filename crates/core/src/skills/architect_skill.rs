@@ -10,9 +10,10 @@
 //! 2. User responds with "approve", "reject", or feedback
 //! 3. `on_input_received` handles response and completes or rejects
 
-use crate::models::ModelConfig;
+use crate::models::{ModelConfig, ValidatedOutput};
 use crate::run_llm_function;
 use crate::skills::artifact_registry::{DecisionArtifact, SpecUpdateSummary};
+use crate::skills::critic_skill::CriticOutput;
 use async_trait::async_trait;
 use radkit::agent::{Artifact, OnInputResult, OnRequestResult, SkillHandler, SkillSlot};
 use radkit::errors::{AgentError, AgentResult};
@@ -55,6 +56,24 @@ pub struct ArchitectOutput {
     pub dependencies: Vec<String>,
 }
 
+impl ValidatedOutput for ArchitectOutput {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        if self.chosen_option.trim().is_empty() {
+            errors.push("chosen_option: must not be empty".to_string());
+        }
+        if self.rationale.trim().is_empty() {
+            errors.push("rationale: must not be empty".to_string());
+        }
+        for (i, update) in self.spec_updates.iter().enumerate() {
+            if update.section.trim().is_empty() {
+                errors.push(format!("spec_updates[{}].section: must not be empty", i));
+            }
+        }
+        errors
+    }
+}
+
 /// Architect skill for making design decisions
 #[skill(
     id = "architect",
@@ -94,17 +113,34 @@ impl ArchitectSkill {
     }
 
     /// SDK-style call for direct Coordinator integration.
+    ///
+    /// `depends_on` holds the approved decisions of the unknowns this one
+    /// depends on; the new decision has to stay consistent with them.
     pub async fn run(
         unknown_id: &str,
         research_json: &str,
+        depends_on: &[ArchitectOutput],
+        feedback: Option<&CriticOutput>,
         spec_context: &str,
         mode: &str,
         config: &ModelConfig,
     ) -> anyhow::Result<ArchitectOutput> {
-        let prompt = format!(
+        let mut prompt = format!(
             "Unknown ID: {}\n\nResearch Results:\n{}\n\nSpec Context:\n{}\n\nMode: {}",
             unknown_id, research_json, spec_context, mode
         );
+        if !depends_on.is_empty() {
+            prompt.push_str(&format!(
+                "\n\nApproved Decisions This Depends On:\n{}",
+                serde_json::to_string_pretty(depends_on)?
+            ));
+        }
+        if let Some(feedback) = feedback {
+            prompt.push_str(&format!(
+                "\n\nReview of Your Previous Decision:\n{}",
+                serde_json::to_string_pretty(feedback)?
+            ));
+        }
         run_llm_function!(config, ArchitectOutput, SYSTEM_PROMPT, prompt)
     }
 }
@@ -4,7 +4,7 @@
 //! Follows the "Rule of 100" - each module should be completable
 //! in one agent conversation.
 
-use crate::models::ModelConfig;
+use crate::models::{ModelConfig, ValidatedOutput};
 use crate::run_llm_function;
 use crate::skills::artifact_registry::{AtomizationArtifact, ModuleSummary};
 use async_trait::async_trait;
@@ -25,6 +25,7 @@ pub struct AtomicModule {
     /// What this module is responsible for
     pub responsibility: String,
     /// Maximum allowed lines
+    #[schemars(range(min = 1))]
     pub max_lines: u32,
     /// Public interface signatures
     pub public_interface: Vec<String>,
@@ -70,6 +71,21 @@ pub struct AtomizerOutput {
     pub integration_points: Vec<IntegrationPoint>,
 }
 
+impl ValidatedOutput for AtomizerOutput {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        if self.modules.is_empty() {
+            errors.push("modules: a plan needs at least one module".to_string());
+        }
+        for (i, module) in self.modules.iter().enumerate() {
+            if module.path.trim().is_empty() {
+                errors.push(format!("modules[{}].path: must not be empty", i));
+            }
+        }
+        errors
+    }
+}
+
 /// Atomizer skill for breaking features into modules
 #[skill(
     id = "atomize",
@@ -96,6 +112,19 @@ impl AtomizerSkill {
     pub fn default() -> Self {
         Self::new(ModelConfig::default())
     }
+
+    /// SDK-style call for direct Coordinator integration.
+    pub async fn run(
+        goal: &str,
+        decisions_json: &str,
+        config: &ModelConfig,
+    ) -> anyhow::Result<AtomizerOutput> {
+        let prompt = format!(
+            "Feature:\n{}\n\nApproved Decisions:\n{}",
+            goal, decisions_json
+        );
+        run_llm_function!(config, AtomizerOutput, SYSTEM_PROMPT, prompt)
+    }
 }
 
 #[async_trait]
@@ -10,6 +10,21 @@
 //! 2. **SDK Mode (`run()`):** Uses `FunctionTool` closures that capture the worktree path.
 //!    This allows direct calls from the Coordinator without A2A runtime.
 //!
+//! ## Compile-Fix Loop (SDK Mode)
+//!
+//! ```text
+//! mission → model pass → cargo check ─ clean ──────────────→ build_passed
+//!                ↑             │
+//!                │             ├─ same errors as last time → stop (no progress)
+//!                │             ├─ max_iterations reached ──→ stop
+//!                └─ fix prompt ┘  (errors grouped by file and code, with source)
+//! ```
+//!
+//! A check fails when cargo exits non-zero, even without a diagnostic that
+//! points at code. Once it is clean, `cargo test` runs in the worktree.
+//! `iterations`, `build_passed`, `tests_passed` and `errors` come from these
+//! runs, not from what the model reports.
+//!
 //! ## Reference Documentation
 //! - See `radkit_docs/docs/guides/tool-execution.md` for `LlmWorker` + tools
 //! - See `radkit_docs/docs/guides/stateful-tools.md` for `ToolContext` patterns
@@ -18,6 +33,7 @@ use crate::models::ModelConfig;
 use crate::run_llm_worker;
 use crate::skills::artifact_registry::{BuildArtifact, FileChange};
 use crate::skills::tools::{build_tools, file_tools};
+use crate::tools::terminal::{self, CompilerError};
 use async_trait::async_trait;
 use radkit::agent::{Artifact, LlmWorker, OnRequestResult, SkillHandler};
 use radkit::errors::{AgentError, AgentResult};
@@ -29,6 +45,7 @@ use radkit::tools::{FunctionTool, ToolResult};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::path::Path;
 use std::process::Command;
 
@@ -44,7 +61,7 @@ pub struct BuilderOutput {
     pub summary: String,
     /// List of files modified
     pub files_modified: Vec<String>,
-    /// Any compiler errors encountered
+    /// Compiler errors, or the failing tests once the build passes
     pub errors: Vec<String>,
     /// Whether cargo build passed
     #[serde(default)]
@@ -88,12 +105,34 @@ impl BuilderSkill {
     ///
     /// This method creates `FunctionTool` closures that capture the worktree path,
     /// allowing it to work without an A2A runtime context.
+    ///
+    /// After the first pass, `cargo check` runs in the worktree up to
+    /// `max_iterations` times. Each failing check gives the model another pass
+    /// with only the errors it left behind, until the check is clean or a pass
+    /// leaves the same errors as the one before it. A clean check is followed
+    /// by `cargo test`.
     pub async fn run(
         mission: &str,
         worktree_path: &Path,
+        max_iterations: u32,
         config: &ModelConfig,
     ) -> anyhow::Result<BuilderOutput> {
-        Self::run_internal(mission, worktree_path, config).await
+        let output = Self::run_internal(mission, worktree_path, config).await?;
+        let mut fixer = WorktreeFixer {
+            mission,
+            worktree_path,
+            config,
+            files_modified: output.files_modified.clone(),
+            output,
+        };
+        let fixed = fix_until_clean(&mut fixer, max_iterations).await?;
+        let WorktreeFixer {
+            output,
+            files_modified,
+            ..
+        } = fixer;
+
+        verify(worktree_path, output, files_modified, fixed).await
     }
 
     /// Internal implementation that uses FunctionTool closures with captured worktree path.
@@ -102,53 +141,255 @@ impl BuilderSkill {
     /// - Create tools using `FunctionTool::new()` with closures
     /// - Closures capture the worktree path at call time
     ///
-    /// Uses provider matching (similar to `run_llm_worker!` macro) because
-    /// `LlmWorker::builder()` requires a concrete type implementing `BaseLlm`,
-    /// not a boxed trait object.
+    /// Resolves the LLM through the provider registry, which requires a
+    /// provider with tool calling support.
     async fn run_internal(
         mission: &str,
         worktree_path: &Path,
         config: &ModelConfig,
     ) -> anyhow::Result<BuilderOutput> {
-        use crate::models::LlmProvider;
-        use radkit::models::providers::{
-            AnthropicLlm, DeepSeekLlm, GeminiLlm, GrokLlm, OpenAILlm, OpenRouterLlm,
-        };
+        use crate::models::registry::{self, ProviderCapabilities};
 
         // Create tools that capture the worktree path
         let tools = create_builder_tools(worktree_path);
 
-        // Match on provider to get concrete LLM type
-        match config.provider {
-            LlmProvider::Anthropic => {
-                let llm = AnthropicLlm::from_env(&config.model)?;
-                run_with_tools(llm, mission, tools).await
-            }
-            LlmProvider::OpenAI => {
-                let mut llm = OpenAILlm::from_env(&config.model)?;
-                if let Some(base_url) = &config.base_url {
-                    llm = llm.with_base_url(base_url);
-                }
-                run_with_tools(llm, mission, tools).await
-            }
-            LlmProvider::Gemini => {
-                let llm = GeminiLlm::from_env(&config.model)?;
-                run_with_tools(llm, mission, tools).await
-            }
-            LlmProvider::OpenRouter => {
-                let llm = OpenRouterLlm::from_env(&config.model)?;
-                run_with_tools(llm, mission, tools).await
+        let llm = registry::resolve(config, ProviderCapabilities::tool_calling())?;
+        run_with_tools(llm, mission, tools).await
+    }
+}
+
+/// The two sides of the compile-fix loop
+#[async_trait]
+trait CompileFixer {
+    /// Errors of a fresh check, empty when it passes
+    async fn check(&mut self) -> anyhow::Result<Vec<CompilerError>>;
+    /// Give the model a pass at `errors`
+    async fn fix(&mut self, errors: &[CompilerError]) -> anyhow::Result<()>;
+}
+
+/// Why the compile-fix loop stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FixExit {
+    Clean,
+    MaxIterations,
+    /// A fix pass left the same errors as the check before it
+    NoProgress,
+}
+
+#[derive(Debug)]
+struct FixOutcome {
+    exit: FixExit,
+    /// Checks run
+    iterations: u32,
+    /// Errors of the last check
+    errors: Vec<CompilerError>,
+}
+
+/// Alternate checks and fix passes until a check is clean, `max_iterations`
+/// checks have run, or a fix pass makes no progress
+async fn fix_until_clean(
+    fixer: &mut impl CompileFixer,
+    max_iterations: u32,
+) -> anyhow::Result<FixOutcome> {
+    let mut previous = None;
+    let mut iterations = 0;
+
+    loop {
+        iterations += 1;
+        let errors = fixer.check().await?;
+        let signature = error_signature(&errors);
+        let exit = if errors.is_empty() {
+            Some(FixExit::Clean)
+        } else if previous.as_ref() == Some(&signature) {
+            Some(FixExit::NoProgress)
+        } else if iterations >= max_iterations.max(1) {
+            Some(FixExit::MaxIterations)
+        } else {
+            None
+        };
+        if let Some(exit) = exit {
+            return Ok(FixOutcome {
+                exit,
+                iterations,
+                errors,
+            });
+        }
+        previous = Some(signature);
+        fixer.fix(&errors).await?;
+    }
+}
+
+/// The output of a run whose fix loop stopped: once the check is clean,
+/// `cargo test` decides `tests_passed`, and its failures become the errors
+async fn verify(
+    worktree_path: &Path,
+    output: BuilderOutput,
+    files_modified: Vec<String>,
+    fixed: FixOutcome,
+) -> anyhow::Result<BuilderOutput> {
+    let build_passed = fixed.exit == FixExit::Clean;
+    let (tests_passed, errors) = if build_passed {
+        let failures = terminal::run_cargo_test(worktree_path).await?.failures();
+        (failures.is_empty(), failures)
+    } else {
+        (false, fixed.errors.iter().map(format_error).collect())
+    };
+    let summary = match fixed.exit {
+        FixExit::Clean if tests_passed => output.summary,
+        FixExit::Clean => format!(
+            "{} (cargo test reports {} failure(s))",
+            output.summary,
+            errors.len()
+        ),
+        FixExit::MaxIterations => format!(
+            "{} (cargo check still reports {} error(s) after {} iteration(s))",
+            output.summary,
+            fixed.errors.len(),
+            fixed.iterations
+        ),
+        FixExit::NoProgress => format!(
+            "{} (stopped after {} iteration(s): the last fix left the same {} error(s))",
+            output.summary,
+            fixed.iterations,
+            fixed.errors.len()
+        ),
+    };
+
+    Ok(BuilderOutput {
+        success: output.success && build_passed && tests_passed,
+        summary,
+        files_modified,
+        errors,
+        build_passed,
+        tests_passed,
+        iterations: fixed.iterations,
+    })
+}
+
+/// Runs `cargo check` in the worktree and fix passes through the model
+struct WorktreeFixer<'a> {
+    mission: &'a str,
+    worktree_path: &'a Path,
+    config: &'a ModelConfig,
+    /// Output of the latest model pass
+    output: BuilderOutput,
+    /// Files modified by any pass
+    files_modified: Vec<String>,
+}
+
+#[async_trait]
+impl CompileFixer for WorktreeFixer<'_> {
+    async fn check(&mut self) -> anyhow::Result<Vec<CompilerError>> {
+        Ok(terminal::run_cargo_check(self.worktree_path)
+            .await?
+            .errors())
+    }
+
+    async fn fix(&mut self, errors: &[CompilerError]) -> anyhow::Result<()> {
+        let prompt = format_fix_prompt(self.mission, self.worktree_path, errors);
+        self.output = BuilderSkill::run_internal(&prompt, self.worktree_path, self.config).await?;
+        for file in &self.output.files_modified {
+            if !self.files_modified.contains(file) {
+                self.files_modified.push(file.clone());
             }
-            LlmProvider::Grok => {
-                let llm = GrokLlm::from_env(&config.model)?;
-                run_with_tools(llm, mission, tools).await
+        }
+        Ok(())
+    }
+}
+
+/// Errors grouped by (file, error code), in file order
+fn group_errors(errors: &[CompilerError]) -> BTreeMap<(&str, &str), Vec<&CompilerError>> {
+    let mut groups: BTreeMap<(&str, &str), Vec<&CompilerError>> = BTreeMap::new();
+    for error in errors {
+        let code = error.code.as_deref().unwrap_or("no code");
+        groups
+            .entry((error.file.as_str(), code))
+            .or_default()
+            .push(error);
+    }
+    groups
+}
+
+/// What a check reported, ignoring positions (a fix elsewhere in the file
+/// shifts lines without making progress on the error)
+fn error_signature(errors: &[CompilerError]) -> BTreeSet<(String, Option<String>, String)> {
+    errors
+        .iter()
+        .map(|e| (e.file.clone(), e.code.clone(), e.message.clone()))
+        .collect()
+}
+
+/// An error in rustc's one-line form
+fn format_error(error: &CompilerError) -> String {
+    if error.file.is_empty() {
+        return format!("error: {}", error.message);
+    }
+    match &error.code {
+        Some(code) => format!(
+            "{}:{}:{}: error[{}]: {}",
+            error.file, error.line, error.column, code, error.message
+        ),
+        None => format!(
+            "{}:{}:{}: error: {}",
+            error.file, error.line, error.column, error.message
+        ),
+    }
+}
+
+/// Lines around `line` (1-based), numbered, with the error line marked
+fn code_span(source: &str, line: u32) -> String {
+    const CONTEXT: u32 = 2;
+    let first = line.saturating_sub(CONTEXT).max(1);
+    source
+        .lines()
+        .enumerate()
+        .map(|(i, text)| (i as u32 + 1, text))
+        .skip_while(|(n, _)| *n < first)
+        .take_while(|(n, _)| *n <= line + CONTEXT)
+        .map(|(n, text)| {
+            let marker = if n == line { '>' } else { ' ' };
+            format!("{}{:>5} | {}\n", marker, n, text)
+        })
+        .collect()
+}
+
+/// Prompt for a fix pass: the mission, then the errors of the last check
+/// with the code they point at
+fn format_fix_prompt(mission: &str, worktree_path: &Path, errors: &[CompilerError]) -> String {
+    let mut prompt = format!(
+        "{}\n\n## Compile Errors\n\n`cargo check` fails with the errors below. \
+         Fix them without rewriting unrelated code.\n\n",
+        mission
+    );
+    let mut sources: HashMap<&str, Option<String>> = HashMap::new();
+
+    for ((file, code), group) in group_errors(errors) {
+        if file.is_empty() {
+            // Cargo failed without pointing at code
+            prompt.push_str("### cargo\n\n");
+            for error in group {
+                prompt.push_str(&format!("```text\n{}\n```\n\n", error.message));
             }
-            LlmProvider::DeepSeek => {
-                let llm = DeepSeekLlm::from_env(&config.model)?;
-                run_with_tools(llm, mission, tools).await
+            continue;
+        }
+        prompt.push_str(&format!("### `{}` ({})\n\n", file, code));
+        let source = sources
+            .entry(file)
+            .or_insert_with(|| std::fs::read_to_string(worktree_path.join(file)).ok());
+        for error in group {
+            prompt.push_str(&format!(
+                "- {}:{}: {}\n",
+                error.line, error.column, error.message
+            ));
+            if let Some(source) = source {
+                prompt.push_str(&format!(
+                    "\n```rust\n{}```\n\n",
+                    code_span(source, error.line)
+                ));
             }
         }
     }
+    prompt
 }
 
 /// Builder tools tuple type for cleaner code
@@ -491,6 +732,7 @@ const SYSTEM_PROMPT: &str = include_str!("defaults/builder.md");
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tools::terminal::ErrorLevel;
 
     #[test]
     fn test_builder_output_serialization() {
@@ -508,4 +750,236 @@ mod tests {
         assert!(json.contains("success"));
         assert!(json.contains("src/lib.rs"));
     }
+
+    fn error(file: &str, line: u32, code: Option<&str>, message: &str) -> CompilerError {
+        CompilerError {
+            file: file.to_string(),
+            line,
+            column: 5,
+            message: message.to_string(),
+            code: code.map(str::to_string),
+            level: ErrorLevel::Error,
+        }
+    }
+
+    #[test]
+    fn test_group_errors_and_signature() {
+        let errors = vec![
+            error("src/b.rs", 3, Some("E0308"), "mismatched types"),
+            error("src/a.rs", 9, Some("E0425"), "cannot find value `x`"),
+            error("src/b.rs", 7, Some("E0308"), "mismatched types"),
+            error("src/b.rs", 1, None, "expected item"),
+        ];
+
+        let groups = group_errors(&errors);
+        let keys: Vec<_> = groups.keys().copied().collect();
+        assert_eq!(
+            keys,
+            vec![
+                ("src/a.rs", "E0425"),
+                ("src/b.rs", "E0308"),
+                ("src/b.rs", "no code")
+            ]
+        );
+        assert_eq!(groups[&("src/b.rs", "E0308")].len(), 2);
+
+        // Moving an error to another line is not progress
+        let shifted: Vec<_> = errors
+            .iter()
+            .cloned()
+            .map(|mut e| {
+                e.line += 4;
+                e
+            })
+            .collect();
+        assert_eq!(error_signature(&errors), error_signature(&shifted));
+        assert_ne!(error_signature(&errors), error_signature(&errors[..3]));
+
+        assert_eq!(
+            format_error(&errors[0]),
+            "src/b.rs:3:5: error[E0308]: mismatched types"
+        );
+        assert_eq!(
+            format_error(&errors[3]),
+            "src/b.rs:1:5: error: expected item"
+        );
+    }
+
+    #[test]
+    fn test_fix_prompt_includes_code_spans() {
+        let dir = std::env::temp_dir().join("catalyst_test_builder_fix_prompt");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(
+            dir.join("src/lib.rs"),
+            "fn one() {}\nfn two() {}\nfn three() -> u32 { \"3\" }\nfn four() {}\nfn five() {}\nfn six() {}\n",
+        )
+        .unwrap();
+
+        let prompt = format_fix_prompt(
+            "Implement the limiter",
+            &dir,
+            &[
+                error("src/lib.rs", 3, Some("E0308"), "mismatched types"),
+                error("src/gone.rs", 1, Some("E0308"), "mismatched types"),
+            ],
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(prompt.starts_with("Implement the limiter"));
+        assert!(prompt.contains("### `src/lib.rs` (E0308)"));
+        assert!(prompt.contains(">    3 | fn three() -> u32 { \"3\" }"));
+        assert!(prompt.contains("     1 | fn one() {}"));
+        assert!(prompt.contains("     5 | fn five() {}"));
+        assert!(!prompt.contains("fn six"));
+        // Files that cannot be read still list their errors
+        assert!(prompt.contains("### `src/gone.rs` (E0308)"));
+    }
+
+    /// Replays one error set per check and counts fix passes
+    struct ScriptedFixer {
+        checks: std::collections::VecDeque<Vec<CompilerError>>,
+        fixes: u32,
+    }
+
+    impl ScriptedFixer {
+        fn new(checks: Vec<Vec<CompilerError>>) -> Self {
+            Self {
+                checks: checks.into(),
+                fixes: 0,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl CompileFixer for ScriptedFixer {
+        async fn check(&mut self) -> anyhow::Result<Vec<CompilerError>> {
+            Ok(self.checks.pop_front().expect("ran out of scripted checks"))
+        }
+
+        async fn fix(&mut self, _errors: &[CompilerError]) -> anyhow::Result<()> {
+            self.fixes += 1;
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fix_loop_stops_when_clean() {
+        let mut fixer = ScriptedFixer::new(vec![
+            vec![error("src/lib.rs", 3, Some("E0308"), "mismatched types")],
+            vec![error(
+                "src/lib.rs",
+                9,
+                Some("E0425"),
+                "cannot find value `x`",
+            )],
+            vec![],
+        ]);
+        let outcome = fix_until_clean(&mut fixer, 5).await.unwrap();
+
+        assert_eq!(outcome.exit, FixExit::Clean);
+        assert_eq!(outcome.iterations, 3);
+        assert!(outcome.errors.is_empty());
+        assert_eq!(fixer.fixes, 2);
+    }
+
+    #[tokio::test]
+    async fn test_fix_loop_stops_at_max_iterations() {
+        let mut fixer = ScriptedFixer::new(vec![
+            vec![error("src/lib.rs", 3, Some("E0308"), "mismatched types")],
+            vec![error(
+                "src/lib.rs",
+                9,
+                Some("E0425"),
+                "cannot find value `x`",
+            )],
+        ]);
+        let outcome = fix_until_clean(&mut fixer, 2).await.unwrap();
+
+        assert_eq!(outcome.exit, FixExit::MaxIterations);
+        assert_eq!(outcome.iterations, 2);
+        assert_eq!(outcome.errors[0].code.as_deref(), Some("E0425"));
+        assert_eq!(fixer.fixes, 1);
+    }
+
+    #[tokio::test]
+    async fn test_fix_loop_stops_on_the_same_errors_twice() {
+        let mut fixer = ScriptedFixer::new(vec![
+            vec![error("src/lib.rs", 3, Some("E0308"), "mismatched types")],
+            // Same error, moved by the fix
+            vec![error("src/lib.rs", 5, Some("E0308"), "mismatched types")],
+        ]);
+        let outcome = fix_until_clean(&mut fixer, 5).await.unwrap();
+
+        assert_eq!(outcome.exit, FixExit::NoProgress);
+        assert_eq!(outcome.iterations, 2);
+        assert_eq!(outcome.errors.len(), 1);
+        assert_eq!(fixer.fixes, 1);
+    }
+
+    #[test]
+    fn test_errors_without_a_span_reach_the_prompt() {
+        let failure = CompilerError {
+            file: String::new(),
+            line: 0,
+            column: 0,
+            message: "error[E0601]: `main` function not found in crate `probe`".to_string(),
+            code: None,
+            level: ErrorLevel::Error,
+        };
+        assert_eq!(
+            format_error(&failure),
+            "error: error[E0601]: `main` function not found in crate `probe`"
+        );
+
+        let prompt = format_fix_prompt("Implement the probe", Path::new("."), &[failure]);
+        assert!(prompt.contains("### cargo\n"));
+        assert!(prompt.contains("`main` function not found"));
+    }
+
+    #[tokio::test]
+    async fn test_failing_tests_fail_the_run() {
+        let dir = std::env::temp_dir().join("catalyst_test_builder_verify");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(
+            dir.join("Cargo.toml"),
+            "[package]\nname = \"verify_probe\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("src/lib.rs"),
+            "#[test]\nfn doubles() {\n    assert_eq!(2 + 2, 5);\n}\n",
+        )
+        .unwrap();
+
+        // The model claims success; the tests say otherwise
+        let claimed = BuilderOutput {
+            success: true,
+            summary: "Implemented doubling".to_string(),
+            files_modified: vec!["src/lib.rs".to_string()],
+            errors: vec![],
+            build_passed: true,
+            tests_passed: true,
+            iterations: 0,
+        };
+        let clean = FixOutcome {
+            exit: FixExit::Clean,
+            iterations: 1,
+            errors: vec![],
+        };
+        let output = verify(&dir, claimed, vec!["src/lib.rs".to_string()], clean)
+            .await
+            .unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(output.build_passed);
+        assert!(!output.tests_passed);
+        assert!(!output.success);
+        assert_eq!(output.errors.len(), 1);
+        assert!(output.errors[0].starts_with("doubles failed"));
+        assert!(output
+            .summary
+            .ends_with("(cargo test reports 1 failure(s))"));
+    }
 }
@@ -18,6 +18,7 @@ use crate::skills::artifact_registry::{ConstraintArtifact, ConstraintViolationSu
 use crate::tools::linter::{
     scan_content, scan_file_with_config, ConstraintConfig, Violation, ViolationKind,
 };
+use anyhow::{Context, Result};
 use async_trait::async_trait;
 use radkit::agent::{Artifact, OnRequestResult, SkillHandler, SkillSlot};
 use radkit::errors::{AgentError, AgentResult};
@@ -85,11 +86,16 @@ impl From<&Violation> for ViolationInfo {
 )]
 pub struct ConstraintSkill {
     config: ConstraintConfig,
+    /// Treat `unwrap()` as a blocking violation (Fortress mode)
+    forbid_unwrap: bool,
 }
 
 impl ConstraintSkill {
     pub fn new(config: ConstraintConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            forbid_unwrap: false,
+        }
     }
 
     pub fn default() -> Self {
@@ -104,6 +110,43 @@ impl ConstraintSkill {
             ..Default::default()
         })
     }
+
+    /// Block on `unwrap()` as well as on length violations
+    pub fn with_forbid_unwrap(mut self, forbid_unwrap: bool) -> Self {
+        self.forbid_unwrap = forbid_unwrap;
+        self
+    }
+
+    /// Whether a violation needs fixing (or approval) before proceeding
+    pub fn is_blocking(&self, violation: &Violation) -> bool {
+        match violation.kind {
+            ViolationKind::FileTooLong | ViolationKind::FunctionTooLong => true,
+            ViolationKind::UnwrapUsed => self.forbid_unwrap,
+            ViolationKind::MissingDocumentation => false,
+        }
+    }
+
+    /// Blocking violations in Rust files under `root`
+    ///
+    /// `files` are paths relative to `root`; other files and files that no
+    /// longer exist are ignored. Violations report the relative path.
+    pub fn check_files(&self, root: &Path, files: &[String]) -> Result<Vec<Violation>> {
+        let mut blocking = Vec::new();
+        for file in files.iter().filter(|f| f.ends_with(".rs")) {
+            let path = root.join(file);
+            if !path.is_file() || self.config.is_exempt(file) {
+                continue;
+            }
+            let content = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            blocking.extend(
+                scan_content(&content, file, &self.config)?
+                    .into_iter()
+                    .filter(|v| self.is_blocking(v)),
+            );
+        }
+        Ok(blocking)
+    }
 }
 
 #[async_trait]
@@ -125,15 +168,8 @@ impl SkillHandler for ConstraintSkill {
         let (violations, file_path) = self.scan_input(input).await?;
 
         // Check for blocking violations (file/function too long)
-        let blocking_violations: Vec<_> = violations
-            .iter()
-            .filter(|v| {
-                matches!(
-                    v.kind,
-                    ViolationKind::FileTooLong | ViolationKind::FunctionTooLong
-                )
-            })
-            .collect();
+        let blocking_violations: Vec<_> =
+            violations.iter().filter(|v| self.is_blocking(v)).collect();
 
         if !blocking_violations.is_empty() {
             // Save state for multi-turn approval flow
@@ -376,3 +412,33 @@ impl ConstraintSkill {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_files_blocks_unwrap_only_when_forbidden() {
+        let dir = std::env::temp_dir().join("catalyst_test_constraint_check_files");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(
+            dir.join("src/lib.rs"),
+            "pub fn port() -> u16 {\n    \"80\".parse().unwrap()\n}\n",
+        )
+        .unwrap();
+        let files = vec!["src/lib.rs".to_string(), "README.md".to_string()];
+
+        let lenient = ConstraintSkill::default();
+        assert!(lenient.check_files(&dir, &files).unwrap().is_empty());
+
+        let strict = ConstraintSkill::default().with_forbid_unwrap(true);
+        let violations = strict.check_files(&dir, &files).unwrap();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].kind, ViolationKind::UnwrapUsed);
+        assert_eq!(violations[0].file, "src/lib.rs");
+        assert_eq!(violations[0].line, Some(2));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -3,7 +3,7 @@
 //! A2A-native skill that reviews decisions and code.
 //! Provides feedback and approval/rejection verdicts.
 
-use crate::models::ModelConfig;
+use crate::models::{ModelConfig, ValidatedOutput};
 use crate::run_llm_function;
 use crate::skills::artifact_registry::{ConcernSummary, ReviewArtifact};
 use async_trait::async_trait;
@@ -20,6 +20,7 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, LLMOutput)]
 pub struct Concern {
     /// Severity: "blocking", "major", "minor", "suggestion"
+    #[schemars(extend("enum" = ["blocking", "major", "minor", "suggestion"]))]
     pub severity: String,
     /// Description of the concern
     pub description: String,
@@ -32,15 +33,33 @@ pub struct Concern {
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, LLMOutput)]
 pub struct CriticOutput {
     /// Overall verdict: "approved", "needs_changes", "rejected"
+    #[schemars(extend("enum" = ["approved", "needs_changes", "rejected"]))]
     pub verdict: String,
     /// Summary of the review
     pub summary: String,
     /// List of concerns found
     pub concerns: Vec<Concern>,
     /// Confidence in the verdict (0.0 - 1.0)
+    #[schemars(range(min = 0.0, max = 1.0))]
     pub confidence: f32,
 }
 
+impl ValidatedOutput for CriticOutput {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        if self.summary.trim().is_empty() {
+            errors.push("summary: must not be empty".to_string());
+        }
+        if self.verdict != "approved" && self.concerns.is_empty() {
+            errors.push(format!(
+                "concerns: a \"{}\" verdict must list at least one concern",
+                self.verdict
+            ));
+        }
+        errors
+    }
+}
+
 /// Critic skill for reviewing decisions and code
 #[skill(
     id = "review",
@@ -81,6 +100,20 @@ impl CriticSkill {
         );
         run_llm_function!(config, CriticOutput, SYSTEM_PROMPT, prompt)
     }
+
+    /// Review the code a Builder produced for a mission
+    pub async fn review_diff(
+        diff: &str,
+        mission: &str,
+        mode: &str,
+        config: &ModelConfig,
+    ) -> anyhow::Result<CriticOutput> {
+        let prompt = format!(
+            "Code Diff to Review:\n{}\n\nMission:\n{}\n\nMode: {}",
+            diff, mission, mode
+        );
+        run_llm_function!(config, CriticOutput, SYSTEM_PROMPT, prompt)
+    }
 }
 
 #[async_trait]
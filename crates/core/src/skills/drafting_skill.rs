@@ -20,7 +20,7 @@
 //!                                   Lead Builder verifies
 //! ```
 
-use crate::models::ModelConfig;
+use crate::models::{ModelConfig, ValidatedOutput};
 use crate::run_llm_function;
 use radkit::macros::LLMOutput;
 use schemars::JsonSchema;
@@ -61,6 +61,19 @@ pub struct DraftingOutput {
     pub notes: Option<String>,
 }
 
+impl ValidatedOutput for DraftingOutput {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        if self.file_path.trim().is_empty() {
+            errors.push("file_path: must not be empty".to_string());
+        }
+        if self.source_code.trim().is_empty() {
+            errors.push("source_code: must contain the generated file".to_string());
+        }
+        errors
+    }
+}
+
 /// Stateless drafting skill for parallel file generation
 ///
 /// Unlike other skills, DraftingSkill has no A2A handler - it's called
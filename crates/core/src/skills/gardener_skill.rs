@@ -0,0 +1,446 @@
+//! # Gardener Skill
+//!
+//! Maintenance sweep over a project. Combines the linter, the symbol index
+//! and the tree-sitter semantic map to find:
+//!
+//! - **Oversized files** past the Rule of 100 file limit
+//! - **Unwrap hot spots**: files with many `.unwrap()` calls
+//! - **Dead modules**: `.rs` files no `mod` declaration pulls in
+//! - **Stale TODOs**: `TODO`/`FIXME` comments untouched for weeks (per `git blame`)
+//!
+//! Issues are filed as work: high-priority ones become Features (stage
+//! `Idea`), the rest become ideas tagged `gardener`. Issues that are already
+//! filed are skipped, so the sweep can run periodically.
+
+use crate::state::{CatalystDb, ContextManager, FeatureManager};
+use crate::tools::linter::{self, ConstraintConfig, ViolationKind};
+use crate::tools::scanner::{self, SymbolKind};
+use crate::tools::{ast_scanner, git};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Tag on the ideas the Gardener files
+pub const GARDENER_TAG: &str = "gardener";
+
+/// Kind of maintenance issue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GardenIssueKind {
+    OversizedFile,
+    UnwrapHotSpot,
+    DeadModule,
+    StaleTodo,
+}
+
+/// How urgently an issue should be dealt with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum GardenPriority {
+    Low,
+    Medium,
+    High,
+}
+
+/// A maintenance issue in one file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GardenIssue {
+    pub kind: GardenIssueKind,
+    pub priority: GardenPriority,
+    /// File path relative to the project root
+    pub file: String,
+    #[serde(default)]
+    pub line: Option<u32>,
+    pub description: String,
+}
+
+impl GardenIssue {
+    /// Title of the Feature or idea filed for this issue
+    pub fn title(&self) -> String {
+        let action = match self.kind {
+            GardenIssueKind::OversizedFile => "Split oversized file",
+            GardenIssueKind::UnwrapHotSpot => "Handle errors instead of unwrapping in",
+            GardenIssueKind::DeadModule => "Remove or wire up dead module",
+            GardenIssueKind::StaleTodo => "Resolve stale TODOs in",
+        };
+        format!("Garden: {} {}", action, self.file)
+    }
+}
+
+/// Result of a sweep, most urgent issues first
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GardenReport {
+    pub files_scanned: usize,
+    pub issues: Vec<GardenIssue>,
+}
+
+/// What a report was filed as
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GardenHarvest {
+    /// IDs of the Features created
+    pub features: Vec<String>,
+    /// IDs of the ideas created
+    pub ideas: Vec<String>,
+    /// Issues that were already filed
+    pub skipped: usize,
+}
+
+/// Thresholds for a sweep
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GardenerConfig {
+    /// File limits and exemptions (shared with the linter)
+    #[serde(default)]
+    pub constraints: ConstraintConfig,
+    /// `.unwrap()` calls that make a file a hot spot
+    #[serde(default = "default_unwrap_hot_spot")]
+    pub unwrap_hot_spot: usize,
+    /// Age in days after which a TODO is stale
+    #[serde(default = "default_stale_todo_days")]
+    pub stale_todo_days: u32,
+}
+
+fn default_unwrap_hot_spot() -> usize {
+    5
+}
+
+fn default_stale_todo_days() -> u32 {
+    30
+}
+
+impl Default for GardenerConfig {
+    fn default() -> Self {
+        Self {
+            constraints: ConstraintConfig::default(),
+            unwrap_hot_spot: default_unwrap_hot_spot(),
+            stale_todo_days: default_stale_todo_days(),
+        }
+    }
+}
+
+/// Gardener skill for periodic codebase maintenance
+pub struct GardenerSkill {
+    config: GardenerConfig,
+}
+
+impl Default for GardenerSkill {
+    fn default() -> Self {
+        Self::new(GardenerConfig::default())
+    }
+}
+
+impl GardenerSkill {
+    pub fn new(config: GardenerConfig) -> Self {
+        Self { config }
+    }
+
+    /// Sweep the Rust sources under `root`
+    pub async fn analyze(&self, root: &Path) -> Result<GardenReport> {
+        let relative = |path: &Path| -> String {
+            let path = path.strip_prefix(root).unwrap_or(path);
+            path.to_string_lossy().trim_start_matches("./").to_string()
+        };
+
+        let files: Vec<String> = rust_files(root).iter().map(|f| relative(f)).collect();
+        let violations =
+            linter::scan_directory_with_config(root, &["rs"], &self.config.constraints)?;
+        let index = scanner::build_index(root)?;
+        let map = match ast_scanner::build_semantic_map(root).await {
+            Ok(map) => map,
+            Err(e) => {
+                tracing::warn!("Semantic map unavailable, using the symbol index: {:#}", e);
+                ast_scanner::SemanticMap::default()
+            }
+        };
+
+        let mut symbols: HashMap<String, usize> = HashMap::new();
+        let mut declared: HashSet<&str> = map.modules.keys().map(String::as_str).collect();
+        for symbol in &index.symbols {
+            if symbol.kind == SymbolKind::Module {
+                declared.insert(&symbol.name);
+            } else {
+                *symbols.entry(relative(&symbol.file)).or_default() += 1;
+            }
+        }
+
+        let mut issues = Vec::new();
+        let mut unwraps: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+        for violation in &violations {
+            let file = relative(Path::new(&violation.file));
+            if file.starts_with(".catalyst/") {
+                continue; // Feature worktrees
+            }
+            match violation.kind {
+                ViolationKind::FileTooLong => {
+                    let lines = std::fs::read_to_string(root.join(&file))
+                        .map(|c| c.lines().count())
+                        .unwrap_or_default();
+                    let priority = if lines > 2 * self.config.constraints.max_file_lines {
+                        GardenPriority::High
+                    } else {
+                        GardenPriority::Medium
+                    };
+                    issues.push(GardenIssue {
+                        kind: GardenIssueKind::OversizedFile,
+                        priority,
+                        description: format!(
+                            "{} ({} symbols)",
+                            violation.message,
+                            symbols.get(&file).copied().unwrap_or_default()
+                        ),
+                        file,
+                        line: None,
+                    });
+                }
+                ViolationKind::UnwrapUsed => {
+                    unwraps.entry(file).or_default().extend(violation.line);
+                }
+                _ => {}
+            }
+        }
+
+        for (file, lines) in unwraps {
+            if lines.len() < self.config.unwrap_hot_spot {
+                continue;
+            }
+            let priority = if lines.len() >= 3 * self.config.unwrap_hot_spot {
+                GardenPriority::High
+            } else {
+                GardenPriority::Medium
+            };
+            issues.push(GardenIssue {
+                kind: GardenIssueKind::UnwrapHotSpot,
+                priority,
+                description: format!(".unwrap() called {} times", lines.len()),
+                line: lines.first().copied(),
+                file,
+            });
+        }
+
+        // Files whose module name no `mod` declaration mentions are not compiled
+        for file in &files {
+            if is_crate_root(file) || declared.contains(module_name(file).as_str()) {
+                continue;
+            }
+            issues.push(GardenIssue {
+                kind: GardenIssueKind::DeadModule,
+                priority: GardenPriority::Low,
+                description: format!(
+                    "No `mod {}` declaration includes this file ({} symbols)",
+                    module_name(file),
+                    symbols.get(file).copied().unwrap_or_default()
+                ),
+                file: file.clone(),
+                line: None,
+            });
+        }
+
+        issues.extend(self.stale_todos(root, &files));
+        issues.sort_by(|a, b| b.priority.cmp(&a.priority).then(a.file.cmp(&b.file)));
+
+        Ok(GardenReport {
+            files_scanned: files.len(),
+            issues,
+        })
+    }
+
+    /// File a report's issues that are not filed yet
+    ///
+    /// High-priority issues become Features, the rest ideas tagged
+    /// [`GARDENER_TAG`].
+    pub fn plant(&self, report: &GardenReport, db: &CatalystDb) -> Result<GardenHarvest> {
+        let fm = FeatureManager::new(db);
+        let cm = ContextManager::new(db);
+        let mut filed: HashSet<String> = fm.list_all()?.into_iter().map(|f| f.title).collect();
+        filed.extend(
+            cm.list_ideas()?
+                .into_iter()
+                .filter_map(|idea| idea.content.lines().next().map(str::to_string)),
+        );
+
+        let mut harvest = GardenHarvest::default();
+        for issue in &report.issues {
+            let title = issue.title();
+            if !filed.insert(title.clone()) {
+                harvest.skipped += 1;
+                continue;
+            }
+            let location = match issue.line {
+                Some(line) => format!("{}:{}", issue.file, line),
+                None => issue.file.clone(),
+            };
+            let description = format!("{}\n\n{}", location, issue.description);
+
+            if issue.priority == GardenPriority::High {
+                let mut feature = fm.create(&title)?;
+                feature.description = Some(description);
+                fm.save(&feature)?;
+                harvest.features.push(feature.id);
+            } else {
+                let content = format!("{}\n\n{}", title, description);
+                let idea = cm.create_tagged_idea(&content, &[GARDENER_TAG])?;
+                harvest.ideas.push(idea.id);
+            }
+        }
+        Ok(harvest)
+    }
+
+    /// TODO/FIXME comments older than the configured age, one issue per file
+    ///
+    /// Without git history nothing is stale.
+    fn stale_todos(&self, root: &Path, files: &[String]) -> Vec<GardenIssue> {
+        let cutoff =
+            chrono::Utc::now().timestamp() - i64::from(self.config.stale_todo_days) * 24 * 60 * 60;
+        let mut issues = Vec::new();
+        for file in files {
+            let Ok(content) = std::fs::read_to_string(root.join(file)) else {
+                continue;
+            };
+            let todos: Vec<u32> = content
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| is_todo(line))
+                .map(|(i, _)| i as u32 + 1)
+                .collect();
+            if todos.is_empty() {
+                continue;
+            }
+            let Ok(times) = git::line_commit_times(root, Path::new(file), &todos) else {
+                continue;
+            };
+            let stale: Vec<(u32, i64)> = times.into_iter().filter(|(_, t)| *t < cutoff).collect();
+            let Some(&(line, oldest)) = stale.iter().min_by_key(|(_, t)| *t) else {
+                continue;
+            };
+            let days = (chrono::Utc::now().timestamp() - oldest) / (24 * 60 * 60);
+            issues.push(GardenIssue {
+                kind: GardenIssueKind::StaleTodo,
+                priority: GardenPriority::Low,
+                file: file.clone(),
+                line: Some(line),
+                description: format!(
+                    "{} TODO/FIXME comments older than {} days (oldest: {} days)",
+                    stale.len(),
+                    self.config.stale_todo_days,
+                    days
+                ),
+            });
+        }
+        issues
+    }
+}
+
+/// Rust files under `root`, skipping Catalyst's own runtime directory
+fn rust_files(root: &Path) -> Vec<PathBuf> {
+    ignore::WalkBuilder::new(root)
+        .hidden(false)
+        .git_ignore(true)
+        .filter_entry(|entry| entry.file_name() != ".catalyst" && entry.file_name() != ".git")
+        .build()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.into_path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "rs"))
+        .collect()
+}
+
+/// Files compiled without a `mod` declaration (crate roots, tests, examples)
+fn is_crate_root(file: &str) -> bool {
+    let path = Path::new(file);
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+    matches!(name, "lib.rs" | "main.rs" | "build.rs")
+        || path.components().any(|c| {
+            matches!(
+                c.as_os_str().to_str(),
+                Some("tests" | "examples" | "benches" | "bin")
+            )
+        })
+}
+
+/// Module name a file is declared under (`a/b.rs` and `a/b/mod.rs` are `b`)
+fn module_name(file: &str) -> String {
+    let path = Path::new(file);
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+    if stem == "mod" {
+        if let Some(dir) = path.parent().and_then(|p| p.file_name()) {
+            return dir.to_string_lossy().to_string();
+        }
+    }
+    stem.to_string()
+}
+
+fn is_todo(line: &str) -> bool {
+    line.find("//")
+        .map(|i| &line[i..])
+        .is_some_and(|comment| comment.contains("TODO") || comment.contains("FIXME"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_analyze_and_plant() {
+        let dir = std::env::temp_dir().join("catalyst_test_gardener");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(dir.join("src/lib.rs"), "pub mod big;\npub mod risky;\n").unwrap();
+        std::fs::write(dir.join("src/big.rs"), "pub fn f() {}\n".repeat(400)).unwrap();
+        std::fs::write(
+            dir.join("src/risky.rs"),
+            "pub fn g(s: &str) -> u32 {\n    s.parse().unwrap()\n}\n".repeat(5),
+        )
+        .unwrap();
+        std::fs::write(dir.join("src/orphan.rs"), "pub struct Lost;\n").unwrap();
+
+        let report = GardenerSkill::default().analyze(&dir).await.unwrap();
+        assert_eq!(report.files_scanned, 4);
+        let kinds: Vec<(GardenIssueKind, &str)> = report
+            .issues
+            .iter()
+            .map(|i| (i.kind, i.file.as_str()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (GardenIssueKind::OversizedFile, "src/big.rs"),
+                (GardenIssueKind::UnwrapHotSpot, "src/risky.rs"),
+                (GardenIssueKind::DeadModule, "src/orphan.rs"),
+            ]
+        );
+        assert_eq!(report.issues[0].priority, GardenPriority::High);
+
+        let db_path = ".catalyst/test_gardener.db";
+        let _ = std::fs::remove_file(db_path);
+        let db = CatalystDb::open_at(db_path).unwrap();
+        let harvest = GardenerSkill::default().plant(&report, &db).unwrap();
+        assert_eq!(harvest.features.len(), 1);
+        assert_eq!(harvest.ideas.len(), 2);
+        let ideas = ContextManager::new(&db).list_ideas().unwrap();
+        assert!(ideas.iter().all(|i| i.tags == vec![GARDENER_TAG]));
+
+        // A second sweep files nothing new
+        let again = GardenerSkill::default().plant(&report, &db).unwrap();
+        assert!(again.features.is_empty() && again.ideas.is_empty());
+        assert_eq!(again.skipped, 3);
+
+        drop(db);
+        let _ = std::fs::remove_file(db_path);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_module_names_and_todos() {
+        assert_eq!(module_name("src/tools/mod.rs"), "tools");
+        assert_eq!(module_name("src/tools/git.rs"), "git");
+        assert!(is_crate_root("src/bin/catalyst.rs"));
+        assert!(!is_crate_root("src/tools/git.rs"));
+        assert!(is_todo("    let x = 1; // TODO: remove"));
+        assert!(!is_todo("let todo = \"TODO\";"));
+    }
+}
@@ -19,7 +19,7 @@
 //! - **Ours**: Current branch (`:2:path`)
 //! - **Theirs**: Incoming branch (`:3:path`)
 
-use crate::models::ModelConfig;
+use crate::models::{ModelConfig, ValidatedOutput};
 use crate::run_llm_function;
 use crate::skills::artifact_registry::{MergeArtifact, MergeResolutionSummary};
 use crate::tools::merge::{
@@ -46,11 +46,27 @@ pub struct MergeResolution {
     /// The resolved/merged code content
     pub resolved_content: String,
     /// Strategy used: "semantic_merge", "prefer_ours", "prefer_theirs"
+    #[schemars(extend("enum" = ["semantic_merge", "prefer_ours", "prefer_theirs"]))]
     pub strategy: String,
     /// Explanation of merge decisions
     pub explanation: String,
 }
 
+impl ValidatedOutput for MergeResolution {
+    fn validate(&self) -> Vec<String> {
+        let has_markers = self.resolved_content.lines().any(|line| {
+            line.starts_with("<<<<<<<")
+                || line.starts_with("=======")
+                || line.starts_with(">>>>>>>")
+        });
+        if has_markers {
+            vec!["resolved_content: still contains conflict markers".to_string()]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
 /// Input for merge skill
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct MergeInput {
@@ -28,6 +28,10 @@
 //! **Safety Layer Skills** (cyborg validation):
 //! - `ConstraintSkill` - Validate code against Rule of 100
 //! - `MergeSkill` - Resolve git merge conflicts
+//! - `RedTeamSkill` - Write hostile tests before the Builder runs
+//!
+//! **Maintenance Skills:**
+//! - `GardenerSkill` - Find maintenance work and file it as features/ideas
 //!
 //! **Utility Skills:**
 //! - `WebScraperSkill` - Clean HTML for research
@@ -56,6 +60,10 @@ pub mod drafting_skill;
 // Safety Layer Skills
 pub mod constraint_skill;
 pub mod merge_skill;
+pub mod red_team_skill;
+
+// Maintenance Skills
+pub mod gardener_skill;
 
 // Meta-Agent Skills
 pub mod orchestrator_skill;
@@ -73,9 +81,11 @@ pub use builder_skill::BuilderSkill;
 pub use constraint_skill::ConstraintSkill;
 pub use critic_skill::CriticSkill;
 pub use drafting_skill::{DraftingMission, DraftingOutput, DraftingSkill};
+pub use gardener_skill::{GardenHarvest, GardenReport, GardenerConfig, GardenerSkill};
 pub use merge_skill::MergeSkill;
 pub use orchestrator_skill::OrchestratorSkill;
 pub use parse_skill::ParseSkill;
+pub use red_team_skill::{RedTeamOutput, RedTeamSkill};
 pub use researcher_skill::ResearcherSkill;
 pub use taskmaster_skill::TaskmasterSkill;
 pub use webscraper_skill::WebScraperSkill;
@@ -234,6 +234,8 @@ impl SkillHandler for OrchestratorSkill {
             let decision = ArchitectSkill::run(
                 &ambiguity.id,
                 &research_json,
+                &[],
+                None,
                 "", // Would load spec here
                 "lab",
                 &self.config,
@@ -4,7 +4,7 @@
 //! This is the first step in "Compile-Time Intelligence" - identifying
 //! unknowns that must be resolved BEFORE code generation.
 
-use crate::models::ModelConfig;
+use crate::models::{ModelConfig, ValidatedOutput};
 use crate::run_llm_function;
 use crate::skills::artifact_registry::{AmbiguitySummary, InferredKnownSummary, UnknownsArtifact};
 use crate::state::CodebaseProfile;
@@ -48,6 +48,9 @@ pub struct Ambiguity {
     pub criticality: Criticality,
     #[serde(default)]
     pub context: Option<String>,
+    /// IDs of ambiguities whose answers constrain this one
+    #[serde(default)]
+    pub depends_on: Vec<String>,
 }
 
 /// An ambiguity that was auto-resolved from codebase analysis
@@ -69,6 +72,42 @@ pub struct ParseOutput {
     pub ambiguities: Vec<Ambiguity>,
 }
 
+impl ValidatedOutput for ParseOutput {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        let mut seen = std::collections::HashSet::new();
+        let ids: std::collections::HashSet<&str> =
+            self.ambiguities.iter().map(|a| a.id.as_str()).collect();
+        for (i, ambiguity) in self.ambiguities.iter().enumerate() {
+            if ambiguity.id.trim().is_empty() {
+                errors.push(format!("ambiguities[{}].id: must not be empty", i));
+            } else if !seen.insert(ambiguity.id.as_str()) {
+                errors.push(format!(
+                    "ambiguities[{}].id: duplicate id \"{}\"",
+                    i, ambiguity.id
+                ));
+            }
+            if ambiguity.question.trim().is_empty() {
+                errors.push(format!("ambiguities[{}].question: must not be empty", i));
+            }
+            for dependency in &ambiguity.depends_on {
+                if dependency == &ambiguity.id {
+                    errors.push(format!(
+                        "ambiguities[{}].depends_on: \"{}\" cannot depend on itself",
+                        i, dependency
+                    ));
+                } else if !ids.contains(dependency.as_str()) {
+                    errors.push(format!(
+                        "ambiguities[{}].depends_on: unknown id \"{}\"",
+                        i, dependency
+                    ));
+                }
+            }
+        }
+        errors
+    }
+}
+
 /// Extended output including inferred knowns
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParseOutputWithKnowns {
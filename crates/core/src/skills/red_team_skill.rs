@@ -0,0 +1,251 @@
+//! # Red Team Skill
+//!
+//! Stateless LlmFunction that writes hostile tests for a feature before the
+//! Builder touches it.
+//!
+//! ## Flow
+//!
+//! ```text
+//! MissionPrompt + AtomizerOutput.test_modules → RedTeamSkill → RedTeamOutput
+//!                                                                    ↓
+//!                                              written into the feature worktree
+//!                                                                    ↓
+//!                                     Builder implements until they pass (unmodified)
+//! ```
+//!
+//! The suite is the feature's contract: the Coordinator only lets a feature
+//! reach `Merging` when every red-team file still has the content written
+//! here and `cargo test --test <file>` runs and passes its tests in the
+//! worktree. Files therefore sit directly under `tests/`, the only place cargo
+//! builds integration test targets from.
+
+use crate::models::{ModelConfig, ValidatedOutput};
+use crate::run_llm_function;
+use crate::skills::atomizer_skill::AtomizerOutput;
+use crate::skills::taskmaster_skill::MissionPrompt;
+use anyhow::{Context, Result};
+use radkit::macros::LLMOutput;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path};
+
+/// One test file of the red-team suite
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, LLMOutput)]
+pub struct RedTeamTest {
+    /// File path relative to the project root (`tests/<name>.rs`)
+    pub path: String,
+    /// Full Rust source of the test file
+    pub content: String,
+    /// Modules of the plan this file attacks
+    #[serde(default)]
+    pub covers: Vec<String>,
+}
+
+/// Output from the red team skill
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, LLMOutput)]
+pub struct RedTeamOutput {
+    /// Test files to add before the feature is built
+    pub tests: Vec<RedTeamTest>,
+    /// What the suite attacks
+    pub summary: String,
+}
+
+impl ValidatedOutput for RedTeamOutput {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        if self.tests.is_empty() {
+            errors.push("tests: the suite needs at least one test file".to_string());
+        }
+        for (i, test) in self.tests.iter().enumerate() {
+            if !is_test_path(&test.path) {
+                errors.push(format!(
+                    "tests[{}].path: \"{}\" must be a .rs file directly under tests/ (e.g. tests/limiter_adversarial.rs)",
+                    i, test.path
+                ));
+            }
+            if test.content.trim().is_empty() {
+                errors.push(format!("tests[{}].content: must not be empty", i));
+            }
+        }
+        errors
+    }
+}
+
+impl RedTeamTest {
+    /// Cargo test target built from this file (its file stem)
+    pub fn target(&self) -> &str {
+        Path::new(&self.path)
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or_default()
+    }
+}
+
+impl RedTeamOutput {
+    /// Write the suite into `root` (a feature worktree)
+    pub fn write_to(&self, root: &Path) -> Result<()> {
+        for test in &self.tests {
+            let path = root.join(&test.path);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, &test.content)
+                .with_context(|| format!("Failed to write {}", test.path))?;
+        }
+        Ok(())
+    }
+
+    /// Test files in `root` that were changed or removed since `write_to`
+    pub fn modified_in(&self, root: &Path) -> Vec<String> {
+        self.tests
+            .iter()
+            .filter(|test| {
+                std::fs::read_to_string(root.join(&test.path))
+                    .map_or(true, |content| content != test.content)
+            })
+            .map(|test| test.path.clone())
+            .collect()
+    }
+}
+
+/// Stateless red team skill
+///
+/// Like DraftingSkill it has no A2A handler; the Coordinator calls `run()`
+/// for each feature whose mode asks for red-team tests.
+pub struct RedTeamSkill;
+
+impl RedTeamSkill {
+    /// Write a hostile test suite for a feature's mission and plan
+    pub async fn run(
+        mission: &MissionPrompt,
+        plan: Option<&AtomizerOutput>,
+        mode: &str,
+        config: &ModelConfig,
+    ) -> Result<RedTeamOutput> {
+        let prompt = format_red_team_prompt(mission, plan, mode);
+        run_llm_function!(config, RedTeamOutput, SYSTEM_PROMPT, &prompt)
+    }
+}
+
+/// Format the red team prompt from a mission and its plan
+fn format_red_team_prompt(
+    mission: &MissionPrompt,
+    plan: Option<&AtomizerOutput>,
+    mode: &str,
+) -> String {
+    let mut prompt = format!(
+        "## Feature\n\n**{}**: {}\n\nMode: {}\n\n",
+        mission.feature_name, mission.objective, mode
+    );
+
+    if !mission.tasks.is_empty() {
+        prompt.push_str("## Tasks\n\n");
+        for task in &mission.tasks {
+            prompt.push_str(&format!(
+                "- {} `{}`: {}\n",
+                task.action, task.file_path, task.implementation
+            ));
+        }
+        prompt.push('\n');
+    }
+
+    if let Some(plan) = plan {
+        prompt.push_str("## Public Interfaces\n\n");
+        for module in &plan.modules {
+            prompt.push_str(&format!("### `{}`\n\n", module.path));
+            for signature in &module.public_interface {
+                prompt.push_str(&format!("```rust\n{}\n```\n\n", signature));
+            }
+        }
+        if !plan.test_modules.is_empty() {
+            prompt.push_str("## Test Modules to Write\n\n");
+            for test in &plan.test_modules {
+                prompt.push_str(&format!(
+                    "- `{}` covering {} (~{} tests)\n",
+                    test.path,
+                    test.covers.join(", "),
+                    test.test_count_estimate
+                ));
+            }
+            prompt.push('\n');
+        }
+    }
+
+    if !mission.verification.is_empty() {
+        prompt.push_str("## Verification Checklist\n\n");
+        for item in &mission.verification {
+            prompt.push_str(&format!("- {}\n", item));
+        }
+        prompt.push('\n');
+    }
+
+    prompt.push_str(
+        "Write each test file directly under `tests/` (e.g. `tests/limiter_adversarial.rs`); \
+         cargo does not build files in subdirectories of `tests/`. The code does not exist \
+         yet; the tests must fail until it is implemented.\n",
+    );
+    prompt
+}
+
+/// Whether `path` is a Rust file directly under `tests/` (an integration test target)
+fn is_test_path(path: &str) -> bool {
+    let path = Path::new(path);
+    let components: Vec<Component> = path.components().collect();
+    path.extension().is_some_and(|ext| ext == "rs")
+        && path.starts_with("tests")
+        && components.len() == 2
+        && components.iter().all(|c| matches!(c, Component::Normal(_)))
+}
+
+const SYSTEM_PROMPT: &str = include_str!("defaults/red_team.md");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn suite(path: &str, content: &str) -> RedTeamOutput {
+        RedTeamOutput {
+            tests: vec![RedTeamTest {
+                path: path.to_string(),
+                content: content.to_string(),
+                covers: vec![],
+            }],
+            summary: "Attacks the limiter".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_validate_test_paths() {
+        assert!(suite("tests/limiter.rs", "#[test] fn t() {}")
+            .validate()
+            .is_empty());
+        assert_eq!(suite("src/limiter.rs", "x").validate().len(), 1);
+        // Cargo never builds files in subdirectories of tests/ as targets
+        assert_eq!(
+            suite("tests/adversarial/limiter.rs", "x").validate().len(),
+            1
+        );
+        assert_eq!(suite("tests/../src/lib.rs", "x").validate().len(), 1);
+        assert_eq!(suite("/tests/limiter.rs", "x").validate().len(), 1);
+        assert_eq!(suite("tests/limiter.rs", " ").validate().len(), 1);
+    }
+
+    #[test]
+    fn test_modified_in_detects_changes() {
+        let dir = std::env::temp_dir().join("catalyst_test_red_team_suite");
+        let _ = std::fs::remove_dir_all(&dir);
+        let suite = suite("tests/limiter_adversarial.rs", "#[test] fn t() {}");
+        assert_eq!(suite.tests[0].target(), "limiter_adversarial");
+
+        suite.write_to(&dir).unwrap();
+        assert!(suite.modified_in(&dir).is_empty());
+
+        std::fs::write(dir.join("tests/limiter_adversarial.rs"), "").unwrap();
+        assert_eq!(
+            suite.modified_in(&dir),
+            vec!["tests/limiter_adversarial.rs"]
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(suite.modified_in(&dir).len(), 1);
+    }
+}
@@ -4,7 +4,9 @@
 //! The bridge between Compile-Time (planning) and Runtime (coding).
 //! Generates precise, context-rich prompts for the Builder agent.
 
-use crate::models::ModelConfig;
+use super::atomizer_skill::AtomizerOutput;
+use super::drafting_skill::DraftingMission;
+use crate::models::{ModelConfig, ValidatedOutput};
 use crate::run_llm_function;
 use crate::skills::artifact_registry::{MissionArtifact, TaskSummary};
 use async_trait::async_trait;
@@ -23,6 +25,7 @@ pub struct MissionTask {
     /// Ordinal number
     pub number: u32,
     /// Action: "Create", "Modify", "Add"
+    #[schemars(extend("enum" = ["Create", "Modify", "Add"]))]
     pub action: String,
     /// File path
     pub file_path: String,
@@ -61,7 +64,7 @@ pub struct MissionPrompt {
     pub constraints: MissionConstraints,
     /// Drafting missions for parallel file generation (Speed Demon)
     #[serde(default)]
-    pub drafting_missions: Vec<super::drafting_skill::DraftingMission>,
+    pub drafting_missions: Vec<DraftingMission>,
     /// Existing signatures to use (not recreate)
     #[serde(default)]
     pub existing_signatures: Vec<String>,
@@ -70,6 +73,61 @@ pub struct MissionPrompt {
     pub verification: Vec<String>,
 }
 
+impl MissionPrompt {
+    /// Derive one drafting mission per task file when the Taskmaster gave none
+    ///
+    /// Signatures and dependencies come from the matching module of `plan`.
+    pub fn fill_drafting_missions(&mut self, plan: &AtomizerOutput) {
+        if !self.drafting_missions.is_empty() {
+            return;
+        }
+        for task in &self.tasks {
+            if let Some(existing) = self
+                .drafting_missions
+                .iter_mut()
+                .find(|m| m.file_path == task.file_path)
+            {
+                existing.logic_summary.push('\n');
+                existing.logic_summary.push_str(&task.implementation);
+                continue;
+            }
+            let module = plan.modules.iter().find(|m| m.path == task.file_path);
+            self.drafting_missions.push(DraftingMission {
+                file_path: task.file_path.clone(),
+                signatures_to_match: module
+                    .map(|m| m.public_interface.clone())
+                    .unwrap_or_default(),
+                logic_summary: task.implementation.clone(),
+                dependencies: module.map(|m| m.dependencies.clone()).unwrap_or_default(),
+                existing_code: None,
+            });
+        }
+    }
+}
+
+impl ValidatedOutput for MissionPrompt {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        if self.tasks.is_empty() && self.drafting_missions.is_empty() {
+            errors.push("tasks: a mission needs at least one task or drafting mission".to_string());
+        }
+        for (i, task) in self.tasks.iter().enumerate() {
+            if task.file_path.trim().is_empty() {
+                errors.push(format!("tasks[{}].file_path: must not be empty", i));
+            }
+        }
+        for (i, mission) in self.drafting_missions.iter().enumerate() {
+            if mission.file_path.trim().is_empty() {
+                errors.push(format!(
+                    "drafting_missions[{}].file_path: must not be empty",
+                    i
+                ));
+            }
+        }
+        errors
+    }
+}
+
 /// Taskmaster skill for generating mission prompts
 #[skill(
     id = "mission",
@@ -96,6 +154,20 @@ impl TaskmasterSkill {
     pub fn default() -> Self {
         Self::new(ModelConfig::default())
     }
+
+    /// SDK-style call for direct Coordinator integration.
+    pub async fn run(
+        goal: &str,
+        decisions_json: &str,
+        plan_json: &str,
+        config: &ModelConfig,
+    ) -> anyhow::Result<MissionPrompt> {
+        let prompt = format!(
+            "Goal:\n{}\n\nFrozen Context (approved decisions):\n{}\n\nAtomic Plan:\n{}",
+            goal, decisions_json, plan_json
+        );
+        run_llm_function!(config, MissionPrompt, SYSTEM_PROMPT, prompt)
+    }
 }
 
 #[async_trait]
@@ -156,3 +228,62 @@ impl SkillHandler for TaskmasterSkill {
 }
 
 const SYSTEM_PROMPT: &str = include_str!("defaults/taskmaster.md");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skills::atomizer_skill::AtomicModule;
+
+    fn task(number: u32, file_path: &str, implementation: &str) -> MissionTask {
+        MissionTask {
+            number,
+            action: "Create".to_string(),
+            file_path: file_path.to_string(),
+            implementation: implementation.to_string(),
+            hints: vec![],
+        }
+    }
+
+    #[test]
+    fn test_fill_drafting_missions_from_tasks() {
+        let plan = AtomizerOutput {
+            feature_id: "F-001".to_string(),
+            feature_name: "Auth".to_string(),
+            modules: vec![AtomicModule {
+                path: "src/auth/token.rs".to_string(),
+                responsibility: "Token parsing".to_string(),
+                max_lines: 80,
+                public_interface: vec!["pub fn parse(raw: &str) -> Result<Token>".to_string()],
+                dependencies: vec!["src/auth/mod.rs".to_string()],
+            }],
+            test_modules: vec![],
+            integration_points: vec![],
+        };
+        let mut mission = MissionPrompt {
+            feature_name: "Auth".to_string(),
+            objective: "Parse tokens".to_string(),
+            tasks: vec![
+                task(1, "src/auth/token.rs", "Define Token"),
+                task(2, "src/auth/token.rs", "Implement parse"),
+                task(3, "src/auth/mod.rs", "Re-export token"),
+            ],
+            constraints: MissionConstraints {
+                max_file_lines: 150,
+                max_function_lines: 30,
+                required_patterns: vec![],
+                forbidden_patterns: vec![],
+            },
+            drafting_missions: vec![],
+            existing_signatures: vec![],
+            verification: vec![],
+        };
+
+        mission.fill_drafting_missions(&plan);
+        assert_eq!(mission.drafting_missions.len(), 2);
+        let token = &mission.drafting_missions[0];
+        assert_eq!(token.logic_summary, "Define Token\nImplement parse");
+        assert_eq!(token.signatures_to_match.len(), 1);
+        assert_eq!(token.dependencies, vec!["src/auth/mod.rs".to_string()]);
+        assert!(mission.drafting_missions[1].signatures_to_match.is_empty());
+    }
+}
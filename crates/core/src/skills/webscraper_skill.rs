@@ -4,7 +4,7 @@
 //! Uses a cheaper/faster model for efficiency.
 //! Called by ResearcherSkill to extract content from web pages.
 
-use crate::models::ModelConfig;
+use crate::models::{ModelConfig, ValidatedOutput};
 use crate::run_llm_function;
 use async_trait::async_trait;
 use radkit::agent::{OnRequestResult, SkillHandler};
@@ -31,6 +31,8 @@ pub struct ScrapedContent {
     pub is_relevant: bool,
 }
 
+impl ValidatedOutput for ScrapedContent {}
+
 /// WebScraper skill for cleaning HTML
 #[skill(
     id = "scrape",
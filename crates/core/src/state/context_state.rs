@@ -143,22 +143,30 @@ impl ContextManager {
 
     /// Create a new idea from text
     pub fn create_idea(&self, content: &str) -> Result<Idea> {
-        let id = format!("idea-{}", Utc::now().format("%Y%m%d-%H%M%S-%3f"));
+        self.create_tagged_idea(content, &[])
+    }
+
+    /// Create a new idea with tags
+    pub fn create_tagged_idea(&self, content: &str, tags: &[&str]) -> Result<Idea> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
         let now = Utc::now();
+        let id = super::db::unique_id(
+            &conn,
+            "ideas",
+            format!("idea-{}", now.format("%Y%m%d-%H%M%S-%3f")),
+        )?;
 
         let idea = Idea {
-            id: id.clone(),
+            id,
             content: content.to_string(),
             source_file: None,
-            tags: Vec::new(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
             created_at: now,
         };
 
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
-
         conn.execute(
             r#"
             INSERT INTO ideas (id, content, source_file, tags_json, created_at)
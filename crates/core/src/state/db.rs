@@ -11,7 +11,7 @@ use std::sync::{Arc, Mutex};
 use crate::skills::prompts;
 
 /// Schema version for migrations
-const SCHEMA_VERSION: i32 = 1;
+const SCHEMA_VERSION: i32 = 9;
 
 /// Unified database manager for all Catalyst state
 pub struct CatalystDb {
@@ -77,6 +77,62 @@ impl CatalystDb {
                 [1],
             )?;
         }
+        if current_version < 2 {
+            self.migrate_v2(&conn)?;
+            conn.execute(
+                "INSERT OR REPLACE INTO schema_version (version) VALUES (?1)",
+                [2],
+            )?;
+        }
+        if current_version < 3 {
+            self.migrate_v3(&conn)?;
+            conn.execute(
+                "INSERT OR REPLACE INTO schema_version (version) VALUES (?1)",
+                [3],
+            )?;
+        }
+        if current_version < 4 {
+            self.migrate_v4(&conn)?;
+            conn.execute(
+                "INSERT OR REPLACE INTO schema_version (version) VALUES (?1)",
+                [4],
+            )?;
+        }
+        if current_version < 5 {
+            self.migrate_v5(&conn)?;
+            conn.execute(
+                "INSERT OR REPLACE INTO schema_version (version) VALUES (?1)",
+                [5],
+            )?;
+        }
+        if current_version < 6 {
+            self.migrate_v6(&conn)?;
+            conn.execute(
+                "INSERT OR REPLACE INTO schema_version (version) VALUES (?1)",
+                [6],
+            )?;
+        }
+        if current_version < 7 {
+            self.migrate_v7(&conn)?;
+            conn.execute(
+                "INSERT OR REPLACE INTO schema_version (version) VALUES (?1)",
+                [7],
+            )?;
+        }
+        if current_version < 8 {
+            self.migrate_v8(&conn)?;
+            conn.execute(
+                "INSERT OR REPLACE INTO schema_version (version) VALUES (?1)",
+                [8],
+            )?;
+        }
+        if current_version < 9 {
+            self.migrate_v9(&conn)?;
+            conn.execute(
+                "INSERT OR REPLACE INTO schema_version (version) VALUES (?1)",
+                [9],
+            )?;
+        }
 
         Ok(())
     }
@@ -266,6 +322,189 @@ impl CatalystDb {
         Ok(())
     }
 
+    /// Migration to version 2 - LLM token/cost ledger
+    fn migrate_v2(&self, conn: &Connection) -> Result<()> {
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS llm_usage (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                run_id TEXT NOT NULL,
+                feature_id TEXT,
+                agent_id TEXT NOT NULL,
+                provider TEXT NOT NULL,
+                model TEXT NOT NULL,
+                input_tokens INTEGER NOT NULL DEFAULT 0,
+                output_tokens INTEGER NOT NULL DEFAULT 0,
+                cost_usd REAL NOT NULL DEFAULT 0,
+                created_at TEXT NOT NULL DEFAULT (datetime('now'))
+            )
+            "#,
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_llm_usage_run ON llm_usage(run_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_llm_usage_feature ON llm_usage(feature_id)",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    /// Migration to version 3 - LLM response cache
+    fn migrate_v3(&self, conn: &Connection) -> Result<()> {
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS llm_cache (
+                key TEXT PRIMARY KEY,
+                prompt_slug TEXT NOT NULL,
+                prompt_version INTEGER NOT NULL,
+                provider TEXT NOT NULL,
+                model TEXT NOT NULL,
+                response TEXT NOT NULL,
+                hits INTEGER NOT NULL DEFAULT 0,
+                created_at TEXT NOT NULL DEFAULT (datetime('now')),
+                expires_at TEXT
+            )
+            "#,
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_llm_cache_prompt ON llm_cache(prompt_slug)",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    /// Migration to version 4 - LLM transcripts
+    fn migrate_v4(&self, conn: &Connection) -> Result<()> {
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS llm_transcripts (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                run_id TEXT NOT NULL,
+                agent_id TEXT NOT NULL,
+                feature_id TEXT,
+                event_id TEXT,
+                prompt_slug TEXT NOT NULL,
+                prompt_version INTEGER,
+                provider TEXT NOT NULL,
+                model TEXT NOT NULL,
+                system_prompt TEXT,
+                messages TEXT NOT NULL DEFAULT '[]',
+                tools TEXT NOT NULL DEFAULT '[]',
+                response TEXT,
+                error TEXT,
+                input_tokens INTEGER NOT NULL DEFAULT 0,
+                output_tokens INTEGER NOT NULL DEFAULT 0,
+                duration_ms INTEGER NOT NULL DEFAULT 0,
+                created_at TEXT NOT NULL DEFAULT (datetime('now'))
+            )
+            "#,
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_llm_transcripts_run ON llm_transcripts(run_id, agent_id)",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    /// Migration to version 5 - feature plans and missions
+    fn migrate_v5(&self, conn: &Connection) -> Result<()> {
+        conn.execute("ALTER TABLE features ADD COLUMN plan TEXT", [])?;
+        conn.execute("ALTER TABLE features ADD COLUMN mission TEXT", [])?;
+
+        Ok(())
+    }
+
+    /// Migration to version 6 - run checkpoints
+    fn migrate_v6(&self, conn: &Connection) -> Result<()> {
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS runs (
+                id TEXT PRIMARY KEY,
+                goal TEXT NOT NULL,
+                status TEXT NOT NULL DEFAULT 'running',
+                feature_id TEXT,
+                unknowns TEXT,
+                error TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )
+            "#,
+            [],
+        )?;
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS run_checkpoints (
+                run_id TEXT NOT NULL,
+                unknown_id TEXT NOT NULL,
+                stage TEXT NOT NULL,
+                data TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                PRIMARY KEY (run_id, unknown_id, stage)
+            )
+            "#,
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    /// Migration to version 7 - spec edit provenance
+    fn migrate_v7(&self, conn: &Connection) -> Result<()> {
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS spec_edits (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                document TEXT NOT NULL,
+                section TEXT NOT NULL,
+                content TEXT NOT NULL,
+                run_id TEXT NOT NULL,
+                unknown_id TEXT NOT NULL,
+                decision TEXT NOT NULL,
+                status TEXT NOT NULL,
+                conflicts_with INTEGER,
+                created_at TEXT NOT NULL
+            )
+            "#,
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_spec_edits_document ON spec_edits(document)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_spec_edits_run ON spec_edits(run_id)",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    /// Migration to version 8 - builder compile-fix results
+    fn migrate_v8(&self, conn: &Connection) -> Result<()> {
+        conn.execute(
+            "ALTER TABLE features ADD COLUMN build_iterations INTEGER",
+            [],
+        )?;
+        conn.execute("ALTER TABLE features ADD COLUMN build_errors TEXT", [])?;
+
+        Ok(())
+    }
+
+    /// Migration to version 9 - feature dependencies
+    fn migrate_v9(&self, conn: &Connection) -> Result<()> {
+        conn.execute("ALTER TABLE features ADD COLUMN depends_on TEXT", [])?;
+
+        Ok(())
+    }
+
     // =========================================================================
     // Prompt Template Methods
     // =========================================================================
@@ -334,6 +573,8 @@ impl CatalystDb {
     }
 
     /// Update a prompt (increments version automatically)
+    ///
+    /// Cached LLM responses for the prompt are dropped.
     pub fn set_prompt(&self, slug: &str, content: &str) -> Result<i32> {
         let conn = self
             .conn
@@ -362,6 +603,10 @@ impl CatalystDb {
             "#,
             params![slug, new_version, content],
         )?;
+        conn.execute(
+            "DELETE FROM llm_cache WHERE prompt_slug = ?1",
+            params![slug],
+        )?;
 
         tracing::debug!("Updated prompt '{}' to version {}", slug, new_version);
         Ok(new_version)
@@ -443,6 +688,24 @@ impl CatalystDb {
     }
 }
 
+/// `base`, or `base-2`, `base-3`, ... if `table` already has a row with that ID
+///
+/// IDs are derived from timestamps, so records created in quick succession
+/// would otherwise collide.
+pub(crate) fn unique_id(conn: &Connection, table: &str, base: String) -> Result<String> {
+    let mut id = base.clone();
+    let mut n = 1;
+    while conn.query_row(
+        &format!("SELECT EXISTS(SELECT 1 FROM {} WHERE id = ?1)", table),
+        params![id],
+        |row| row.get::<_, bool>(0),
+    )? {
+        n += 1;
+        id = format!("{}-{}", base, n);
+    }
+    Ok(id)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -475,6 +738,9 @@ mod tests {
         assert!(tables.contains(&"interactions".to_string()));
         assert!(tables.contains(&"prompt_templates".to_string()));
         assert!(tables.contains(&"project_documents".to_string()));
+        assert!(tables.contains(&"llm_usage".to_string()));
+        assert!(tables.contains(&"llm_cache".to_string()));
+        assert!(tables.contains(&"llm_transcripts".to_string()));
 
         drop(conn);
         let _ = fs::remove_file(path);
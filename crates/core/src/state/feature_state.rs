@@ -3,6 +3,8 @@
 //! Feature storage using SQLite. Each feature is a row in the `features` table.
 
 use super::db::CatalystDb;
+use crate::skills::atomizer_skill::AtomizerOutput;
+use crate::skills::taskmaster_skill::MissionPrompt;
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use rusqlite::params;
@@ -23,6 +25,8 @@ pub enum PipelineStage {
     Researching,
     /// Architect is designing
     Architecting,
+    /// Atomized plan and missions are ready
+    Planned,
     /// Builder is implementing
     Building,
     /// RedTeam is testing
@@ -33,6 +37,8 @@ pub enum PipelineStage {
     Complete,
     /// Failed with error
     Failed,
+    /// A prerequisite failed, is blocked, or is part of a dependency cycle
+    Blocked,
 }
 
 impl PipelineStage {
@@ -42,11 +48,13 @@ impl PipelineStage {
             Self::Parsing => "parsing",
             Self::Researching => "researching",
             Self::Architecting => "architecting",
+            Self::Planned => "planned",
             Self::Building => "building",
             Self::Testing => "testing",
             Self::Merging => "merging",
             Self::Complete => "complete",
             Self::Failed => "failed",
+            Self::Blocked => "blocked",
         }
     }
 
@@ -56,11 +64,13 @@ impl PipelineStage {
             "parsing" => Self::Parsing,
             "researching" => Self::Researching,
             "architecting" => Self::Architecting,
+            "planned" => Self::Planned,
             "building" => Self::Building,
             "testing" => Self::Testing,
             "merging" => Self::Merging,
             "complete" => Self::Complete,
             "failed" => Self::Failed,
+            "blocked" => Self::Blocked,
             _ => Self::Idea,
         }
     }
@@ -84,6 +94,21 @@ pub struct Feature {
     /// Error message if Failed
     #[serde(default)]
     pub error: Option<String>,
+    /// Atomizer module breakdown
+    #[serde(default)]
+    pub plan: Option<AtomizerOutput>,
+    /// Taskmaster mission with drafting missions
+    #[serde(default)]
+    pub mission: Option<MissionPrompt>,
+    /// `cargo check` runs of the Builder's last compile-fix loop
+    #[serde(default)]
+    pub build_iterations: Option<u32>,
+    /// Compiler errors left when that loop stopped
+    #[serde(default)]
+    pub build_errors: Vec<String>,
+    /// Features that must be merged before this one is built
+    #[serde(default)]
+    pub depends_on: Vec<String>,
     /// Creation timestamp
     pub created_at: DateTime<Utc>,
     /// Last update timestamp
@@ -105,7 +130,11 @@ impl FeatureManager {
 
     /// Create a new feature
     pub fn create(&self, title: &str) -> Result<Feature> {
-        let id = generate_feature_id();
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+        let id = super::db::unique_id(&conn, "features", generate_feature_id())?;
         let now = Utc::now();
 
         let feature = Feature {
@@ -115,15 +144,15 @@ impl FeatureManager {
             description: None,
             worktree_path: None,
             error: None,
+            plan: None,
+            mission: None,
+            build_iterations: None,
+            build_errors: Vec::new(),
+            depends_on: Vec::new(),
             created_at: now,
             updated_at: now,
         };
 
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
-
         conn.execute(
             r#"
             INSERT INTO features (id, title, stage, description, worktree_path, error, created_at, updated_at)
@@ -155,7 +184,7 @@ impl FeatureManager {
         let feature = conn
             .query_row(
                 r#"
-            SELECT id, title, stage, description, worktree_path, error, created_at, updated_at
+            SELECT id, title, stage, description, worktree_path, error, created_at, updated_at, plan, mission, build_iterations, build_errors, depends_on
             FROM features WHERE id = ?1
             "#,
                 params![id],
@@ -207,6 +236,26 @@ impl FeatureManager {
         Ok(())
     }
 
+    /// Forget a feature's worktree once it has been removed
+    pub fn clear_worktree(&self, id: &str) -> Result<()> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        let now = Utc::now().to_rfc3339();
+        let affected = conn.execute(
+            "UPDATE features SET worktree_path = NULL, updated_at = ?1 WHERE id = ?2",
+            params![now, id],
+        )?;
+
+        if affected == 0 {
+            anyhow::bail!("Feature not found: {}", id);
+        }
+
+        Ok(())
+    }
+
     /// Mark a feature as failed
     pub fn set_failed(&self, id: &str, error: &str) -> Result<()> {
         let conn = self
@@ -227,6 +276,86 @@ impl FeatureManager {
         Ok(())
     }
 
+    /// Mark a feature as blocked by its prerequisites
+    pub fn set_blocked(&self, id: &str, reason: &str) -> Result<()> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        let now = Utc::now().to_rfc3339();
+        let affected = conn.execute(
+            "UPDATE features SET stage = 'blocked', error = ?1, updated_at = ?2 WHERE id = ?3",
+            params![reason, now, id],
+        )?;
+
+        if affected == 0 {
+            anyhow::bail!("Feature not found: {}", id);
+        }
+
+        Ok(())
+    }
+
+    /// Set the features that must be merged before this one is built
+    pub fn set_depends_on(&self, id: &str, depends_on: &[String]) -> Result<()> {
+        if depends_on.iter().any(|dep| dep == id) {
+            anyhow::bail!("Feature {} cannot depend on itself", id);
+        }
+        self.set_json_column(id, "depends_on", &serde_json::to_string(depends_on)?)
+    }
+
+    /// Store the Atomizer plan for a feature
+    pub fn set_plan(&self, id: &str, plan: &AtomizerOutput) -> Result<()> {
+        self.set_json_column(id, "plan", &serde_json::to_string(plan)?)
+    }
+
+    /// Store the Taskmaster mission for a feature
+    pub fn set_mission(&self, id: &str, mission: &MissionPrompt) -> Result<()> {
+        self.set_json_column(id, "mission", &serde_json::to_string(mission)?)
+    }
+
+    /// Record where the Builder's compile-fix loop stopped
+    pub fn set_build_result(&self, id: &str, iterations: u32, errors: &[String]) -> Result<()> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        let now = Utc::now().to_rfc3339();
+        let affected = conn.execute(
+            "UPDATE features SET build_iterations = ?1, build_errors = ?2, updated_at = ?3 WHERE id = ?4",
+            params![iterations, serde_json::to_string(errors)?, now, id],
+        )?;
+
+        if affected == 0 {
+            anyhow::bail!("Feature not found: {}", id);
+        }
+
+        Ok(())
+    }
+
+    fn set_json_column(&self, id: &str, column: &str, json: &str) -> Result<()> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        let now = Utc::now().to_rfc3339();
+        let affected = conn.execute(
+            &format!(
+                "UPDATE features SET {} = ?1, updated_at = ?2 WHERE id = ?3",
+                column
+            ),
+            params![json, now, id],
+        )?;
+
+        if affected == 0 {
+            anyhow::bail!("Feature not found: {}", id);
+        }
+
+        Ok(())
+    }
+
     /// Save/update a feature
     pub fn save(&self, feature: &Feature) -> Result<()> {
         let conn = self
@@ -237,8 +366,8 @@ impl FeatureManager {
         conn.execute(
             r#"
             INSERT OR REPLACE INTO features 
-            (id, title, stage, description, worktree_path, error, created_at, updated_at)
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            (id, title, stage, description, worktree_path, error, created_at, updated_at, plan, mission, build_iterations, build_errors, depends_on)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
             "#,
             params![
                 feature.id,
@@ -252,6 +381,11 @@ impl FeatureManager {
                 feature.error,
                 feature.created_at.to_rfc3339(),
                 feature.updated_at.to_rfc3339(),
+                feature.plan.as_ref().map(serde_json::to_string).transpose()?,
+                feature.mission.as_ref().map(serde_json::to_string).transpose()?,
+                feature.build_iterations,
+                serde_json::to_string(&feature.build_errors)?,
+                serde_json::to_string(&feature.depends_on)?,
             ],
         )
         .context("Failed to save feature")?;
@@ -268,7 +402,7 @@ impl FeatureManager {
 
         let mut stmt = conn.prepare(
             r#"
-            SELECT id, title, stage, description, worktree_path, error, created_at, updated_at
+            SELECT id, title, stage, description, worktree_path, error, created_at, updated_at, plan, mission, build_iterations, build_errors, depends_on
             FROM features
             ORDER BY created_at DESC
             "#,
@@ -291,7 +425,7 @@ impl FeatureManager {
 
         let mut stmt = conn.prepare(
             r#"
-            SELECT id, title, stage, description, worktree_path, error, created_at, updated_at
+            SELECT id, title, stage, description, worktree_path, error, created_at, updated_at, plan, mission, build_iterations, build_errors, depends_on
             FROM features
             WHERE stage = ?1
             ORDER BY created_at DESC
@@ -328,6 +462,11 @@ impl FeatureManager {
         let error: Option<String> = row.get(5)?;
         let created_at_str: String = row.get(6)?;
         let updated_at_str: String = row.get(7)?;
+        let plan: Option<String> = row.get(8)?;
+        let mission: Option<String> = row.get(9)?;
+        let build_iterations: Option<u32> = row.get(10)?;
+        let build_errors: Option<String> = row.get(11)?;
+        let depends_on: Option<String> = row.get(12)?;
 
         Ok(Feature {
             id,
@@ -336,6 +475,15 @@ impl FeatureManager {
             description,
             worktree_path: worktree_path.map(PathBuf::from),
             error,
+            plan: plan.and_then(|json| serde_json::from_str(&json).ok()),
+            mission: mission.and_then(|json| serde_json::from_str(&json).ok()),
+            build_iterations,
+            build_errors: build_errors
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default(),
+            depends_on: depends_on
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default(),
             created_at: DateTime::parse_from_rfc3339(&created_at_str)
                 .map(|t| t.with_timezone(&Utc))
                 .unwrap_or_else(|_| Utc::now()),
@@ -368,4 +516,86 @@ mod tests {
         let id = generate_feature_id();
         assert!(id.starts_with("f-"));
     }
+
+    #[test]
+    fn test_plan_and_mission_persist() {
+        use crate::skills::atomizer_skill::AtomicModule;
+        use crate::skills::taskmaster_skill::MissionConstraints;
+
+        let db_path = ".catalyst/test_feature_plan.db";
+        let _ = std::fs::remove_file(db_path);
+        let db = CatalystDb::open_at(db_path).unwrap();
+        let fm = FeatureManager::new(&db);
+
+        let feature = fm.create("Add rate limiting").unwrap();
+        assert!(feature.plan.is_none() && feature.mission.is_none());
+
+        let plan = AtomizerOutput {
+            feature_id: feature.id.clone(),
+            feature_name: "Rate limiting".to_string(),
+            modules: vec![AtomicModule {
+                path: "src/limiter.rs".to_string(),
+                responsibility: "Token bucket".to_string(),
+                max_lines: 100,
+                public_interface: vec!["pub fn acquire()".to_string()],
+                dependencies: vec![],
+            }],
+            test_modules: vec![],
+            integration_points: vec![],
+        };
+        let mission = MissionPrompt {
+            feature_name: "Rate limiting".to_string(),
+            objective: "Limit requests".to_string(),
+            tasks: vec![],
+            constraints: MissionConstraints {
+                max_file_lines: 150,
+                max_function_lines: 30,
+                required_patterns: vec![],
+                forbidden_patterns: vec![],
+            },
+            drafting_missions: vec![],
+            existing_signatures: vec![],
+            verification: vec![],
+        };
+        fm.set_plan(&feature.id, &plan).unwrap();
+        fm.set_mission(&feature.id, &mission).unwrap();
+        fm.update_stage(&feature.id, PipelineStage::Planned)
+            .unwrap();
+
+        let loaded = fm.load(&feature.id).unwrap();
+        assert_eq!(loaded.stage, PipelineStage::Planned);
+        assert_eq!(loaded.plan.unwrap().modules[0].path, "src/limiter.rs");
+        assert_eq!(loaded.mission.unwrap().objective, "Limit requests");
+        assert!(loaded.build_iterations.is_none() && loaded.build_errors.is_empty());
+        assert!(fm.set_plan("f-missing", &plan).is_err());
+
+        let errors = vec!["src/limiter.rs:3:5: error[E0308]: mismatched types".to_string()];
+        fm.set_build_result(&feature.id, 3, &errors).unwrap();
+        let mut loaded = fm.load(&feature.id).unwrap();
+        assert_eq!(loaded.build_iterations, Some(3));
+        assert_eq!(loaded.build_errors, errors);
+
+        // save() round-trips the build result
+        loaded.build_errors.clear();
+        fm.save(&loaded).unwrap();
+        let loaded = fm.load(&feature.id).unwrap();
+        assert_eq!(loaded.build_iterations, Some(3));
+        assert!(loaded.build_errors.is_empty());
+
+        let prerequisite = fm.create("Add a token bucket").unwrap();
+        fm.set_depends_on(&feature.id, std::slice::from_ref(&prerequisite.id))
+            .unwrap();
+        assert!(fm
+            .set_depends_on(&feature.id, std::slice::from_ref(&feature.id))
+            .is_err());
+        fm.set_blocked(&feature.id, "Prerequisite failed").unwrap();
+        let loaded = fm.load(&feature.id).unwrap();
+        assert_eq!(loaded.depends_on, vec![prerequisite.id]);
+        assert_eq!(loaded.stage, PipelineStage::Blocked);
+        assert_eq!(loaded.error.as_deref(), Some("Prerequisite failed"));
+
+        drop(fm);
+        drop(db);
+        let _ = std::fs::remove_file(db_path);
+    }
 }
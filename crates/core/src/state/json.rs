@@ -1,4 +1,5 @@
 use super::db::CatalystDb;
+use super::project_state::ProjectMode;
 use anyhow::{Context, Result};
 use rusqlite::params;
 use serde::{Deserialize, Serialize};
@@ -11,6 +12,9 @@ pub struct ProjectState {
     pub phase: String,
     /// Active agent working on the project
     pub active_agent: Option<String>,
+    /// Mode of the latest run
+    #[serde(default)]
+    pub mode: Option<ProjectMode>,
     /// Last successful build timestamp
     pub last_build: Option<String>,
     /// List of pending tasks/tickets
@@ -0,0 +1,304 @@
+//! # LLM Response Cache
+//!
+//! Content-addressed cache of LLM responses using SQLite. Entries are keyed on
+//! a SHA-256 of provider, model, prompt slug, the prompt's current version in
+//! `prompt_templates` and the exact input, so a template edit never serves a
+//! stale answer. `CatalystDb::set_prompt` also deletes the slug's entries.
+
+use super::db::CatalystDb;
+use crate::models::{CacheRequest, ResponseCache};
+use anyhow::{Context, Result};
+use radkit::models::LlmResponse;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::sync::{Arc, Mutex};
+
+/// Cache size and usage
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LlmCacheStats {
+    pub entries: u64,
+    pub expired: u64,
+    pub hits: u64,
+}
+
+/// LLM response cache in the project database
+#[derive(Clone)]
+pub struct LlmCache {
+    conn: Arc<Mutex<Connection>>,
+    /// Entry lifetime in seconds (`None` = until invalidated)
+    ttl_secs: Option<u64>,
+    /// Skip lookups (responses are still stored)
+    bypass: bool,
+}
+
+impl std::fmt::Debug for LlmCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LlmCache")
+            .field("ttl_secs", &self.ttl_secs)
+            .field("bypass", &self.bypass)
+            .finish_non_exhaustive()
+    }
+}
+
+impl LlmCache {
+    /// Create a new LlmCache from a CatalystDb
+    pub fn new(db: &CatalystDb) -> Self {
+        Self {
+            conn: db.connection(),
+            ttl_secs: None,
+            bypass: false,
+        }
+    }
+
+    /// Expire entries after `ttl_secs`
+    pub fn with_ttl(mut self, ttl_secs: Option<u64>) -> Self {
+        self.ttl_secs = ttl_secs;
+        self
+    }
+
+    /// Ignore cached entries for this run, but refresh them with new responses
+    pub fn with_bypass(mut self, bypass: bool) -> Self {
+        self.bypass = bypass;
+        self
+    }
+
+    /// Look up a fresh response
+    pub fn lookup(&self, request: &CacheRequest) -> Result<Option<LlmResponse>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        let (key, _) = cache_key(&conn, request)?;
+        let response: Option<String> = conn
+            .query_row(
+                r#"
+                SELECT response FROM llm_cache
+                WHERE key = ?1 AND (expires_at IS NULL OR expires_at > datetime('now'))
+                "#,
+                params![key],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        let Some(response) = response else {
+            return Ok(None);
+        };
+        conn.execute(
+            "UPDATE llm_cache SET hits = hits + 1 WHERE key = ?1",
+            params![key],
+        )?;
+
+        Ok(Some(
+            serde_json::from_str(&response).context("Corrupt cached LLM response")?,
+        ))
+    }
+
+    /// Store a response (replaces any existing entry)
+    pub fn store(&self, request: &CacheRequest, response: &LlmResponse) -> Result<()> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        let (key, version) = cache_key(&conn, request)?;
+        let expires = self.ttl_secs.map(|secs| format!("+{} seconds", secs));
+        conn.execute(
+            r#"
+            INSERT OR REPLACE INTO llm_cache
+                (key, prompt_slug, prompt_version, provider, model, response, expires_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6,
+                CASE WHEN ?7 IS NULL THEN NULL ELSE datetime('now', ?7) END)
+            "#,
+            params![
+                key,
+                request.prompt_slug,
+                version,
+                request.provider,
+                request.model,
+                serde_json::to_string(response)?,
+                expires,
+            ],
+        )
+        .context("Failed to cache LLM response")?;
+
+        Ok(())
+    }
+
+    /// Drop all entries for a prompt slug
+    pub fn invalidate_prompt(&self, slug: &str) -> Result<usize> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        Ok(conn.execute(
+            "DELETE FROM llm_cache WHERE prompt_slug = ?1",
+            params![slug],
+        )?)
+    }
+
+    /// Drop expired entries
+    pub fn purge_expired(&self) -> Result<usize> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        Ok(conn.execute(
+            "DELETE FROM llm_cache WHERE expires_at IS NOT NULL AND expires_at <= datetime('now')",
+            [],
+        )?)
+    }
+
+    /// Drop everything
+    pub fn clear(&self) -> Result<usize> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        Ok(conn.execute("DELETE FROM llm_cache", [])?)
+    }
+
+    pub fn stats(&self) -> Result<LlmCacheStats> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        conn.query_row(
+            r#"
+            SELECT COUNT(*),
+                COALESCE(SUM(expires_at IS NOT NULL AND expires_at <= datetime('now')), 0),
+                COALESCE(SUM(hits), 0)
+            FROM llm_cache
+            "#,
+            [],
+            |row| {
+                Ok(LlmCacheStats {
+                    entries: row.get::<_, i64>(0)? as u64,
+                    expired: row.get::<_, i64>(1)? as u64,
+                    hits: row.get::<_, i64>(2)? as u64,
+                })
+            },
+        )
+        .context("Failed to read LLM cache stats")
+    }
+}
+
+impl ResponseCache for LlmCache {
+    fn get(&self, request: &CacheRequest) -> Option<LlmResponse> {
+        if self.bypass {
+            return None;
+        }
+        self.lookup(request).unwrap_or_else(|e| {
+            tracing::warn!("LLM cache lookup failed: {}", e);
+            None
+        })
+    }
+
+    fn put(&self, request: &CacheRequest, response: &LlmResponse) {
+        if let Err(e) = self.store(request, response) {
+            tracing::warn!("{}", e);
+        }
+    }
+}
+
+/// Content address of a request at the prompt's current version
+fn cache_key(conn: &Connection, request: &CacheRequest) -> Result<(String, i32)> {
+    let version: i32 = conn
+        .query_row(
+            "SELECT version FROM prompt_templates WHERE slug = ?1",
+            params![request.prompt_slug],
+            |row| row.get(0),
+        )
+        .optional()?
+        .unwrap_or(0);
+
+    let mut hasher = Sha256::new();
+    for part in [
+        request.provider.as_str(),
+        request.model.as_str(),
+        request.prompt_slug.as_str(),
+        &version.to_string(),
+        request.input.as_str(),
+    ] {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
+    }
+
+    let key = hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    Ok((key, version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use radkit::models::{Content, TokenUsage};
+    use std::fs;
+
+    fn request(input: &str) -> CacheRequest {
+        CacheRequest {
+            provider: "anthropic".to_string(),
+            model: "claude-sonnet-4-20250514".to_string(),
+            prompt_slug: "architect".to_string(),
+            input: input.to_string(),
+        }
+    }
+
+    fn response(text: &str) -> LlmResponse {
+        LlmResponse::new(Content::from_text(text), TokenUsage::empty())
+    }
+
+    #[test]
+    fn test_llm_cache_roundtrip_and_prompt_invalidation() {
+        let path = ".catalyst/test_llm_cache.db";
+        let _ = fs::remove_file(path);
+
+        let db = CatalystDb::open_at(path).unwrap();
+        db.seed_prompts().unwrap();
+        let cache = LlmCache::new(&db);
+
+        assert!(cache.get(&request("goal")).is_none());
+        cache.put(&request("goal"), &response("decision"));
+
+        let hit = cache.get(&request("goal")).unwrap();
+        assert_eq!(hit.content().first_text(), Some("decision"));
+        assert!(cache.get(&request("other goal")).is_none());
+        assert_eq!(cache.stats().unwrap().hits, 1);
+
+        // Bypass skips lookups but still stores
+        let bypass = cache.clone().with_bypass(true);
+        assert!(bypass.get(&request("goal")).is_none());
+
+        db.set_prompt("architect", "New architect prompt").unwrap();
+        assert!(cache.get(&request("goal")).is_none());
+        assert_eq!(cache.stats().unwrap().entries, 0);
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_llm_cache_ttl() {
+        let path = ".catalyst/test_llm_cache_ttl.db";
+        let _ = fs::remove_file(path);
+
+        let db = CatalystDb::open_at(path).unwrap();
+        let expired = LlmCache::new(&db).with_ttl(Some(0));
+        expired.put(&request("goal"), &response("stale"));
+        assert!(expired.get(&request("goal")).is_none());
+        assert_eq!(expired.purge_expired().unwrap(), 1);
+
+        let fresh = LlmCache::new(&db).with_ttl(Some(3600));
+        fresh.put(&request("goal"), &response("fresh"));
+        assert!(fresh.get(&request("goal")).is_some());
+
+        let _ = fs::remove_file(path);
+    }
+}
@@ -5,8 +5,14 @@ pub mod feature_state;
 pub mod interaction;
 pub mod io;
 pub mod json;
+pub mod llm_cache;
+pub mod project_state;
+pub mod run_state;
 pub mod snapshots;
+pub mod spec_edits;
 pub mod specs;
+pub mod transcripts;
+pub mod usage;
 
 pub use db::CatalystDb;
 
@@ -17,5 +23,11 @@ pub use interaction::{
     Interaction, InteractionKind, InteractionManager, InteractionResponse, InteractionStatus,
 };
 pub use json::ProjectState;
+pub use llm_cache::{LlmCache, LlmCacheStats};
+pub use project_state::ProjectMode;
+pub use run_state::{Checkpoint, CheckpointStage, RunManager, RunRecord, RunStatus};
 pub use snapshots::{RollbackResult, Snapshot, SnapshotManager};
+pub use spec_edits::{NewSpecEdit, SpecEdit, SpecEditManager, SpecEditStatus};
 pub use specs::SpecManager;
+pub use transcripts::{redact_secrets, TranscriptEntry, TranscriptManager, TranscriptSummary};
+pub use usage::{UsageEntry, UsageGroup, UsageLedger, UsageSummary, UsageTotals};
@@ -8,17 +8,50 @@ use serde::{Deserialize, Serialize};
 use std::path::Path;
 
 /// Project mode determines agent composition and strictness
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum ProjectMode {
     /// Fast prototyping, minimal checks
     SpeedRun,
     /// Production-grade, standard checks
+    #[default]
     Lab,
     /// Enterprise-grade, full audit
     Fortress,
 }
 
+impl ProjectMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::SpeedRun => "speed_run",
+            Self::Lab => "lab",
+            Self::Fortress => "fortress",
+        }
+    }
+}
+
+impl std::fmt::Display for ProjectMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for ProjectMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().replace(['-', ' '], "_").as_str() {
+            "speed_run" | "speedrun" => Ok(Self::SpeedRun),
+            "lab" => Ok(Self::Lab),
+            "fortress" => Ok(Self::Fortress),
+            other => anyhow::bail!(
+                "Unknown mode '{}' (expected speed_run, lab or fortress)",
+                other
+            ),
+        }
+    }
+}
+
 /// Status of an unknown
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -133,4 +166,15 @@ mod tests {
         let json = serde_json::to_string(&mode).unwrap();
         assert_eq!(json, "\"fortress\"");
     }
+
+    #[test]
+    fn test_project_mode_from_str() {
+        assert_eq!(
+            "speed-run".parse::<ProjectMode>().unwrap(),
+            ProjectMode::SpeedRun
+        );
+        assert_eq!("Lab".parse::<ProjectMode>().unwrap(), ProjectMode::Lab);
+        assert_eq!(ProjectMode::Fortress.to_string(), "fortress");
+        assert!("yolo".parse::<ProjectMode>().is_err());
+    }
 }
@@ -0,0 +1,379 @@
+//! # Run Checkpoints
+//!
+//! Swarm runs using SQLite. Each run is a row in `runs`; every per-unknown
+//! stage it finishes (research, decision, verdict) is a row in
+//! `run_checkpoints`, so an interrupted run can skip finished work when it is
+//! resumed.
+
+use super::db::CatalystDb;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use rusqlite::{params, OptionalExtension};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// Lifecycle of a run
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatus {
+    /// In progress (or interrupted, if no process is running it)
+    Running,
+    Completed,
+    Failed,
+    /// Stopped by the user (not listed as interrupted, but can still be
+    /// resumed by ID)
+    Cancelled,
+}
+
+impl RunStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Running => "running",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+            Self::Cancelled => "cancelled",
+        }
+    }
+
+    fn from_str(s: &str) -> Self {
+        match s {
+            "completed" => Self::Completed,
+            "failed" => Self::Failed,
+            "cancelled" => Self::Cancelled,
+            _ => Self::Running,
+        }
+    }
+}
+
+/// Per-unknown stage a checkpoint records
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckpointStage {
+    Research,
+    Decision,
+    Verdict,
+}
+
+impl CheckpointStage {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Research => "research",
+            Self::Decision => "decision",
+            Self::Verdict => "verdict",
+        }
+    }
+
+    fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "research" => Some(Self::Research),
+            "decision" => Some(Self::Decision),
+            "verdict" => Some(Self::Verdict),
+            _ => None,
+        }
+    }
+}
+
+/// A stored run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub id: String,
+    pub goal: String,
+    pub status: RunStatus,
+    /// Feature the run's plan is stored on
+    pub feature_id: Option<String>,
+    /// Unknowns Parser output
+    pub unknowns: Option<serde_json::Value>,
+    pub error: Option<String>,
+    /// Unknowns with a recorded verdict
+    pub completed_unknowns: usize,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Output of a finished stage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub unknown_id: String,
+    pub stage: CheckpointStage,
+    pub data: serde_json::Value,
+}
+
+impl Checkpoint {
+    /// The stage output as its original type
+    pub fn parse<T: DeserializeOwned>(&self) -> Result<T> {
+        serde_json::from_value(self.data.clone()).with_context(|| {
+            format!(
+                "Invalid {} checkpoint for {}",
+                self.stage.as_str(),
+                self.unknown_id
+            )
+        })
+    }
+}
+
+const RUN_COLUMNS: &str = r#"
+    id, goal, status, feature_id, unknowns, error, created_at, updated_at,
+    (SELECT COUNT(*) FROM run_checkpoints c WHERE c.run_id = runs.id AND c.stage = 'verdict')
+"#;
+
+/// Manager for runs and their checkpoints in SQLite
+pub struct RunManager {
+    conn: Arc<Mutex<rusqlite::Connection>>,
+}
+
+impl RunManager {
+    /// Create a new RunManager from a CatalystDb
+    pub fn new(db: &CatalystDb) -> Self {
+        Self {
+            conn: db.connection(),
+        }
+    }
+
+    /// Record the start of a run
+    pub fn start(&self, run_id: &str, goal: &str) -> Result<()> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        let now = Utc::now().to_rfc3339();
+        conn.execute(
+            r#"
+            INSERT INTO runs (id, goal, status, created_at, updated_at)
+            VALUES (?1, ?2, 'running', ?3, ?3)
+            "#,
+            params![run_id, goal, now],
+        )
+        .context("Failed to record run")?;
+
+        Ok(())
+    }
+
+    /// Load a run by ID
+    pub fn load(&self, run_id: &str) -> Result<RunRecord> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        conn.query_row(
+            &format!("SELECT {} FROM runs WHERE id = ?1", RUN_COLUMNS),
+            params![run_id],
+            Self::row_to_run,
+        )
+        .optional()?
+        .ok_or_else(|| anyhow::anyhow!("Run not found: {}", run_id))
+    }
+
+    /// Runs still marked as running, newest first
+    ///
+    /// At startup these are runs whose process died before they finished;
+    /// later, they include runs in progress in this process as well.
+    pub fn list_interrupted(&self) -> Result<Vec<RunRecord>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM runs WHERE status = 'running' ORDER BY created_at DESC",
+            RUN_COLUMNS
+        ))?;
+        let runs = stmt
+            .query_map([], Self::row_to_run)?
+            .collect::<Result<Vec<_>, _>>()
+            .context("Failed to list runs")?;
+
+        Ok(runs)
+    }
+
+    /// Store the Unknowns Parser output of a run
+    pub fn set_unknowns<T: Serialize>(&self, run_id: &str, unknowns: &T) -> Result<()> {
+        let json = serde_json::to_string(unknowns)?;
+        self.update(run_id, "unknowns = ?1", &json)
+    }
+
+    /// Link a run to the feature holding its plan
+    pub fn set_feature(&self, run_id: &str, feature_id: &str) -> Result<()> {
+        self.update(run_id, "feature_id = ?1", feature_id)
+    }
+
+    /// Set a run's status (clearing the error unless one is given)
+    pub fn set_status(&self, run_id: &str, status: RunStatus, error: Option<&str>) -> Result<()> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        let now = Utc::now().to_rfc3339();
+        let affected = conn.execute(
+            "UPDATE runs SET status = ?1, error = ?2, updated_at = ?3 WHERE id = ?4",
+            params![status.as_str(), error, now, run_id],
+        )?;
+
+        if affected == 0 {
+            anyhow::bail!("Run not found: {}", run_id);
+        }
+
+        Ok(())
+    }
+
+    fn update(&self, run_id: &str, assignment: &str, value: &str) -> Result<()> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        let now = Utc::now().to_rfc3339();
+        let affected = conn.execute(
+            &format!(
+                "UPDATE runs SET {}, updated_at = ?2 WHERE id = ?3",
+                assignment
+            ),
+            params![value, now, run_id],
+        )?;
+
+        if affected == 0 {
+            anyhow::bail!("Run not found: {}", run_id);
+        }
+
+        Ok(())
+    }
+
+    /// Record a finished stage for an unknown (replacing an earlier one)
+    pub fn save_checkpoint<T: Serialize>(
+        &self,
+        run_id: &str,
+        unknown_id: &str,
+        stage: CheckpointStage,
+        output: &T,
+    ) -> Result<()> {
+        let json = serde_json::to_string(output)?;
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        conn.execute(
+            r#"
+            INSERT OR REPLACE INTO run_checkpoints (run_id, unknown_id, stage, data, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            "#,
+            params![
+                run_id,
+                unknown_id,
+                stage.as_str(),
+                json,
+                Utc::now().to_rfc3339()
+            ],
+        )
+        .context("Failed to save checkpoint")?;
+
+        Ok(())
+    }
+
+    /// All checkpoints of a run, oldest first
+    pub fn checkpoints(&self, run_id: &str) -> Result<Vec<Checkpoint>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT unknown_id, stage, data FROM run_checkpoints
+            WHERE run_id = ?1
+            ORDER BY created_at
+            "#,
+        )?;
+        let rows = stmt
+            .query_map(params![run_id], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(unknown_id, stage, data)| {
+                Some(Checkpoint {
+                    unknown_id,
+                    stage: CheckpointStage::from_str(&stage)?,
+                    data: serde_json::from_str(&data).ok()?,
+                })
+            })
+            .collect())
+    }
+
+    fn row_to_run(row: &rusqlite::Row) -> rusqlite::Result<RunRecord> {
+        let status: String = row.get(2)?;
+        let unknowns: Option<String> = row.get(4)?;
+        let completed: i64 = row.get(8)?;
+
+        Ok(RunRecord {
+            id: row.get(0)?,
+            goal: row.get(1)?,
+            status: RunStatus::from_str(&status),
+            feature_id: row.get(3)?,
+            unknowns: unknowns.and_then(|json| serde_json::from_str(&json).ok()),
+            error: row.get(5)?,
+            completed_unknowns: completed as usize,
+            created_at: row.get(6)?,
+            updated_at: row.get(7)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkpoints_survive_reopen() {
+        let path = ".catalyst/test_run_state.db";
+        let _ = std::fs::remove_file(path);
+
+        {
+            let db = CatalystDb::open_at(path).unwrap();
+            let runs = RunManager::new(&db);
+            runs.start("run-1", "Add caching").unwrap();
+            runs.set_unknowns("run-1", &serde_json::json!({"ambiguities": []}))
+                .unwrap();
+            runs.save_checkpoint("run-1", "U-1", CheckpointStage::Research, &"notes")
+                .unwrap();
+            runs.save_checkpoint("run-1", "U-1", CheckpointStage::Verdict, &"ok")
+                .unwrap();
+            runs.save_checkpoint("run-1", "U-2", CheckpointStage::Research, &"first")
+                .unwrap();
+            runs.save_checkpoint("run-1", "U-2", CheckpointStage::Research, &"second")
+                .unwrap();
+        }
+
+        // Reopen as a restarted process would
+        let db = CatalystDb::open_at(path).unwrap();
+        let runs = RunManager::new(&db);
+        let interrupted = runs.list_interrupted().unwrap();
+        assert_eq!(interrupted.len(), 1);
+        assert_eq!(interrupted[0].completed_unknowns, 1);
+        assert!(interrupted[0].unknowns.is_some());
+
+        let checkpoints = runs.checkpoints("run-1").unwrap();
+        assert_eq!(checkpoints.len(), 3);
+        let u2 = checkpoints.iter().find(|c| c.unknown_id == "U-2").unwrap();
+        assert_eq!(u2.parse::<String>().unwrap(), "second");
+
+        runs.set_status("run-1", RunStatus::Completed, None)
+            .unwrap();
+        assert!(runs.list_interrupted().unwrap().is_empty());
+        assert_eq!(runs.load("run-1").unwrap().status, RunStatus::Completed);
+        assert!(runs.load("run-missing").is_err());
+
+        drop(runs);
+        drop(db);
+        let _ = std::fs::remove_file(path);
+    }
+}
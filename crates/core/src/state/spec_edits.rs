@@ -0,0 +1,223 @@
+//! # Spec Edits
+//!
+//! Provenance of the living spec. Every section an approved Architect
+//! decision writes into a project document is a row in `spec_edits`, naming
+//! the run, unknown and decision it came from.
+//!
+//! An edit that would overwrite a section another unknown of the same run
+//! already wrote (with different content) is not applied; it is stored as a
+//! conflict pointing at the edit it clashed with.
+
+use super::db::CatalystDb;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// Whether an edit made it into its document
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SpecEditStatus {
+    Applied,
+    /// Clashed with an earlier edit of the same section in the run
+    Conflict,
+}
+
+impl SpecEditStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Applied => "applied",
+            Self::Conflict => "conflict",
+        }
+    }
+
+    fn from_str(s: &str) -> Self {
+        match s {
+            "conflict" => Self::Conflict,
+            _ => Self::Applied,
+        }
+    }
+}
+
+/// One section edit and where it came from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecEdit {
+    pub id: i64,
+    /// Slug of the project document
+    pub document: String,
+    /// Heading of the section within the document
+    pub section: String,
+    pub content: String,
+    pub run_id: String,
+    pub unknown_id: String,
+    /// Option the Architect chose
+    pub decision: String,
+    pub status: SpecEditStatus,
+    /// Applied edit this one conflicts with
+    pub conflicts_with: Option<i64>,
+    pub created_at: String,
+}
+
+/// An edit about to be recorded
+#[derive(Debug, Clone)]
+pub struct NewSpecEdit<'a> {
+    pub document: &'a str,
+    pub section: &'a str,
+    pub content: &'a str,
+    pub run_id: &'a str,
+    pub unknown_id: &'a str,
+    pub decision: &'a str,
+}
+
+const EDIT_COLUMNS: &str = "id, document, section, content, run_id, unknown_id, decision, status, conflicts_with, created_at";
+
+/// Manager for spec edit history in SQLite
+pub struct SpecEditManager {
+    conn: Arc<Mutex<rusqlite::Connection>>,
+}
+
+impl SpecEditManager {
+    /// Create a new SpecEditManager from a CatalystDb
+    pub fn new(db: &CatalystDb) -> Self {
+        Self {
+            conn: db.connection(),
+        }
+    }
+
+    /// Record an edit
+    pub fn record(
+        &self,
+        edit: &NewSpecEdit,
+        status: SpecEditStatus,
+        conflicts_with: Option<i64>,
+    ) -> Result<SpecEdit> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        let now = Utc::now().to_rfc3339();
+        conn.execute(
+            r#"
+            INSERT INTO spec_edits
+                (document, section, content, run_id, unknown_id, decision, status, conflicts_with, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+            "#,
+            params![
+                edit.document,
+                edit.section,
+                edit.content,
+                edit.run_id,
+                edit.unknown_id,
+                edit.decision,
+                status.as_str(),
+                conflicts_with,
+                now
+            ],
+        )
+        .context("Failed to record spec edit")?;
+
+        Ok(SpecEdit {
+            id: conn.last_insert_rowid(),
+            document: edit.document.to_string(),
+            section: edit.section.to_string(),
+            content: edit.content.to_string(),
+            run_id: edit.run_id.to_string(),
+            unknown_id: edit.unknown_id.to_string(),
+            decision: edit.decision.to_string(),
+            status,
+            conflicts_with,
+            created_at: now,
+        })
+    }
+
+    /// Latest edit a run recorded for a section (any unknown, any status)
+    pub fn find(
+        &self,
+        run_id: &str,
+        unknown_id: &str,
+        document: &str,
+        section: &str,
+    ) -> Result<Option<SpecEdit>> {
+        self.query_one(
+            "run_id = ?1 AND unknown_id = ?2 AND document = ?3 AND section = ?4 COLLATE NOCASE",
+            params![run_id, unknown_id, document, section],
+        )
+    }
+
+    /// Edit a run applied to a section, if any
+    pub fn applied_in_run(
+        &self,
+        run_id: &str,
+        document: &str,
+        section: &str,
+    ) -> Result<Option<SpecEdit>> {
+        self.query_one(
+            "run_id = ?1 AND document = ?2 AND section = ?3 COLLATE NOCASE AND status = 'applied'",
+            params![run_id, document, section],
+        )
+    }
+
+    /// Edits of a document, oldest first
+    pub fn for_document(&self, document: &str) -> Result<Vec<SpecEdit>> {
+        self.query_all("document = ?1", params![document])
+    }
+
+    /// Edits made by a run, oldest first
+    pub fn for_run(&self, run_id: &str) -> Result<Vec<SpecEdit>> {
+        self.query_all("run_id = ?1", params![run_id])
+    }
+
+    fn query_one(&self, filter: &str, args: impl rusqlite::Params) -> Result<Option<SpecEdit>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        Ok(conn
+            .query_row(
+                &format!(
+                    "SELECT {} FROM spec_edits WHERE {} ORDER BY id DESC LIMIT 1",
+                    EDIT_COLUMNS, filter
+                ),
+                args,
+                Self::row_to_edit,
+            )
+            .optional()?)
+    }
+
+    fn query_all(&self, filter: &str, args: impl rusqlite::Params) -> Result<Vec<SpecEdit>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM spec_edits WHERE {} ORDER BY id",
+            EDIT_COLUMNS, filter
+        ))?;
+        let edits = stmt
+            .query_map(args, Self::row_to_edit)?
+            .collect::<Result<Vec<_>, _>>()
+            .context("Failed to list spec edits")?;
+
+        Ok(edits)
+    }
+
+    fn row_to_edit(row: &rusqlite::Row) -> rusqlite::Result<SpecEdit> {
+        let status: String = row.get(7)?;
+        Ok(SpecEdit {
+            id: row.get(0)?,
+            document: row.get(1)?,
+            section: row.get(2)?,
+            content: row.get(3)?,
+            run_id: row.get(4)?,
+            unknown_id: row.get(5)?,
+            decision: row.get(6)?,
+            status: SpecEditStatus::from_str(&status),
+            conflicts_with: row.get(8)?,
+            created_at: row.get(9)?,
+        })
+    }
+}
@@ -2,10 +2,19 @@
 //!
 //! Manages project specifications (architecture, features, etc.) in the database.
 //! Replaces the filesystem-based hybrid spec system with SQL queries.
+//!
+//! Approved Architect decisions are merged into the documents section by
+//! section with [`SpecManager::apply_decision`]; the history of those edits is
+//! kept by the [`SpecEditManager`].
 
 use super::db::CatalystDb;
+use super::spec_edits::{NewSpecEdit, SpecEdit, SpecEditManager, SpecEditStatus};
+use crate::skills::architect_skill::ArchitectOutput;
 use anyhow::Result;
 
+/// Document for spec updates that name no known document or section
+const DEFAULT_UPDATE_DOCUMENT: &str = "architecture";
+
 /// Manager for project specifications
 ///
 /// Reads/writes spec fragments from the `project_documents` table.
@@ -51,6 +60,101 @@ impl<'a> SpecManager<'a> {
         Ok(())
     }
 
+    /// Merge the spec updates of an approved decision into the project documents
+    ///
+    /// An update's `section` is `<document>#<Heading>` (e.g.
+    /// `architecture#Caching`); a bare heading goes to the first document that
+    /// already has it, or to `architecture`. The update replaces the body of
+    /// that section, or adds the section if it is missing. Applying the same
+    /// decision again changes nothing, and an update to a section another
+    /// unknown of the run already wrote differently is recorded as a conflict
+    /// instead of being applied.
+    pub fn apply_decision(
+        &self,
+        run_id: &str,
+        decision: &ArchitectOutput,
+    ) -> Result<Vec<SpecEdit>> {
+        let edits = SpecEditManager::new(self.db);
+        let documents = self.list_fragments()?;
+        let mut recorded = Vec::new();
+
+        for update in &decision.spec_updates {
+            let content = update.content.trim();
+            if content.is_empty() {
+                continue;
+            }
+            let (document, section) = self.locate(&update.section, &documents);
+            if let Some(existing) = edits.find(run_id, &decision.unknown_id, &document, &section)? {
+                // Applied before the run was interrupted
+                recorded.push(existing);
+                continue;
+            }
+
+            let edit = NewSpecEdit {
+                document: &document,
+                section: &section,
+                content,
+                run_id,
+                unknown_id: &decision.unknown_id,
+                decision: &decision.chosen_option,
+            };
+            if let Some(applied) = edits.applied_in_run(run_id, &document, &section)? {
+                if applied.content.trim() != content {
+                    recorded.push(edits.record(
+                        &edit,
+                        SpecEditStatus::Conflict,
+                        Some(applied.id),
+                    )?);
+                    continue;
+                }
+            }
+
+            let (title, current) = self.db.get_document(&document).unwrap_or_else(|_| {
+                (
+                    slug_to_title(&document),
+                    format!("# {}\n", slug_to_title(&document)),
+                )
+            });
+            let body = format!(
+                "<!-- {}: {} ({}) -->\n{}",
+                decision.unknown_id, decision.chosen_option, run_id, content
+            );
+            self.db.set_document(
+                &document,
+                &title,
+                &replace_section(&current, &section, &body),
+            )?;
+            recorded.push(edits.record(&edit, SpecEditStatus::Applied, None)?);
+        }
+
+        Ok(recorded)
+    }
+
+    /// Document and heading a spec update's `section` refers to
+    fn locate(&self, section: &str, documents: &[String]) -> (String, String) {
+        if let Some((prefix, heading)) = section.split_once(['#', '>', '/']) {
+            let slug = normalize_slug(prefix);
+            let heading = clean_heading(heading);
+            if documents.contains(&slug) && !heading.is_empty() {
+                return (slug, heading.to_string());
+            }
+        }
+
+        let heading = clean_heading(section);
+        let existing = documents
+            .iter()
+            .filter(|slug| slug.as_str() != "unknowns")
+            .find(|slug| {
+                self.read_fragment(slug).is_ok_and(|content| {
+                    find_section(&content.lines().collect::<Vec<_>>(), heading).is_some()
+                })
+            });
+        match existing {
+            Some(slug) => (slug.clone(), heading.to_string()),
+            None => (DEFAULT_UPDATE_DOCUMENT.to_string(), heading.to_string()),
+        }
+    }
+
     /// Seed default project documents if empty
     pub fn seed_defaults(&self) -> Result<usize> {
         let existing = self.db.list_documents()?;
@@ -86,6 +190,70 @@ fn normalize_slug(name: &str) -> String {
         .replace(' ', "_")
 }
 
+/// Heading text without leading `#`s and surrounding whitespace
+fn clean_heading(heading: &str) -> &str {
+    heading.trim().trim_start_matches('#').trim()
+}
+
+/// Level and text of a Markdown heading line
+fn parse_heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    let rest = &line[level..];
+    ((1..=6).contains(&level) && rest.starts_with(' ')).then(|| (level, rest.trim()))
+}
+
+/// Lines `start..end` of the section titled `heading` (start is the heading line)
+///
+/// The section runs until the next heading of the same or a higher level;
+/// headings inside code fences are ignored.
+fn find_section(lines: &[&str], heading: &str) -> Option<(usize, usize)> {
+    let mut in_fence = false;
+    let mut found: Option<(usize, usize)> = None;
+
+    for (i, line) in lines.iter().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        let Some((level, text)) = parse_heading(line) else {
+            continue;
+        };
+        match found {
+            Some((start, section_level)) if level <= section_level => return Some((start, i)),
+            None if text.eq_ignore_ascii_case(heading) => found = Some((i, level)),
+            _ => {}
+        }
+    }
+
+    found.map(|(start, _)| (start, lines.len()))
+}
+
+/// `doc` with the body of section `heading` replaced by `body` (appended if missing)
+fn replace_section(doc: &str, heading: &str, body: &str) -> String {
+    let lines: Vec<&str> = doc.lines().collect();
+    let Some((start, end)) = find_section(&lines, heading) else {
+        return format!(
+            "{}\n\n## {}\n\n{}\n",
+            doc.trim_end(),
+            heading,
+            body.trim_end()
+        );
+    };
+
+    let mut merged: Vec<&str> = lines[..=start].to_vec();
+    merged.push("");
+    merged.extend(body.trim_end().lines());
+    if end < lines.len() {
+        merged.push("");
+        merged.extend(&lines[end..]);
+    }
+    merged.join("\n") + "\n"
+}
+
 /// Convert a slug to a display title
 fn slug_to_title(slug: &str) -> String {
     slug.split('_')
@@ -198,4 +366,94 @@ mod tests {
         assert_eq!(slug_to_title("architecture"), "Architecture");
         assert_eq!(slug_to_title("my_feature"), "My Feature");
     }
+
+    #[test]
+    fn test_replace_section_keeps_neighbours() {
+        let doc = "# Spec\n\n## Storage\nSQLite\n\n```sh\n## not a heading\n```\n### Backups\nNightly\n\n## Auth\nNone\n";
+
+        let merged = replace_section(doc, "storage", "Postgres");
+        assert_eq!(
+            merged,
+            "# Spec\n\n## Storage\n\nPostgres\n\n## Auth\nNone\n"
+        );
+
+        let appended = replace_section(doc, "Caching", "moka");
+        assert!(appended.starts_with(doc.trim_end()));
+        assert!(appended.ends_with("\n\n## Caching\n\nmoka\n"));
+    }
+
+    #[test]
+    fn test_apply_decision_records_provenance_and_conflicts() {
+        use crate::skills::architect_skill::SpecUpdate;
+
+        let path = ".catalyst/test_spec_updates.db";
+        let _ = std::fs::remove_file(path);
+        let db = CatalystDb::open_at(path).unwrap();
+        let specs = SpecManager::new(&db);
+        specs.seed_defaults().unwrap();
+
+        let decision = |unknown_id: &str, option: &str, updates: &[(&str, &str)]| ArchitectOutput {
+            unknown_id: unknown_id.to_string(),
+            chosen_option: option.to_string(),
+            rationale: String::new(),
+            spec_updates: updates
+                .iter()
+                .map(|(section, content)| SpecUpdate {
+                    section: section.to_string(),
+                    content: content.to_string(),
+                })
+                .collect(),
+            dependencies: vec![],
+        };
+
+        let cache = decision(
+            "U-1",
+            "moka",
+            &[
+                ("architecture#Caching", "In-process moka cache"),
+                ("Tech Stack", "| Cache | moka |"),
+            ],
+        );
+        let edits = specs.apply_decision("run-1", &cache).unwrap();
+        assert_eq!(edits.len(), 2);
+        assert_eq!(edits[1].document, "spec"); // Heading found in an existing document
+        assert!(specs
+            .read_fragment("spec")
+            .unwrap()
+            .contains("| Cache | moka |"));
+        let architecture = specs.read_fragment("architecture").unwrap();
+        assert!(architecture
+            .contains("## Caching\n\n<!-- U-1: moka (run-1) -->\nIn-process moka cache"));
+
+        // Re-applying (a resumed run) changes nothing
+        assert_eq!(
+            specs.apply_decision("run-1", &cache).unwrap()[0].id,
+            edits[0].id
+        );
+        assert_eq!(specs.read_fragment("architecture").unwrap(), architecture);
+
+        // Another unknown rewriting the same section conflicts
+        let redis = decision("U-2", "redis", &[("Architecture > caching", "Redis")]);
+        let conflict = &specs.apply_decision("run-1", &redis).unwrap()[0];
+        assert_eq!(conflict.status, SpecEditStatus::Conflict);
+        assert_eq!(conflict.conflicts_with, Some(edits[0].id));
+        assert_eq!(specs.read_fragment("architecture").unwrap(), architecture);
+
+        // A later run may change it
+        specs.apply_decision("run-2", &redis).unwrap();
+        assert!(specs
+            .read_fragment("architecture")
+            .unwrap()
+            .contains("Redis"));
+        assert_eq!(
+            SpecEditManager::new(&db)
+                .for_document("architecture")
+                .unwrap()
+                .len(),
+            3
+        );
+
+        drop(db);
+        let _ = std::fs::remove_file(path);
+    }
 }
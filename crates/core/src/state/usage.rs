@@ -0,0 +1,265 @@
+//! # Usage Ledger
+//!
+//! Token and cost ledger using SQLite. Each LLM call is a row in the `llm_usage`
+//! table, attributed to a run, agent and (optionally) feature.
+
+use super::db::CatalystDb;
+use crate::models::LlmUsage;
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// A recorded LLM call
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageEntry {
+    pub id: i64,
+    pub run_id: String,
+    pub feature_id: Option<String>,
+    pub agent_id: String,
+    pub provider: String,
+    pub model: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_usd: f64,
+    pub created_at: String,
+}
+
+/// Aggregated usage
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct UsageTotals {
+    pub calls: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_usd: f64,
+}
+
+impl UsageTotals {
+    pub fn total_tokens(&self) -> u64 {
+        self.input_tokens + self.output_tokens
+    }
+
+    /// Add a single call
+    pub fn add(&mut self, usage: &LlmUsage, cost_usd: f64) {
+        self.calls += 1;
+        self.input_tokens += usage.input_tokens;
+        self.output_tokens += usage.output_tokens;
+        self.cost_usd += cost_usd;
+    }
+}
+
+/// Usage totals grouped by a key (agent, feature or run)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageGroup {
+    pub key: String,
+    #[serde(flatten)]
+    pub totals: UsageTotals,
+}
+
+/// Usage summary for a run, or across all runs
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UsageSummary {
+    pub total: UsageTotals,
+    pub by_agent: Vec<UsageGroup>,
+    pub by_feature: Vec<UsageGroup>,
+    pub by_run: Vec<UsageGroup>,
+}
+
+/// Manager for the LLM usage ledger in SQLite
+#[derive(Clone)]
+pub struct UsageLedger {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl std::fmt::Debug for UsageLedger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UsageLedger").finish_non_exhaustive()
+    }
+}
+
+impl UsageLedger {
+    /// Create a new UsageLedger from a CatalystDb
+    pub fn new(db: &CatalystDb) -> Self {
+        Self {
+            conn: db.connection(),
+        }
+    }
+
+    /// Record one LLM call
+    pub fn record(&self, run_id: &str, usage: &LlmUsage, cost_usd: f64) -> Result<()> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        conn.execute(
+            r#"
+            INSERT INTO llm_usage (run_id, feature_id, agent_id, provider, model, input_tokens, output_tokens, cost_usd)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            "#,
+            params![
+                run_id,
+                usage.feature_id,
+                usage.agent_id,
+                usage.provider,
+                usage.model,
+                usage.input_tokens as i64,
+                usage.output_tokens as i64,
+                cost_usd,
+            ],
+        )
+        .context("Failed to record LLM usage")?;
+
+        Ok(())
+    }
+
+    /// List recorded calls, newest first
+    pub fn list(&self, run_id: Option<&str>, limit: usize) -> Result<Vec<UsageEntry>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT id, run_id, feature_id, agent_id, provider, model, input_tokens, output_tokens, cost_usd, created_at
+            FROM llm_usage
+            WHERE ?1 IS NULL OR run_id = ?1
+            ORDER BY id DESC
+            LIMIT ?2
+            "#,
+        )?;
+
+        let rows = stmt.query_map(params![run_id, limit as i64], |row| {
+            Ok(UsageEntry {
+                id: row.get(0)?,
+                run_id: row.get(1)?,
+                feature_id: row.get(2)?,
+                agent_id: row.get(3)?,
+                provider: row.get(4)?,
+                model: row.get(5)?,
+                input_tokens: row.get::<_, i64>(6)? as u64,
+                output_tokens: row.get::<_, i64>(7)? as u64,
+                cost_usd: row.get(8)?,
+                created_at: row.get(9)?,
+            })
+        })?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row?);
+        }
+        Ok(entries)
+    }
+
+    /// Totals for a run, or across all runs
+    pub fn totals(&self, run_id: Option<&str>) -> Result<UsageTotals> {
+        Ok(self
+            .grouped("'total'", run_id)?
+            .pop()
+            .map(|g| g.totals)
+            .unwrap_or_default())
+    }
+
+    /// Summary grouped by agent, feature and run
+    pub fn summary(&self, run_id: Option<&str>) -> Result<UsageSummary> {
+        Ok(UsageSummary {
+            total: self.totals(run_id)?,
+            by_agent: self.grouped("agent_id", run_id)?,
+            by_feature: self.grouped("COALESCE(feature_id, '')", run_id)?,
+            by_run: self.grouped("run_id", run_id)?,
+        })
+    }
+
+    /// Aggregate by a fixed SQL key expression
+    fn grouped(&self, key_expr: &str, run_id: Option<&str>) -> Result<Vec<UsageGroup>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        let sql = format!(
+            r#"
+            SELECT {key} AS key, COUNT(*), SUM(input_tokens), SUM(output_tokens), SUM(cost_usd)
+            FROM llm_usage
+            WHERE ?1 IS NULL OR run_id = ?1
+            GROUP BY key
+            HAVING COUNT(*) > 0
+            ORDER BY SUM(cost_usd) DESC, key
+            "#,
+            key = key_expr
+        );
+        let mut stmt = conn.prepare(&sql)?;
+
+        let rows = stmt.query_map(params![run_id], |row| {
+            Ok(UsageGroup {
+                key: row.get(0)?,
+                totals: UsageTotals {
+                    calls: row.get::<_, i64>(1)? as u64,
+                    input_tokens: row.get::<_, i64>(2)? as u64,
+                    output_tokens: row.get::<_, i64>(3)? as u64,
+                    cost_usd: row.get(4)?,
+                },
+            })
+        })?;
+
+        let mut groups = Vec::new();
+        for row in rows {
+            groups.push(row?);
+        }
+        Ok(groups)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn usage(agent: &str, feature: Option<&str>, input: u64, output: u64) -> LlmUsage {
+        LlmUsage {
+            agent_id: agent.to_string(),
+            feature_id: feature.map(str::to_string),
+            provider: "anthropic".to_string(),
+            model: "claude-sonnet-4-20250514".to_string(),
+            input_tokens: input,
+            output_tokens: output,
+        }
+    }
+
+    #[test]
+    fn test_usage_ledger_totals_and_groups() {
+        let path = ".catalyst/test_usage.db";
+        let _ = fs::remove_file(path);
+
+        let db = CatalystDb::open_at(path).unwrap();
+        let ledger = UsageLedger::new(&db);
+
+        ledger
+            .record("run-a", &usage("architect", None, 100, 50), 0.5)
+            .unwrap();
+        ledger
+            .record("run-a", &usage("builder", Some("f-1"), 200, 100), 1.0)
+            .unwrap();
+        ledger
+            .record("run-b", &usage("architect", None, 10, 5), 0.1)
+            .unwrap();
+
+        let run_a = ledger.totals(Some("run-a")).unwrap();
+        assert_eq!(run_a.calls, 2);
+        assert_eq!(run_a.total_tokens(), 450);
+        assert!((run_a.cost_usd - 1.5).abs() < 1e-9);
+
+        let all = ledger.summary(None).unwrap();
+        assert_eq!(all.total.calls, 3);
+        assert_eq!(all.by_run.len(), 2);
+        assert_eq!(all.by_agent[0].key, "builder");
+        assert!(all.by_feature.iter().any(|g| g.key == "f-1"));
+
+        let entries = ledger.list(Some("run-b"), 10).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].agent_id, "architect");
+
+        let _ = fs::remove_file(path);
+    }
+}
@@ -18,12 +18,19 @@
 //!     │
 //!     └── Continue to Architect/Critic
 //! ```
+//!
+//! Missions are researched concurrently, up to the limit the agent was spawned
+//! with; progress updates carry the `unknown_id` they belong to. A mission
+//! whose token is cancelled stops and reports a [`Cancelled`] error.
 
 use crate::models::ModelConfig;
 use crate::skills::researcher_skill::{ResearchOutput, ResearcherSkill};
+use crate::swarm::control::{cancellable, Cancelled};
 use serde::{Deserialize, Serialize};
-use tokio::sync::{mpsc, oneshot};
+use std::sync::Arc;
+use tokio::sync::{mpsc, oneshot, Semaphore};
 use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
 
 /// A research mission to be executed asynchronously
 #[derive(Debug)]
@@ -34,6 +41,8 @@ pub struct ResearchMission {
     pub question: String,
     /// Additional context
     pub context: String,
+    /// Stops the mission when cancelled
+    pub cancel: CancellationToken,
     /// Channel to send the result back
     pub response_tx: oneshot::Sender<Result<ResearchOutput, anyhow::Error>>,
 }
@@ -63,68 +72,101 @@ pub struct ResearchAgentHandle {
 /// Spawn a research agent that processes missions asynchronously
 ///
 /// Returns channels for submitting missions and receiving progress updates.
+/// At most `max_concurrent` missions are researched at once; the rest wait
+/// in the mission channel.
 pub fn spawn_research_agent(
     config: ModelConfig,
     progress_tx: mpsc::Sender<ResearchProgress>,
+    max_concurrent: usize,
 ) -> ResearchAgentHandle {
     let (mission_tx, mut mission_rx) = mpsc::channel::<ResearchMission>(32);
+    let semaphore = Arc::new(Semaphore::new(max_concurrent.max(1)));
+    let config = Arc::new(config);
 
     let task_handle = tokio::spawn(async move {
         while let Some(mission) = mission_rx.recv().await {
-            let unknown_id = mission.unknown_id.clone();
+            let Ok(permit) = Arc::clone(&semaphore).acquire_owned().await else {
+                break;
+            };
+            let config = Arc::clone(&config);
+            let progress_tx = progress_tx.clone();
+            tokio::spawn(async move {
+                let _permit = permit; // Hold permit until the mission is done
+                run_mission(mission, &config, &progress_tx).await;
+            });
+        }
+    });
+
+    ResearchAgentHandle {
+        mission_tx,
+        task_handle,
+    }
+}
+
+/// Research one mission, reporting progress and sending back the result
+async fn run_mission(
+    mission: ResearchMission,
+    config: &ModelConfig,
+    progress_tx: &mpsc::Sender<ResearchProgress>,
+) {
+    let unknown_id = mission.unknown_id.clone();
+    if mission.cancel.is_cancelled() {
+        let _ = mission
+            .response_tx
+            .send(Err(Cancelled(format!("Research for {}", unknown_id)).into()));
+        return;
+    }
+
+    // Emit started event
+    let _ = progress_tx
+        .send(ResearchProgress::Started {
+            unknown_id: unknown_id.clone(),
+        })
+        .await;
 
-            // Emit started event
+    // Emit status: searching
+    let _ = progress_tx
+        .send(ResearchProgress::Status {
+            unknown_id: unknown_id.clone(),
+            message: "Searching crates.io and web...".to_string(),
+        })
+        .await;
+
+    // Run the actual research
+    let config = config.clone().with_cancel(mission.cancel.clone());
+    let result = cancellable(
+        &mission.cancel,
+        &format!("Research for {}", unknown_id),
+        ResearcherSkill::run(
+            &mission.unknown_id,
+            &mission.question,
+            &mission.context,
+            &config,
+        ),
+    )
+    .await;
+
+    // Emit completion or failure event
+    match &result {
+        Ok(_) => {
             let _ = progress_tx
-                .send(ResearchProgress::Started {
+                .send(ResearchProgress::Completed {
                     unknown_id: unknown_id.clone(),
                 })
                 .await;
-
-            // Emit status: searching
+        }
+        Err(e) => {
             let _ = progress_tx
-                .send(ResearchProgress::Status {
+                .send(ResearchProgress::Failed {
                     unknown_id: unknown_id.clone(),
-                    message: "Searching crates.io and web...".to_string(),
+                    error: e.to_string(),
                 })
                 .await;
-
-            // Run the actual research
-            let result = ResearcherSkill::run(
-                &mission.unknown_id,
-                &mission.question,
-                &mission.context,
-                &config,
-            )
-            .await;
-
-            // Emit completion or failure event
-            match &result {
-                Ok(_) => {
-                    let _ = progress_tx
-                        .send(ResearchProgress::Completed {
-                            unknown_id: unknown_id.clone(),
-                        })
-                        .await;
-                }
-                Err(e) => {
-                    let _ = progress_tx
-                        .send(ResearchProgress::Failed {
-                            unknown_id: unknown_id.clone(),
-                            error: e.to_string(),
-                        })
-                        .await;
-                }
-            }
-
-            // Send result back to coordinator
-            let _ = mission.response_tx.send(result);
         }
-    });
-
-    ResearchAgentHandle {
-        mission_tx,
-        task_handle,
     }
+
+    // Send result back to coordinator
+    let _ = mission.response_tx.send(result);
 }
 
 #[cfg(test)]
@@ -0,0 +1,156 @@
+//! # Run Budget
+//!
+//! Token/cost metering for a coordinator run.
+//!
+//! The coordinator hands a [`UsageMeter`] to every `ModelConfig` it builds. The
+//! meter prices each call, writes it to the `llm_usage` ledger and keeps running
+//! totals so the coordinator can check the budget between agent steps.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+use crate::models::{LlmUsage, PriceTable, UsageSink};
+use crate::state::{UsageLedger, UsageTotals};
+
+/// What to do when a run exceeds its budget
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetAction {
+    /// Ask the user through the inbox whether to continue
+    #[default]
+    Pause,
+    /// Stop the run
+    Abort,
+}
+
+/// Token and cost limits for a run
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Budget {
+    pub max_cost_usd: Option<f64>,
+    pub max_tokens: Option<u64>,
+}
+
+impl Budget {
+    /// Describe the first exceeded limit, if any
+    pub fn exceeded(&self, totals: &UsageTotals) -> Option<String> {
+        if let Some(max) = self.max_cost_usd {
+            if totals.cost_usd > max {
+                return Some(format!(
+                    "cost ${:.4} exceeds budget ${:.4}",
+                    totals.cost_usd, max
+                ));
+            }
+        }
+        if let Some(max) = self.max_tokens {
+            if totals.total_tokens() > max {
+                return Some(format!(
+                    "{} tokens exceeds budget of {}",
+                    totals.total_tokens(),
+                    max
+                ));
+            }
+        }
+        None
+    }
+}
+
+/// Usage sink for a single run
+#[derive(Debug)]
+pub struct UsageMeter {
+    run_id: String,
+    ledger: UsageLedger,
+    prices: PriceTable,
+    totals: Mutex<UsageTotals>,
+}
+
+impl UsageMeter {
+    pub fn new(run_id: impl Into<String>, ledger: UsageLedger, prices: PriceTable) -> Self {
+        Self {
+            run_id: run_id.into(),
+            ledger,
+            prices,
+            totals: Mutex::new(UsageTotals::default()),
+        }
+    }
+
+    /// Start from usage already spent (when resuming a run)
+    pub fn with_totals(self, totals: UsageTotals) -> Self {
+        *self.totals.lock().unwrap_or_else(|e| e.into_inner()) = totals;
+        self
+    }
+
+    pub fn run_id(&self) -> &str {
+        &self.run_id
+    }
+
+    /// Running totals for this run
+    pub fn totals(&self) -> UsageTotals {
+        self.totals
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+}
+
+impl UsageSink for UsageMeter {
+    fn record(&self, usage: LlmUsage) {
+        let cost = self.prices.cost(&usage);
+        self.totals
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .add(&usage, cost);
+
+        if let Err(e) = self.ledger.record(&self.run_id, &usage, cost) {
+            tracing::warn!("Failed to record LLM usage: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ModelPrice;
+    use crate::state::CatalystDb;
+
+    #[test]
+    fn test_meter_tracks_totals_and_budget() {
+        let path = ".catalyst/test_budget.db";
+        let _ = std::fs::remove_file(path);
+        let db = CatalystDb::open_at(path).unwrap();
+
+        let mut prices = PriceTable::empty();
+        prices.set("test-model", ModelPrice::new(10.0, 10.0));
+        let meter = UsageMeter::new("run-1", UsageLedger::new(&db), prices);
+
+        meter.record(LlmUsage {
+            agent_id: "architect".to_string(),
+            feature_id: None,
+            provider: "anthropic".to_string(),
+            model: "test-model".to_string(),
+            input_tokens: 60_000,
+            output_tokens: 40_000,
+        });
+
+        let totals = meter.totals();
+        assert_eq!(totals.total_tokens(), 100_000);
+        assert!((totals.cost_usd - 1.0).abs() < 1e-9);
+        assert_eq!(
+            UsageLedger::new(&db).totals(Some("run-1")).unwrap().calls,
+            1
+        );
+
+        let budget = Budget {
+            max_cost_usd: Some(2.0),
+            max_tokens: None,
+        };
+        assert!(budget.exceeded(&totals).is_none());
+
+        let budget = Budget {
+            max_cost_usd: None,
+            max_tokens: Some(50_000),
+        };
+        assert!(budget.exceeded(&totals).unwrap().contains("tokens"));
+
+        let _ = std::fs::remove_file(path);
+    }
+}
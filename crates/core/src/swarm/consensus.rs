@@ -0,0 +1,221 @@
+//! # Review Consensus
+//!
+//! Several critics reviewing the same decision, each as its own agent (so
+//! with its own model through the per-agent overrides) and optionally with a
+//! persona that narrows what it looks for.
+//!
+//! ```json
+//! "review_panel": {
+//!   "quorum": "majority",
+//!   "critics": [
+//!     { "id": "critic" },
+//!     { "id": "security_critic", "persona": "security" },
+//!     { "id": "performance_critic", "persona": "performance" }
+//!   ]
+//! }
+//! ```
+//!
+//! The reviews are folded into one verdict by the panel's [`Quorum`]. The
+//! concerns of every critic who did not approve are kept on it, so an
+//! Architect asked to decide again sees every objection.
+
+use crate::skills::critic_skill::{Concern, CriticOutput};
+use serde::{Deserialize, Serialize};
+
+/// How the reviews of a panel decide the verdict
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Quorum {
+    /// Every critic must approve
+    Unanimous,
+    /// More than half of the critics must approve
+    #[default]
+    Majority,
+    /// Approvals must outweigh objections, each review weighted by its confidence
+    ConfidenceWeighted,
+}
+
+/// One critic of a panel
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PanelCritic {
+    /// Agent ID, used for the critic's model overrides and events
+    pub id: String,
+    /// Angle the critic reviews from (e.g. "security", "maintainability")
+    #[serde(default)]
+    pub persona: Option<String>,
+}
+
+/// Critics that review every decision together
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReviewPanel {
+    pub critics: Vec<PanelCritic>,
+    #[serde(default)]
+    pub quorum: Quorum,
+}
+
+impl Quorum {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Unanimous => "unanimous",
+            Self::Majority => "majority",
+            Self::ConfidenceWeighted => "confidence_weighted",
+        }
+    }
+
+    /// Whether `reviews` pass under this rule
+    fn passes(&self, reviews: &[(String, CriticOutput)]) -> bool {
+        let approvals = reviews.iter().filter(|(_, r)| approves(r)).count();
+        match self {
+            Self::Unanimous => approvals == reviews.len(),
+            Self::Majority => approvals * 2 > reviews.len(),
+            Self::ConfidenceWeighted => {
+                let (approve, object) =
+                    reviews
+                        .iter()
+                        .fold((0.0, 0.0), |(approve, object), (_, review)| {
+                            let weight = review.confidence.clamp(0.0, 1.0);
+                            if approves(review) {
+                                (approve + weight, object)
+                            } else {
+                                (approve, object + weight)
+                            }
+                        });
+                approve > object
+            }
+        }
+    }
+}
+
+/// Fold the reviews of a panel (critic ID, review) into one verdict
+///
+/// The verdict keeps the concerns of every critic who did not approve, tagged
+/// with their critic, even when the panel passes; a rejection is "rejected" if any critic
+/// rejected outright and "needs_changes" otherwise.
+pub fn aggregate(quorum: Quorum, reviews: &[(String, CriticOutput)]) -> CriticOutput {
+    let passed = !reviews.is_empty() && quorum.passes(reviews);
+    let on_side = |review: &CriticOutput| approves(review) == passed;
+
+    let verdict = if passed {
+        "approved"
+    } else if reviews.iter().any(|(_, r)| r.verdict == "rejected") {
+        "rejected"
+    } else {
+        "needs_changes"
+    };
+
+    let concerns = reviews
+        .iter()
+        .filter(|(_, review)| !approves(review))
+        .flat_map(|(id, review)| {
+            review.concerns.iter().map(move |concern| Concern {
+                description: format!("[{}] {}", id, concern.description),
+                ..concern.clone()
+            })
+        })
+        .collect();
+
+    // Confidence is the share of the (confidence-weighted) vote behind the verdict
+    let total: f32 = reviews
+        .iter()
+        .map(|(_, r)| r.confidence.clamp(0.0, 1.0))
+        .sum();
+    let behind: f32 = reviews
+        .iter()
+        .filter(|(_, review)| on_side(review))
+        .map(|(_, r)| r.confidence.clamp(0.0, 1.0))
+        .sum();
+    let confidence = if total > 0.0 { behind / total } else { 0.0 };
+
+    let approvals = reviews.iter().filter(|(_, r)| approves(r)).count();
+    let summary = format!(
+        "{}/{} critics approved ({}). {}",
+        approvals,
+        reviews.len(),
+        quorum.as_str(),
+        reviews
+            .iter()
+            .map(|(id, review)| format!("{} ({}): {}", id, review.verdict, review.summary))
+            .collect::<Vec<_>>()
+            .join(" | ")
+    );
+
+    CriticOutput {
+        verdict: verdict.to_string(),
+        summary,
+        concerns,
+        confidence,
+    }
+}
+
+fn approves(review: &CriticOutput) -> bool {
+    review.verdict == "approved"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn review(id: &str, verdict: &str, confidence: f32) -> (String, CriticOutput) {
+        (
+            id.to_string(),
+            CriticOutput {
+                verdict: verdict.to_string(),
+                summary: format!("{} says {}", id, verdict),
+                concerns: if verdict == "approved" {
+                    Vec::new()
+                } else {
+                    vec![Concern {
+                        severity: "major".to_string(),
+                        description: format!("{} objects", id),
+                        suggested_fix: None,
+                    }]
+                },
+                confidence,
+            },
+        )
+    }
+
+    #[test]
+    fn test_quorum_rules() {
+        let reviews = vec![
+            review("critic", "approved", 0.4),
+            review("security", "needs_changes", 0.9),
+            review("performance", "approved", 0.3),
+        ];
+
+        let unanimous = aggregate(Quorum::Unanimous, &reviews);
+        assert_eq!(unanimous.verdict, "needs_changes");
+        assert_eq!(unanimous.concerns.len(), 1);
+        assert_eq!(
+            unanimous.concerns[0].description,
+            "[security] security objects"
+        );
+
+        let majority = aggregate(Quorum::Majority, &reviews);
+        assert_eq!(majority.verdict, "approved");
+        assert!(majority
+            .summary
+            .starts_with("2/3 critics approved (majority)"));
+        // The dissent is kept even though the decision passed
+        assert_eq!(majority.concerns.len(), 1);
+
+        let weighted = aggregate(Quorum::ConfidenceWeighted, &reviews);
+        assert_eq!(weighted.verdict, "needs_changes");
+        assert!((weighted.confidence - 0.9 / 1.6).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_outright_rejection_and_ties() {
+        let reviews = vec![
+            review("critic", "approved", 0.8),
+            review("security", "rejected", 0.8),
+        ];
+        // Half is not a majority, and equal weights do not outweigh
+        assert_eq!(aggregate(Quorum::Majority, &reviews).verdict, "rejected");
+        assert_eq!(
+            aggregate(Quorum::ConfidenceWeighted, &reviews).verdict,
+            "rejected"
+        );
+        assert_eq!(aggregate(Quorum::Majority, &[]).verdict, "needs_changes");
+    }
+}
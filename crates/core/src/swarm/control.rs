@@ -0,0 +1,211 @@
+//! # Run Control
+//!
+//! Cancellation, pause and skip for a running swarm.
+//!
+//! Each coordinator owns a [`RunControl`]. Its run token is cancelled by
+//! `CoordinatorCommand::Abort`; every unknown and feature gets a child token,
+//! so skipping an unknown or cancelling a feature stops only its own work
+//! while aborting the run stops everything. The tokens are handed to LLM calls
+//! through `ModelConfig::with_cancel`.
+//!
+//! Pausing does not interrupt calls in flight: the coordinator waits at its
+//! next stage boundary (a new research mission, architect or critic call,
+//! builder attempt or feature) until the run is resumed or cancelled.
+
+use anyhow::Result;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tokio::sync::watch;
+use tokio_util::sync::CancellationToken;
+
+/// Error for work stopped by a cancel, skip or abort
+#[derive(Debug, thiserror::Error)]
+#[error("{0} cancelled")]
+pub struct Cancelled(pub String);
+
+/// Whether `error` (or anything it wraps) is a [`Cancelled`]
+pub fn is_cancelled(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| cause.is::<Cancelled>())
+}
+
+/// Run `future` unless `token` is cancelled first
+pub async fn cancellable<T>(
+    token: &CancellationToken,
+    what: &str,
+    future: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    tokio::select! {
+        biased;
+        _ = token.cancelled() => Err(Cancelled(what.to_string()).into()),
+        result = future => result,
+    }
+}
+
+/// Shared cancellation and pause state of one run
+#[derive(Debug, Clone)]
+pub struct RunControl {
+    inner: Arc<ControlState>,
+}
+
+#[derive(Debug)]
+struct ControlState {
+    run: CancellationToken,
+    paused: watch::Sender<bool>,
+    unknowns: Mutex<HashMap<String, CancellationToken>>,
+    features: Mutex<HashMap<String, CancellationToken>>,
+}
+
+impl Default for RunControl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RunControl {
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(ControlState {
+                run: CancellationToken::new(),
+                paused: watch::channel(false).0,
+                unknowns: Mutex::new(HashMap::new()),
+                features: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+
+    /// Token cancelled when the whole run is aborted
+    pub fn token(&self) -> CancellationToken {
+        self.inner.run.clone()
+    }
+
+    /// Abort the run
+    pub fn cancel(&self) {
+        self.inner.run.cancel();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.run.is_cancelled()
+    }
+
+    /// Hold the run at its next stage boundary
+    pub fn pause(&self) {
+        self.inner.paused.send_replace(true);
+    }
+
+    /// Let a paused run continue
+    pub fn resume(&self) {
+        self.inner.paused.send_replace(false);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        *self.inner.paused.borrow()
+    }
+
+    /// Wait until the run is not paused
+    ///
+    /// Fails with [`Cancelled`] if `token` is cancelled first.
+    pub async fn wait_while_paused(&self, token: &CancellationToken) -> Result<()> {
+        let mut paused = self.inner.paused.subscribe();
+        cancellable(token, "Run", async move {
+            // The sender lives as long as `self`, so this cannot fail
+            let _ = paused.wait_for(|paused| !paused).await;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Token for the work on one unknown (cancelled by skipping it)
+    pub fn unknown_token(&self, unknown_id: &str) -> CancellationToken {
+        scoped(&self.inner.unknowns, &self.inner.run, unknown_id)
+    }
+
+    /// Stop the work on an unknown; it is left without a decision
+    pub fn skip_unknown(&self, unknown_id: &str) {
+        self.unknown_token(unknown_id).cancel();
+    }
+
+    /// Whether an unknown was skipped (or the run aborted)
+    pub fn is_skipped(&self, unknown_id: &str) -> bool {
+        self.unknown_token(unknown_id).is_cancelled()
+    }
+
+    /// Token for the work on one feature (cancelled by cancelling it)
+    pub fn feature_token(&self, feature_id: &str) -> CancellationToken {
+        scoped(&self.inner.features, &self.inner.run, feature_id)
+    }
+
+    /// Stop the work on a feature
+    pub fn cancel_feature(&self, feature_id: &str) {
+        self.feature_token(feature_id).cancel();
+    }
+}
+
+/// Child token of `run` for `id`, created on first use
+///
+/// Cancelling an ID before its work starts is remembered, so the work never
+/// starts.
+fn scoped(
+    tokens: &Mutex<HashMap<String, CancellationToken>>,
+    run: &CancellationToken,
+    id: &str,
+) -> CancellationToken {
+    tokens
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .entry(id.to_string())
+        .or_insert_with(|| run.child_token())
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_skip_and_cancel_are_scoped() {
+        let control = RunControl::new();
+        let research = control.unknown_token("U-1");
+
+        control.skip_unknown("U-1");
+        control.cancel_feature("feat-2");
+        assert!(research.is_cancelled());
+        assert!(control.is_skipped("U-1"));
+        assert!(!control.is_skipped("U-2"));
+        assert!(control.feature_token("feat-2").is_cancelled());
+        assert!(!control.feature_token("feat-1").is_cancelled());
+        assert!(!control.is_cancelled());
+
+        // Aborting the run cancels everything below it
+        control.cancel();
+        assert!(control.is_skipped("U-2"));
+        assert!(control.feature_token("feat-1").is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn test_pause_holds_until_resumed_or_cancelled() {
+        let control = RunControl::new();
+        control.pause();
+
+        let waiting = tokio::spawn({
+            let control = control.clone();
+            async move { control.wait_while_paused(&control.token()).await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!waiting.is_finished());
+
+        control.resume();
+        tokio::time::timeout(Duration::from_millis(500), waiting)
+            .await
+            .expect("resume should release the wait")
+            .unwrap()
+            .unwrap();
+
+        control.pause();
+        let token = control.feature_token("feat-1");
+        control.cancel_feature("feat-1");
+        let error = control.wait_while_paused(&token).await.unwrap_err();
+        assert!(is_cancelled(&error.context("Builder failed")));
+    }
+}
@@ -8,24 +8,48 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::{mpsc, oneshot, Semaphore};
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
 
 // New: Centralized model types from models module
-use crate::models::{LlmProvider, ModelConfig};
+use crate::models::{
+    CassetteConfig, FallbackModel, GenerationParams, LlmProvider, ModelConfig, ModelPrice,
+    PriceTable, RepairObserver, ResponseCache, RetryObserver, RetryPolicy, TranscriptSink,
+    UsageSink,
+};
 use crate::skills::{
-    architect_skill::ArchitectOutput, critic_skill::CriticOutput,
-    parse_skill::UnknownsParserOutput, researcher_skill::ResearchOutput, ArchitectSkill,
-    BuilderSkill, CriticSkill, ParseSkill, ResearcherSkill,
+    architect_skill::ArchitectOutput,
+    atomizer_skill::AtomizerOutput,
+    builder_skill::BuilderOutput,
+    critic_skill::CriticOutput,
+    parse_skill::{Ambiguity, UnknownsParserOutput},
+    researcher_skill::ResearchOutput,
+    taskmaster_skill::MissionPrompt,
+    ArchitectSkill, AtomizerSkill, BuilderSkill, ConstraintSkill, CriticSkill, ParseSkill,
+    RedTeamOutput, RedTeamSkill, ResearcherSkill, TaskmasterSkill,
+};
+use crate::state::{
+    CatalystDb, CheckpointStage, FeatureManager, LlmCache, ProjectMode, ProjectState, RunManager,
+    RunRecord, RunStatus, SpecManager, TranscriptManager, UsageLedger, UsageTotals,
 };
-use crate::state::{CatalystDb, ProjectState, SpecManager};
 
-use super::events::{SwarmEvent, SwarmEventKind};
-use super::pipeline::Pipeline;
+use super::budget::{Budget, BudgetAction, UsageMeter};
+use super::consensus::{self, ReviewPanel};
+use super::control::{cancellable, is_cancelled, Cancelled, RunControl};
+use super::events::{LlmEventForwarder, SwarmEvent, SwarmEventKind};
+use super::feature_graph::{FeatureGraph, FeatureSchedule, FeatureStatus};
+use super::graph::{PipelineGraph, StageContext, StageSkill, StageSpec};
+use super::pipeline::{Pipeline, PipelineStage};
+use super::policy::ModePolicy;
+use super::transcripts::TranscriptRecorder;
+use super::unknown_graph::UnknownGraph;
 
 /// Configuration for the coordinator
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CoordinatorConfig {
-    /// Project mode (speed_run, lab, fortress)
-    pub mode: String,
+    /// Project mode (speed_run, lab, fortress); see [`ModePolicy`]
+    #[serde(default)]
+    pub mode: ProjectMode,
     /// Maximum critic rejections
     pub max_rejections: u32,
     /// Global LLM provider (default: Anthropic)
@@ -40,25 +64,89 @@ pub struct CoordinatorConfig {
     /// Per-agent provider overrides (agent_id -> provider)
     #[serde(default)]
     pub per_agent_providers: HashMap<String, LlmProvider>,
-    /// Per-agent base URL overrides (agent_id -> base_url, for OpenAI)
+    /// Per-agent base URL overrides (agent_id -> base_url, for OpenAI/Local)
     #[serde(default)]
     pub per_agent_base_urls: HashMap<String, String>,
+    /// Generation parameters for all agents
+    #[serde(default)]
+    pub generation: GenerationParams,
+    /// Per-agent generation overrides (agent_id -> params, merged over `generation`)
+    #[serde(default)]
+    pub per_agent_generation: HashMap<String, GenerationParams>,
+    /// Endpoint for the Local provider (Ollama, llama.cpp, vLLM).
+    /// Defaults to `http://localhost:11434/v1`.
+    #[serde(default)]
+    pub local_base_url: Option<String>,
     /// Require human approval for critic rejections
     pub require_critic_approval: bool,
     /// Require human approval for architect decisions
     pub require_architect_approval: bool,
     /// Maximum concurrent features (default: 3)
     pub max_concurrent_features: usize,
+    /// Research missions run at once across a run's unknowns (default: 4)
+    #[serde(default)]
+    pub max_concurrent_research: Option<usize>,
     /// Model for WebScraper (cheaper model for HTML cleanup)
     pub scraper_model: Option<String>,
     /// Custom SearXNG instance URL (overrides auto-discovery)
     pub searxng_url: Option<String>,
+    /// Cassette to record LLM responses into, or replay from with the Mock provider
+    #[serde(default)]
+    pub cassette: Option<CassetteConfig>,
+    /// Maximum estimated spend for a run in USD
+    #[serde(default)]
+    pub max_cost_usd: Option<f64>,
+    /// Maximum input + output tokens for a run
+    #[serde(default)]
+    pub max_tokens: Option<u64>,
+    /// Pause (ask via inbox) or abort when the budget is exceeded
+    #[serde(default)]
+    pub budget_action: BudgetAction,
+    /// Price overrides keyed by "provider/model", model, model prefix or provider
+    #[serde(default)]
+    pub prices: HashMap<String, ModelPrice>,
+    /// Retry policy for transient LLM errors (429, 529, 5xx, timeouts)
+    #[serde(default)]
+    pub retry: RetryPolicy,
+    /// Provider/model pairs tried in order when an agent's model keeps failing
+    #[serde(default)]
+    pub fallbacks: Vec<FallbackModel>,
+    /// Per-agent fallback overrides (agent_id -> fallbacks)
+    #[serde(default)]
+    pub per_agent_fallbacks: HashMap<String, Vec<FallbackModel>>,
+    /// Serve repeated LLM calls from the project database (opt-in)
+    #[serde(default)]
+    pub response_cache: bool,
+    /// Lifetime of cached responses in seconds (default: until the prompt changes)
+    #[serde(default)]
+    pub cache_ttl_secs: Option<u64>,
+    /// Ignore cached responses for this run (fresh responses are still cached)
+    #[serde(default)]
+    pub cache_bypass: bool,
+    /// Re-prompts allowed when an agent's structured output fails validation
+    #[serde(default)]
+    pub max_repairs: Option<u32>,
+    /// Builder runs allowed to get `cargo check`/`cargo test` passing when
+    /// executing a plan (default: 3)
+    #[serde(default)]
+    pub max_build_attempts: Option<u32>,
+    /// `cargo check` runs in each Builder run's compile-fix loop (default: 4)
+    #[serde(default)]
+    pub max_fix_iterations: Option<u32>,
+    /// Stage graph to run (default: the standard pipeline built from
+    /// `max_rejections` and `require_architect_approval`)
+    #[serde(default)]
+    pub pipeline: Option<PipelineGraph>,
+    /// Critics that review decisions together, in place of the single Critic
+    /// (review stages with their own agent keep it)
+    #[serde(default)]
+    pub review_panel: Option<ReviewPanel>,
 }
 
 impl Default for CoordinatorConfig {
     fn default() -> Self {
         Self {
-            mode: "lab".to_string(),
+            mode: ProjectMode::Lab,
             max_rejections: 3,
             global_provider: LlmProvider::Anthropic,
             global_model: None,
@@ -66,11 +154,31 @@ impl Default for CoordinatorConfig {
             per_agent_models: HashMap::new(),
             per_agent_providers: HashMap::new(),
             per_agent_base_urls: HashMap::new(),
+            generation: GenerationParams::default(),
+            per_agent_generation: HashMap::new(),
+            local_base_url: None,
             require_critic_approval: true,
             require_architect_approval: false,
             max_concurrent_features: 3,
+            max_concurrent_research: None,
             scraper_model: None, // Uses "claude-3-haiku" by default in webscraper
             searxng_url: None,   // Uses auto-discovery by default
+            cassette: None,
+            max_cost_usd: None,
+            max_tokens: None,
+            budget_action: BudgetAction::Pause,
+            prices: HashMap::new(),
+            retry: RetryPolicy::default(),
+            fallbacks: Vec::new(),
+            per_agent_fallbacks: HashMap::new(),
+            response_cache: false,
+            cache_ttl_secs: None,
+            cache_bypass: false,
+            max_repairs: None,
+            max_build_attempts: None,
+            max_fix_iterations: None,
+            pipeline: None,
+            review_panel: None,
         }
     }
 }
@@ -90,13 +198,23 @@ pub struct ApprovalResponse {
     pub feedback: Option<String>,
 }
 
-/// Commands sent from API to Coordinator for inbox control
+/// Commands sent from API to Coordinator for inbox and run control
+///
+/// Commands are applied as they arrive, including while agents are working.
 #[derive(Debug)]
 pub enum CoordinatorCommand {
-    /// Resume coordinator after interaction was resolved
-    Resume(String),
-    /// Abort the current operation
+    /// An inbox interaction was resolved (wakes the coordinator waiting on it)
+    InteractionResolved(String),
+    /// Abort the run, stopping LLM calls and builds in flight
     Abort,
+    /// Hold the run at its next stage boundary
+    Pause,
+    /// Continue a paused run
+    Resume,
+    /// Stop work on an unknown, leaving it without a decision
+    SkipUnknown(String),
+    /// Stop building a feature and remove its worktree
+    CancelFeature(String),
 }
 
 /// Result of running the swarm on unknowns
@@ -114,8 +232,93 @@ pub struct SwarmResult {
     pub events: Vec<SwarmEvent>,
     /// Whether the pipeline succeeded
     pub success: bool,
+    /// Feature the plan was stored on
+    pub feature_id: Option<String>,
+    /// Atomizer module breakdown (when the decisions were approved)
+    pub plan: Option<AtomizerOutput>,
+    /// Taskmaster mission with drafting missions
+    pub mission: Option<MissionPrompt>,
+    /// Unknowns skipped by the user (no decision or verdict)
+    pub skipped: Vec<String>,
+}
+
+/// Result of executing a goal through to merged code
+#[derive(Debug)]
+pub struct ExecutionResult {
+    /// Planning result (decisions, plan and mission)
+    pub swarm: SwarmResult,
+    /// Files written by the drafting phase
+    pub files_drafted: Vec<String>,
+    /// Last Builder report
+    pub build: Option<BuilderOutput>,
+    /// Whether the feature branch was merged
+    pub merged: bool,
+    /// Files left conflicted by the merge
+    pub conflicts: Vec<String>,
+    /// Why execution stopped early
+    pub error: Option<String>,
+}
+
+/// Work an interrupted run had already finished
+#[derive(Default)]
+struct ResumePoint {
+    unknowns: Option<UnknownsParserOutput>,
+    checkpoints: HashMap<String, UnknownCheckpoint>,
+}
+
+/// Finished stages of one unknown
+#[derive(Default)]
+struct UnknownCheckpoint {
+    research: Option<ResearchOutput>,
+    decision: Option<ArchitectOutput>,
+    verdict: Option<CriticOutput>,
+}
+
+impl ResumePoint {
+    fn load(runs: &RunManager, record: &RunRecord) -> Result<Self> {
+        let unknowns = record
+            .unknowns
+            .clone()
+            .map(serde_json::from_value)
+            .transpose()
+            .context("Invalid unknowns checkpoint")?;
+
+        let mut checkpoints: HashMap<String, UnknownCheckpoint> = HashMap::new();
+        for checkpoint in runs.checkpoints(&record.id)? {
+            let entry = checkpoints
+                .entry(checkpoint.unknown_id.clone())
+                .or_default();
+            match checkpoint.stage {
+                CheckpointStage::Research => entry.research = Some(checkpoint.parse()?),
+                CheckpointStage::Decision => entry.decision = Some(checkpoint.parse()?),
+                CheckpointStage::Verdict => entry.verdict = Some(checkpoint.parse()?),
+            }
+        }
+
+        Ok(Self {
+            unknowns,
+            checkpoints,
+        })
+    }
+}
+
+/// Research missions of a run that are still in flight
+struct ResearchBatch {
+    /// Each task yields the index of its unknown and the research result
+    tasks: JoinSet<(usize, Result<ResearchOutput>)>,
+    /// Events from local research tasks
+    events: mpsc::Receiver<SwarmEvent>,
 }
 
+/// Builder runs per plan when `max_build_attempts` is unset
+const DEFAULT_BUILD_ATTEMPTS: u32 = 3;
+
+/// Compile-fix iterations per Builder run when `max_fix_iterations` is unset
+const DEFAULT_FIX_ITERATIONS: u32 = 4;
+
+/// Concurrent research missions when `max_concurrent_research` is unset
+const DEFAULT_RESEARCH_CONCURRENCY: usize = 4;
+
 /// The swarm coordinator
 pub struct Coordinator {
     config: CoordinatorConfig,
@@ -127,32 +330,104 @@ pub struct Coordinator {
     research_tx: Option<mpsc::Sender<super::a2a_bridge::ResearchMission>>,
     /// Receiver for research progress events
     progress_rx: Option<mpsc::Receiver<super::a2a_bridge::ResearchProgress>>,
-    /// Command receiver for inbox signals from API
-    command_rx: Option<mpsc::Receiver<CoordinatorCommand>>,
+    /// IDs of resolved inbox interactions, routed from the command channel
+    resolved_rx: Option<mpsc::Receiver<String>>,
+    /// Cancellation and pause state of this run
+    control: RunControl,
     /// Unified database for all state
     db: Arc<CatalystDb>,
+    /// ID of this run (keys the usage ledger)
+    run_id: String,
+    /// Token/cost meter shared with every LLM client of this run
+    usage: Arc<UsageMeter>,
+    /// Active budget (lifted when the user chooses to continue)
+    budget: Budget,
+    /// LLM response cache, when enabled
+    cache: Option<Arc<LlmCache>>,
+    /// Records every LLM exchange of this run
+    transcripts: Arc<TranscriptRecorder>,
+    /// Existing feature to plan; a new one is created from the goal otherwise
+    feature_id: Option<String>,
 }
 
 impl Coordinator {
     /// Create a new coordinator with a CatalystDb
     pub fn new(config: CoordinatorConfig, db: Arc<CatalystDb>) -> Self {
         let max_rejections = config.max_rejections;
+        let graph = config.pipeline.clone().unwrap_or_else(|| {
+            PipelineGraph::standard(max_rejections, config.require_architect_approval)
+        });
+        let mut pipeline = Pipeline::from_graph(graph);
+        pipeline.max_rejections = max_rejections;
+        let run_id = format!("run-{}", super::events::uuid_v4());
+        let usage = Arc::new(UsageMeter::new(
+            run_id.clone(),
+            UsageLedger::new(&db),
+            PriceTable::default().with_overrides(&config.prices),
+        ));
+        let budget = Budget {
+            max_cost_usd: config.max_cost_usd,
+            max_tokens: config.max_tokens,
+        };
+        let transcripts = Arc::new(TranscriptRecorder::new(
+            run_id.clone(),
+            TranscriptManager::new(&db),
+        ));
+        let cache = config.response_cache.then(|| {
+            Arc::new(
+                LlmCache::new(&db)
+                    .with_ttl(config.cache_ttl_secs)
+                    .with_bypass(config.cache_bypass),
+            )
+        });
         Self {
             config,
-            pipeline: Pipeline {
-                max_rejections,
-                ..Pipeline::default()
-            },
+            pipeline,
             events: Vec::new(),
             event_tx: None,
             approval_tx: None,
             research_tx: None,
             progress_rx: None,
-            command_rx: None,
+            resolved_rx: None,
+            control: RunControl::new(),
             db,
+            run_id,
+            usage,
+            budget,
+            cache,
+            transcripts,
+            feature_id: None,
         }
     }
 
+    /// ID of this run
+    pub fn run_id(&self) -> &str {
+        &self.run_id
+    }
+
+    /// Token usage and estimated cost of this run so far
+    pub fn usage(&self) -> UsageTotals {
+        self.usage.totals()
+    }
+
+    /// Handle for cancelling, pausing or skipping work of this run
+    pub fn control(&self) -> RunControl {
+        self.control.clone()
+    }
+
+    /// What the configured mode changes about this run
+    pub fn policy(&self) -> ModePolicy {
+        ModePolicy::for_mode(self.config.mode)
+    }
+
+    /// `cargo check` runs allowed in each Builder run
+    fn fix_iterations(&self) -> u32 {
+        self.config
+            .max_fix_iterations
+            .unwrap_or(DEFAULT_FIX_ITERATIONS)
+            .max(1)
+    }
+
     /// Set event channel for streaming events
     pub fn with_event_channel(mut self, tx: mpsc::Sender<SwarmEvent>) -> Self {
         self.event_tx = Some(tx);
@@ -168,25 +443,50 @@ impl Coordinator {
         self
     }
 
+    /// Store the plan and mission on an existing feature
+    pub fn with_feature(mut self, feature_id: impl Into<String>) -> Self {
+        self.feature_id = Some(feature_id.into());
+        self
+    }
+
     /// Enable async research agent (A2A bridge)
     ///
     /// Spawns a background task that processes research missions.
     /// Research progress events are emitted via the event channel.
     pub fn with_research_agent(mut self) -> Self {
+        self.spawn_research_agent();
+        self
+    }
+
+    fn spawn_research_agent(&mut self) {
         let (progress_tx, progress_rx) = mpsc::channel(64);
         let config = self.get_model_config("researcher");
-        let handle = super::a2a_bridge::spawn_research_agent(config, progress_tx);
+        let handle = super::a2a_bridge::spawn_research_agent(
+            config,
+            progress_tx,
+            self.research_concurrency(),
+        );
         self.research_tx = Some(handle.mission_tx);
         self.progress_rx = Some(progress_rx);
-        self
+    }
+
+    fn research_concurrency(&self) -> usize {
+        self.config
+            .max_concurrent_research
+            .unwrap_or(DEFAULT_RESEARCH_CONCURRENCY)
+            .max(1)
     }
 
     /// Enable inbox channel for human-in-the-loop interactions
     ///
     /// This allows the coordinator to pause and wait for user responses
-    /// via the API. Interactions are persisted to SQLite.
+    /// via the API. Interactions are persisted to SQLite. Run control commands
+    /// on the channel take effect immediately; must be called from within a
+    /// Tokio runtime.
     pub fn with_inbox_channel(mut self, rx: mpsc::Receiver<CoordinatorCommand>) -> Self {
-        self.command_rx = Some(rx);
+        let (resolved_tx, resolved_rx) = mpsc::channel(16);
+        tokio::spawn(route_commands(rx, self.control.clone(), resolved_tx));
+        self.resolved_rx = Some(resolved_rx);
         self
     }
 
@@ -207,35 +507,161 @@ impl Coordinator {
             .get(agent_id)
             .or(self.config.global_model.as_ref())
             .cloned()
-            .unwrap_or_else(|| match provider {
-                LlmProvider::Anthropic => "claude-sonnet-4-20250514".to_string(),
-                LlmProvider::OpenAI => "gpt-4o".to_string(),
-                LlmProvider::Gemini => "gemini-2.0-flash-exp".to_string(),
-                LlmProvider::OpenRouter => "anthropic/claude-3.5-sonnet".to_string(),
-                LlmProvider::Grok => "grok-2".to_string(),
-                LlmProvider::DeepSeek => "deepseek-chat".to_string(),
+            .unwrap_or_else(|| {
+                provider
+                    .spec()
+                    .map(|spec| spec.default_model.clone())
+                    .unwrap_or_default()
             });
 
-        // Get base_url: per-agent override -> global (only for OpenAI)
-        let base_url = if provider.supports_base_url() {
+        // Get base_url: per-agent override -> provider endpoint -> global.
+        // Local has its own endpoint so it can run alongside a hosted OpenAI config.
+        let base_url = if !provider.supports_base_url() {
+            None
+        } else if provider == LlmProvider::Local {
+            let global = (self.config.global_provider == LlmProvider::Local)
+                .then_some(self.config.base_url.as_ref())
+                .flatten();
             self.config
                 .per_agent_base_urls
                 .get(agent_id)
-                .or(self.config.base_url.as_ref())
+                .or(self.config.local_base_url.as_ref())
+                .or(global)
                 .cloned()
         } else {
-            None
+            let global = (self.config.global_provider != LlmProvider::Local)
+                .then_some(self.config.base_url.as_ref())
+                .flatten();
+            self.config
+                .per_agent_base_urls
+                .get(agent_id)
+                .or(global)
+                .cloned()
+        };
+
+        let generation = match self.config.per_agent_generation.get(agent_id) {
+            Some(overrides) => self.config.generation.merged(overrides),
+            None => self.config.generation.clone(),
         };
 
+        let forwarder = Arc::new(LlmEventForwarder::new(self.event_tx.clone()));
         ModelConfig {
             provider,
             model,
             base_url,
+            generation,
+            agent_id: Some(agent_id.to_string()),
+            cassette: self.config.cassette.clone(),
+            feature_id: None,
+            usage_sink: Some(Arc::clone(&self.usage) as Arc<dyn UsageSink>),
+            retry: self.config.retry.clone(),
+            fallbacks: self
+                .config
+                .per_agent_fallbacks
+                .get(agent_id)
+                .unwrap_or(&self.config.fallbacks)
+                .clone(),
+            retry_observer: Some(Arc::clone(&forwarder) as Arc<dyn RetryObserver>),
+            response_cache: self
+                .cache
+                .clone()
+                .map(|cache| cache as Arc<dyn ResponseCache>),
+            transcript_sink: Some(Arc::clone(&self.transcripts) as Arc<dyn TranscriptSink>),
+            max_repairs: self.config.max_repairs,
+            repair_observer: Some(forwarder as Arc<dyn RepairObserver>),
+            cancel: Some(self.control.token()),
+        }
+    }
+
+    /// Wait out a pause before starting more work on behalf of `token`
+    ///
+    /// Fails with [`Cancelled`] (naming `what`) once `token` is cancelled.
+    async fn pause_point(&mut self, token: &CancellationToken, what: &str) -> Result<()> {
+        if token.is_cancelled() {
+            return Err(Cancelled(what.to_string()).into());
+        }
+        if !self.control.is_paused() {
+            return Ok(());
+        }
+
+        self.emit(
+            SwarmEvent::new(SwarmEventKind::RunPaused, "coordinator")
+                .with_data(serde_json::json!({"run_id": self.run_id, "waiting": what})),
+        )
+        .await;
+        self.control
+            .wait_while_paused(token)
+            .await
+            .map_err(|_| Cancelled(what.to_string()))?;
+        self.emit(
+            SwarmEvent::new(SwarmEventKind::RunContinued, "coordinator")
+                .with_data(serde_json::json!({"run_id": self.run_id})),
+        )
+        .await;
+        Ok(())
+    }
+
+    /// Check the run budget before starting more LLM work
+    ///
+    /// On `BudgetAction::Pause` the user is asked through the inbox whether to
+    /// continue; continuing lifts the budget for the rest of the run. Without an
+    /// inbox, or on `BudgetAction::Abort`, the run is stopped.
+    async fn enforce_budget(&mut self) -> Result<()> {
+        let totals = self.usage.totals();
+        let Some(reason) = self.budget.exceeded(&totals) else {
+            return Ok(());
+        };
+
+        self.emit(
+            SwarmEvent::new(SwarmEventKind::BudgetExceeded, "coordinator").with_data(
+                serde_json::json!({
+                    "run_id": self.run_id,
+                    "reason": reason,
+                    "usage": totals,
+                    "action": self.config.budget_action,
+                }),
+            ),
+        )
+        .await;
+
+        if self.config.budget_action == BudgetAction::Abort || self.resolved_rx.is_none() {
+            anyhow::bail!("Budget exceeded: {}", reason);
+        }
+
+        use crate::state::{Interaction, InteractionKind, InteractionStatus};
+        use chrono::Utc;
+
+        let interaction = Interaction {
+            id: format!("int-budget-{}-{}", self.run_id, totals.calls),
+            thread_id: self.run_id.clone(),
+            kind: InteractionKind::Decision,
+            status: InteractionStatus::Pending,
+            from_agent: "coordinator".to_string(),
+            title: "Budget exceeded".to_string(),
+            description: format!(
+                "This run's {}. Continue without a budget, or abort?",
+                reason
+            ),
+            options: vec!["Continue".to_string(), "Abort".to_string()],
+            schema: None,
+            created_at: Utc::now(),
+            resolved_at: None,
+            response: None,
+        };
+
+        let response = self.ask_user(interaction).await?;
+        if response.selected_option.as_deref() == Some("Continue") {
+            self.budget = Budget::default();
+            Ok(())
+        } else {
+            anyhow::bail!("Run aborted: budget exceeded ({})", reason)
         }
     }
 
-    /// Emit an event
-    async fn emit(&mut self, event: SwarmEvent) {
+    /// Emit an event, stamped with the run ID
+    async fn emit(&mut self, mut event: SwarmEvent) {
+        event.run_id = Some(self.run_id.clone());
+        self.transcripts.note_event(&event);
         self.events.push(event.clone());
         if let Some(tx) = &self.event_tx {
             let _ = tx.send(event).await;
@@ -278,46 +704,162 @@ impl Coordinator {
         )
         .await;
 
-        // 3. Wait for signal from API
-        if let Some(ref mut rx) = self.command_rx {
-            loop {
-                match rx.recv().await {
-                    Some(CoordinatorCommand::Resume(id)) if id == interaction.id => {
-                        // Reload from disk to get user's response
-                        let mgr = InteractionManager::new(&self.db);
-                        let updated = mgr.load(&id)?;
-
-                        // Emit resolved event
-                        self.emit(
-                            SwarmEvent::new(
-                                SwarmEventKind::InteractionResolved,
-                                &interaction.from_agent,
-                            )
-                            .with_data(serde_json::json!({"interaction_id": id})),
-                        )
-                        .await;
-
-                        return updated.response.ok_or_else(|| {
-                            anyhow::anyhow!("Interaction resolved but no response found")
-                        });
-                    }
-                    Some(CoordinatorCommand::Abort) => {
-                        anyhow::bail!("User aborted operation");
-                    }
-                    None => {
-                        anyhow::bail!("Coordinator command channel closed");
-                    }
-                    _ => continue, // Ignore unrelated Resume signals
-                }
-            }
-        } else {
+        // 3. Wait for signal from API (or for the run to be aborted)
+        let Some(resolved_rx) = self.resolved_rx.as_mut() else {
             anyhow::bail!("Inbox channel not configured - use with_inbox_channel()");
+        };
+        let token = self.control.token();
+        loop {
+            let resolved = tokio::select! {
+                _ = token.cancelled() => return Err(Cancelled("Run".to_string()).into()),
+                resolved = resolved_rx.recv() => resolved,
+            };
+            match resolved {
+                Some(id) if id == interaction.id => break,
+                Some(_) => continue, // Ignore unrelated interactions
+                None => anyhow::bail!("Coordinator command channel closed"),
+            }
         }
+
+        // Reload from disk to get user's response
+        let updated = mgr.load(&interaction.id)?;
+
+        // Emit resolved event
+        self.emit(
+            SwarmEvent::new(SwarmEventKind::InteractionResolved, &interaction.from_agent)
+                .with_data(serde_json::json!({"interaction_id": interaction.id})),
+        )
+        .await;
+
+        updated
+            .response
+            .ok_or_else(|| anyhow::anyhow!("Interaction resolved but no response found"))
     }
 
     /// Run the swarm on a user goal
+    ///
+    /// Every finished per-unknown stage is checkpointed under the run ID, so
+    /// the run can be continued with [`resume`](Self::resume) if it is cut off.
     #[tracing::instrument(skip(self), fields(goal_preview = %goal.chars().take(50).collect::<String>()))]
     pub async fn run(&mut self, goal: &str) -> Result<SwarmResult> {
+        RunManager::new(&self.db).start(&self.run_id, goal)?;
+        self.run_checkpointed(goal, ResumePoint::default()).await
+    }
+
+    /// Continue an interrupted run, skipping the stages it already finished
+    ///
+    /// The coordinator takes over the run's ID, so usage, transcripts and the
+    /// budget carry on from where the run stopped.
+    pub async fn resume(&mut self, run_id: &str) -> Result<SwarmResult> {
+        let runs = RunManager::new(&self.db);
+        let record = runs.load(run_id)?;
+        if record.status == RunStatus::Completed {
+            anyhow::bail!("Run {} already completed", run_id);
+        }
+
+        self.adopt_run(run_id);
+        if record.feature_id.is_some() {
+            self.feature_id = record.feature_id.clone();
+        }
+        let resume_point = ResumePoint::load(&runs, &record)?;
+        runs.set_status(run_id, RunStatus::Running, None)?;
+
+        self.emit(
+            SwarmEvent::new(SwarmEventKind::RunResumed, "coordinator").with_data(
+                serde_json::json!({
+                    "run_id": run_id,
+                    "completed_unknowns": record.completed_unknowns,
+                }),
+            ),
+        )
+        .await;
+
+        self.run_checkpointed(&record.goal, resume_point).await
+    }
+
+    /// Switch this coordinator to an existing run ID
+    fn adopt_run(&mut self, run_id: &str) {
+        let spent = UsageLedger::new(&self.db)
+            .totals(Some(run_id))
+            .unwrap_or_default();
+        self.run_id = run_id.to_string();
+        self.usage = Arc::new(
+            UsageMeter::new(
+                run_id,
+                UsageLedger::new(&self.db),
+                PriceTable::default().with_overrides(&self.config.prices),
+            )
+            .with_totals(spent),
+        );
+        self.transcripts = Arc::new(TranscriptRecorder::new(
+            run_id,
+            TranscriptManager::new(&self.db),
+        ));
+        // The research agent holds the previous run's usage meter
+        if self.research_tx.is_some() {
+            self.spawn_research_agent();
+        }
+    }
+
+    /// Run the stages and record how the run ended
+    ///
+    /// Whatever error an aborted run stops with is reported as [`Cancelled`].
+    async fn run_checkpointed(
+        &mut self,
+        goal: &str,
+        resume_point: ResumePoint,
+    ) -> Result<SwarmResult> {
+        let result = match self.run_stages(goal, resume_point).await {
+            Err(_) if self.control.is_cancelled() => {
+                self.record_cancelled().await;
+                Err(Cancelled("Run".to_string()).into())
+            }
+            result => result,
+        };
+        let runs = RunManager::new(&self.db);
+        let status = match &result {
+            Ok(result) if result.success => {
+                runs.set_status(&self.run_id, RunStatus::Completed, None)
+            }
+            Ok(_) => runs.set_status(&self.run_id, RunStatus::Failed, None),
+            Err(e) if is_cancelled(e) => runs.set_status(&self.run_id, RunStatus::Cancelled, None),
+            Err(e) => runs.set_status(&self.run_id, RunStatus::Failed, Some(&e.to_string())),
+        };
+        if let Err(e) = status {
+            tracing::warn!("Failed to record run status: {}", e);
+        }
+        result
+    }
+
+    /// Announce an aborted run and leave the project idle
+    async fn record_cancelled(&mut self) {
+        self.pipeline.stage = PipelineStage::Failed;
+        let mut project_state = ProjectState::load(&self.db).unwrap_or_default();
+        project_state.active_agent = None;
+        project_state.phase = "cancelled".to_string();
+        let _ = project_state.save(&self.db);
+
+        self.emit(
+            SwarmEvent::new(SwarmEventKind::RunCancelled, "coordinator")
+                .with_data(serde_json::json!({"run_id": self.run_id})),
+        )
+        .await;
+    }
+
+    /// Save a finished stage so a resumed run can skip it
+    fn checkpoint<T: Serialize>(&self, unknown_id: &str, stage: CheckpointStage, output: &T) {
+        if let Err(e) =
+            RunManager::new(&self.db).save_checkpoint(&self.run_id, unknown_id, stage, output)
+        {
+            tracing::warn!("Failed to checkpoint {} {:?}: {}", unknown_id, stage, e);
+        }
+    }
+
+    async fn run_stages(
+        &mut self,
+        goal: &str,
+        mut resume_point: ResumePoint,
+    ) -> Result<SwarmResult> {
         self.emit(SwarmEvent::new(
             SwarmEventKind::PipelineStarted,
             "coordinator",
@@ -328,378 +870,1329 @@ impl Coordinator {
         let mut project_state = ProjectState::load(&self.db).unwrap_or_default();
         project_state.active_agent = Some("coordinator".to_string());
         project_state.phase = "planning".to_string();
+        project_state.mode = Some(self.config.mode);
         let _ = project_state.save(&self.db);
 
-        // Stage 1: Parse unknowns
-        self.emit(SwarmEvent::new(
-            SwarmEventKind::AgentStarted,
-            "unknowns_parser",
-        ))
-        .await;
-
-        let model_config = self.get_model_config("unknowns_parser");
-        let parse_output = ParseSkill::run(goal, &model_config).await.context(format!(
-            "Unknowns Parser failed (provider: {:?}, model: {})",
-            model_config.provider, model_config.model
-        ))?;
-
-        // Use ParseOutput directly (types are now in parse_skill)
-        let unknowns = parse_output;
-
-        self.emit(
-            SwarmEvent::new(SwarmEventKind::AgentCompleted, "unknowns_parser")
-                .with_data(serde_json::to_value(&unknowns)?),
-        )
-        .await;
+        self.pipeline
+            .graph()
+            .validate()
+            .context("Invalid pipeline")?;
 
-        // Save unknowns to spec fragment
-        let unknowns_md = format!(
-            "# Unknowns\n\nGenerated by Unknowns Parser\n\n{}\n",
+        // Parse unknowns (kept from the checkpoint when resuming)
+        let parse_stage = self.pipeline.graph().parse_stage().cloned();
+        let unknowns = if let Some(unknowns) = resume_point.unknowns.take() {
             unknowns
-                .ambiguities
-                .iter()
-                .map(|u| format!(
-                    "## [{}] {}\n**Category:** {:?}\n**Priority:** {:?}\n\n{}\n",
-                    u.id,
-                    u.question,
-                    u.category,
-                    u.criticality,
-                    u.context.as_deref().unwrap_or("")
-                ))
-                .collect::<Vec<String>>()
-                .join("\n")
-        );
-
-        // Save unknowns to database (via SpecManager)
-        let spec_mgr = SpecManager::new(&self.db);
-        if let Err(e) = spec_mgr.write_fragment("unknowns", &unknowns_md) {
-            tracing::warn!("Failed to save unknowns: {}", e);
-        }
-
-        self.pipeline.advance();
+        } else if let Some(stage) = parse_stage.filter(|_| self.policy().parse_unknowns) {
+            self.pipeline.enter(&stage);
+            let agent = stage.agent().to_string();
+            let mut attempt = 0;
+            let unknowns = loop {
+                attempt += 1;
+                self.emit(SwarmEvent::new(SwarmEventKind::AgentStarted, &agent))
+                    .await;
 
-        let mut research_results = Vec::new();
-        let mut decisions = Vec::new();
-        let mut verdicts = Vec::new();
+                let model_config = self.get_model_config(&agent);
+                let unknowns = ParseSkill::run(goal, &model_config).await.context(format!(
+                    "Unknowns Parser failed (provider: {:?}, model: {})",
+                    model_config.provider, model_config.model
+                ))?;
 
-        // Process each unknown through the pipeline
-        for ambiguity in &unknowns.ambiguities {
-            // Stage 2: Research (sync or async via A2A bridge)
-            let research = if self.research_tx.is_some() {
-                // === Async Research via A2A Bridge ===
                 self.emit(
-                    SwarmEvent::new(SwarmEventKind::ResearchStarted, "researcher")
-                        .with_unknown(&ambiguity.id),
+                    SwarmEvent::new(SwarmEventKind::AgentCompleted, &agent)
+                        .with_data(serde_json::to_value(&unknowns)?),
                 )
                 .await;
 
-                // Dispatch mission (clone tx to avoid borrow issues)
-                let research_tx = self.research_tx.as_ref().unwrap().clone();
-                let (response_tx, response_rx) = oneshot::channel();
-                let mission = super::a2a_bridge::ResearchMission {
-                    unknown_id: ambiguity.id.clone(),
-                    question: ambiguity.question.clone(),
-                    context: ambiguity.context.clone().unwrap_or_default(),
-                    response_tx,
-                };
+                let approved = self
+                    .approve_stage(
+                        &stage,
+                        "goal",
+                        attempt,
+                        format!("Approve {} unknowns", unknowns.ambiguities.len()),
+                        goal.to_string(),
+                    )
+                    .await;
+                if approved {
+                    break unknowns;
+                }
+            };
+
+            // Save unknowns to spec fragment
+            let unknowns_md = format!(
+                "# Unknowns\n\nGenerated by Unknowns Parser\n\n{}\n",
+                unknowns
+                    .ambiguities
+                    .iter()
+                    .map(|u| format!(
+                        "## [{}] {}\n**Category:** {:?}\n**Priority:** {:?}\n{}\n{}\n",
+                        u.id,
+                        u.question,
+                        u.category,
+                        u.criticality,
+                        if u.depends_on.is_empty() {
+                            String::new()
+                        } else {
+                            format!("**Depends on:** {}\n", u.depends_on.join(", "))
+                        },
+                        u.context.as_deref().unwrap_or("")
+                    ))
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            );
+
+            // Save unknowns to database (via SpecManager)
+            let spec_mgr = SpecManager::new(&self.db);
+            if let Err(e) = spec_mgr.write_fragment("unknowns", &unknowns_md) {
+                tracing::warn!("Failed to save unknowns: {}", e);
+            }
+            if let Err(e) = RunManager::new(&self.db).set_unknowns(&self.run_id, &unknowns) {
+                tracing::warn!("Failed to checkpoint unknowns: {}", e);
+            }
+            unknowns
+        } else {
+            // Speed run (or a pipeline without a parse stage): plan straight from the goal
+            let unknowns = UnknownsParserOutput {
+                ambiguities: Vec::new(),
+            };
+            if let Err(e) = RunManager::new(&self.db).set_unknowns(&self.run_id, &unknowns) {
+                tracing::warn!("Failed to checkpoint unknowns: {}", e);
+            }
+            unknowns
+        };
 
-                if research_tx.send(mission).await.is_err() {
-                    anyhow::bail!("Failed to dispatch research mission");
+        // Results are kept in ambiguity order, whatever order research finishes in
+        let total = unknowns.ambiguities.len();
+        let mut research_results: Vec<Option<ResearchOutput>> = vec![None; total];
+        let mut decisions: Vec<Option<ArchitectOutput>> = vec![None; total];
+        let mut verdicts: Vec<Option<CriticOutput>> = vec![None; total];
+        let mut skipped = vec![false; total];
+
+        let mut researched = Vec::new();
+        let mut pending = Vec::new();
+        for (index, ambiguity) in unknowns.ambiguities.iter().enumerate() {
+            let checkpoint = resume_point
+                .checkpoints
+                .remove(&ambiguity.id)
+                .unwrap_or_default();
+            if let Some(verdict) = checkpoint.verdict {
+                // Finished before the run was interrupted
+                research_results[index] = checkpoint.research;
+                decisions[index] = checkpoint.decision;
+                verdicts[index] = Some(verdict);
+                continue;
+            }
+            let context = StageContext {
+                mode: self.config.mode,
+                ambiguity: Some(ambiguity),
+            };
+            match (
+                checkpoint.research,
+                self.pipeline.graph().unknown_entry(&context),
+            ) {
+                (Some(research), _) => researched.push((index, research)),
+                (None, Some(stage)) if stage.skill == StageSkill::Research => {
+                    pending.push((index, ambiguity.clone(), stage.agent().to_string()))
                 }
+                // No research stage on this unknown's path
+                (None, _) => researched.push((
+                    index,
+                    ResearchOutput {
+                        unknown_id: ambiguity.id.clone(),
+                        options: Vec::new(),
+                        summary: "Not researched (no research stage for this unknown)".to_string(),
+                        recommended: None,
+                    },
+                )),
+            }
+        }
 
-                // Wait for result while draining progress events
-                let mut response_rx = response_rx;
-                let result = loop {
-                    // Try to receive progress events first (non-blocking)
-                    if let Some(ref mut progress_rx) = self.progress_rx {
-                        match progress_rx.try_recv() {
-                            Ok(progress) => {
-                                // Convert progress to SwarmEvent
-                                let event = match &progress {
-                                    super::a2a_bridge::ResearchProgress::Started { unknown_id } => {
-                                        SwarmEvent::new(
-                                            SwarmEventKind::ResearchStarted,
-                                            "researcher",
-                                        )
-                                        .with_unknown(unknown_id)
-                                    }
-                                    super::a2a_bridge::ResearchProgress::Status {
-                                        unknown_id,
-                                        message,
-                                    } => SwarmEvent::new(
-                                        SwarmEventKind::ResearchProgress,
-                                        "researcher",
-                                    )
-                                    .with_unknown(unknown_id)
-                                    .with_data(serde_json::json!({ "message": message })),
-                                    super::a2a_bridge::ResearchProgress::Completed {
-                                        unknown_id,
-                                    } => SwarmEvent::new(
-                                        SwarmEventKind::ResearchCompleted,
-                                        "researcher",
-                                    )
-                                    .with_unknown(unknown_id),
-                                    super::a2a_bridge::ResearchProgress::Failed {
-                                        unknown_id,
-                                        error,
-                                    } => SwarmEvent::new(SwarmEventKind::AgentFailed, "researcher")
-                                        .with_unknown(unknown_id)
-                                        .with_data(serde_json::json!({ "error": error })),
-                                };
-                                self.emit(event).await;
-                                continue; // Check for more progress events
-                            }
-                            Err(mpsc::error::TryRecvError::Empty) => {
-                                // No more progress events, check for result
-                            }
-                            Err(mpsc::error::TryRecvError::Disconnected) => {
-                                // Progress channel closed, just wait for result
-                            }
+        let graph = UnknownGraph::new(&unknowns.ambiguities);
+        if !graph.cycles().is_empty() {
+            self.report_dependency_cycles(graph.cycles()).await?;
+        }
+
+        // Research every remaining unknown concurrently
+        if !pending.is_empty() {
+            self.pipeline.stage = PipelineStage::Researching;
+            self.pause_point(&self.control.token(), "Run").await?;
+            self.enforce_budget().await?;
+        }
+        let mut batch = self.dispatch_research(pending);
+
+        // An unknown goes on to its decision stages once its research is in and
+        // the unknowns it depends on are decided (or skipped)
+        loop {
+            let ready = researched
+                .iter()
+                .enumerate()
+                .filter(|(_, (index, _))| {
+                    self.control.is_skipped(&unknowns.ambiguities[*index].id)
+                        || graph
+                            .dependencies(*index)
+                            .iter()
+                            .all(|&dep| verdicts[dep].is_some() || skipped[dep])
+                })
+                .min_by_key(|(_, (index, _))| graph.rank(*index))
+                .map(|(position, _)| position);
+            let (index, research) = match ready {
+                Some(position) => researched.swap_remove(position),
+                None => match self.next_research(&mut batch).await? {
+                    Some((index, Ok(research))) => {
+                        let id = &unknowns.ambiguities[index].id;
+                        self.checkpoint(id, CheckpointStage::Research, &research);
+                        researched.push((index, research));
+                        continue;
+                    }
+                    Some((index, Err(e))) => {
+                        let id = &unknowns.ambiguities[index].id;
+                        if !self.control.is_skipped(id) || self.control.is_cancelled() {
+                            return Err(e);
                         }
+                        self.note_skipped(id).await;
+                        skipped[index] = true;
+                        continue;
                     }
+                    None => break,
+                },
+            };
+            let ambiguity = &unknowns.ambiguities[index];
 
-                    // Check if result is ready (with small timeout to allow progress events)
-                    match tokio::time::timeout(
-                        std::time::Duration::from_millis(50),
-                        &mut response_rx,
-                    )
-                    .await
+            let depends_on: Vec<ArchitectOutput> = graph
+                .declared(index)
+                .iter()
+                .filter_map(|&dep| decisions[dep].clone())
+                .collect();
+            let (decision, verdict) =
+                match self.decide_unknown(ambiguity, &research, &depends_on).await {
+                    Ok(outcome) => outcome,
+                    Err(_)
+                        if self.control.is_skipped(&ambiguity.id)
+                            && !self.control.is_cancelled() =>
                     {
-                        Ok(Ok(Ok(research))) => break research,
-                        Ok(Ok(Err(e))) => {
-                            self.emit(
-                                SwarmEvent::new(SwarmEventKind::AgentFailed, "researcher")
-                                    .with_unknown(&ambiguity.id)
-                                    .with_data(serde_json::json!({ "error": e.to_string() })),
-                            )
-                            .await;
-                            anyhow::bail!("Researcher failed: {}", e);
-                        }
-                        Ok(Err(_)) => anyhow::bail!("Research channel closed unexpectedly"),
-                        Err(_) => {
-                            // Timeout - loop again to check for progress events
-                            continue;
-                        }
+                        self.note_skipped(&ambiguity.id).await;
+                        research_results[index] = Some(research);
+                        skipped[index] = true;
+                        continue;
                     }
+                    Err(e) => return Err(e),
                 };
+            if let Some(decision) = &decision {
+                self.checkpoint(&ambiguity.id, CheckpointStage::Decision, decision);
+                self.merge_spec_updates(decision).await;
+            }
+            self.checkpoint(&ambiguity.id, CheckpointStage::Verdict, &verdict);
 
-                result
-            } else {
-                // === Sync Research (fallback) ===
-                self.emit(
-                    SwarmEvent::new(SwarmEventKind::AgentStarted, "researcher")
-                        .with_unknown(&ambiguity.id),
-                )
-                .await;
-
-                let result = ResearcherSkill::run(
-                    &ambiguity.id,
-                    &ambiguity.question,
-                    ambiguity.context.as_deref().unwrap_or(""),
-                    &self.get_model_config("researcher"),
-                )
-                .await
-                .context("Researcher failed")?;
+            research_results[index] = Some(research);
+            decisions[index] = decision;
+            verdicts[index] = Some(verdict);
+        }
 
-                self.emit(
-                    SwarmEvent::new(SwarmEventKind::AgentCompleted, "researcher")
-                        .with_unknown(&ambiguity.id),
-                )
-                .await;
+        let research_results: Vec<ResearchOutput> =
+            research_results.into_iter().flatten().collect();
+        let decisions: Vec<ArchitectOutput> = decisions.into_iter().flatten().collect();
+        let verdicts: Vec<CriticOutput> = verdicts.into_iter().flatten().collect();
+        let skipped: Vec<String> = unknowns
+            .ambiguities
+            .iter()
+            .zip(&skipped)
+            .filter(|(_, skipped)| **skipped)
+            .map(|(ambiguity, _)| ambiguity.id.clone())
+            .collect();
+
+        let mut success = verdicts.iter().all(|v| v.verdict == "approved");
+
+        // Atomize the approved decisions and bundle missions
+        let mut planned = None;
+        if !success {
+            if let Some(id) = &self.feature_id {
+                let _ = FeatureManager::new(&self.db)
+                    .set_failed(id, "Critic did not approve every decision");
+            }
+        } else if self.pipeline.graph().after_unknowns().is_some() {
+            match self.plan_feature(goal, &decisions).await {
+                Ok(result) => planned = Some(result),
+                Err(e) => {
+                    tracing::warn!("Planning failed: {}", e);
+                    if let Some(id) = &self.feature_id {
+                        let _ = FeatureManager::new(&self.db).set_failed(id, &e.to_string());
+                    }
+                    success = false;
+                }
+            }
+        }
+        self.pipeline.stage = if success {
+            PipelineStage::Complete
+        } else {
+            PipelineStage::Failed
+        };
 
-                result
-            };
+        self.emit(SwarmEvent::new(
+            if success {
+                SwarmEventKind::PipelineCompleted
+            } else {
+                SwarmEventKind::PipelineFailed
+            },
+            "coordinator",
+        ))
+        .await;
 
-            // Stage 3-4: Architect-Critic loop
-            let mut attempts = 0;
-            let max_attempts = self.config.max_rejections as usize;
+        // Update project state
+        project_state.active_agent = None;
+        project_state.phase = if success { "execution_ready" } else { "failed" }.to_string();
+        let _ = project_state.save(&self.db);
 
-            loop {
-                attempts += 1;
+        let (feature_id, plan, mission) = match planned {
+            Some((id, plan, mission)) => (Some(id), plan, mission),
+            None => (self.feature_id.clone(), None, None),
+        };
 
-                // Stage 3: Architect decision
-                self.emit(
-                    SwarmEvent::new(SwarmEventKind::AgentStarted, "architect")
-                        .with_unknown(&ambiguity.id),
-                )
-                .await;
+        Ok(SwarmResult {
+            unknowns,
+            research: research_results,
+            decisions,
+            verdicts,
+            events: self.events.clone(),
+            success,
+            feature_id,
+            plan,
+            mission,
+            skipped,
+        })
+    }
 
-                let research_json = serde_json::to_string_pretty(&research)?;
-                let decision = ArchitectSkill::run(
-                    &ambiguity.id,
-                    &research_json,
-                    "", // Would load spec here
-                    &self.config.mode,
-                    &self.get_model_config("architect"),
-                )
-                .await
-                .context("Architect failed")?;
+    /// Write an accepted decision's spec updates into the project documents
+    async fn merge_spec_updates(&mut self, decision: &ArchitectOutput) {
+        use crate::state::SpecEditStatus;
 
-                self.emit(
-                    SwarmEvent::new(SwarmEventKind::AgentCompleted, "architect")
-                        .with_unknown(&ambiguity.id),
-                )
-                .await;
+        if decision.spec_updates.is_empty() {
+            return;
+        }
+        let edits = match SpecManager::new(&self.db).apply_decision(&self.run_id, decision) {
+            Ok(edits) => edits,
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to apply spec updates of {}: {}",
+                    decision.unknown_id,
+                    e
+                );
+                return;
+            }
+        };
 
-                // Request approval for architect if configured (uses Inbox)
-                if self.config.require_architect_approval {
-                    use crate::state::{Interaction, InteractionKind, InteractionStatus};
-                    use chrono::Utc;
-
-                    let interaction = Interaction {
-                        id: format!("int-arch-{}-{}", ambiguity.id, attempts),
-                        thread_id: ambiguity.id.clone(),
-                        kind: InteractionKind::Decision,
-                        status: InteractionStatus::Pending,
-                        from_agent: "architect".to_string(),
-                        title: format!("Approve: {}", decision.chosen_option),
-                        description: decision.rationale.clone(),
-                        options: vec![
-                            "Approve".to_string(),
-                            "Reject".to_string(),
-                            "Modify".to_string(),
-                        ],
-                        schema: None,
-                        created_at: Utc::now(),
-                        resolved_at: None,
-                        response: None,
+        let section = |edit: &crate::state::SpecEdit| format!("{}#{}", edit.document, edit.section);
+        let (applied, conflicts): (Vec<_>, Vec<_>) = edits
+            .iter()
+            .partition(|edit| edit.status == SpecEditStatus::Applied);
+        for conflict in &conflicts {
+            tracing::warn!(
+                "Spec update of {} conflicts with an earlier decision: {}",
+                decision.unknown_id,
+                section(conflict)
+            );
+        }
+        self.emit(
+            SwarmEvent::new(SwarmEventKind::SpecUpdated, "architect")
+                .with_unknown(&decision.unknown_id)
+                .with_data(serde_json::json!({
+                    "applied": applied.iter().map(|e| section(e)).collect::<Vec<_>>(),
+                    "conflicts": conflicts.iter().map(|e| section(e)).collect::<Vec<_>>(),
+                })),
+        )
+        .await;
+    }
+
+    /// Announce an unknown the user skipped
+    async fn note_skipped(&mut self, unknown_id: &str) {
+        tracing::info!("Unknown {} skipped", unknown_id);
+        self.emit(
+            SwarmEvent::new(SwarmEventKind::UnknownSkipped, "coordinator").with_unknown(unknown_id),
+        )
+        .await;
+    }
+
+    /// Start research for every pending unknown
+    ///
+    /// Missions go to the A2A research agent when it is enabled (it bounds
+    /// its own concurrency); otherwise they run as local tasks limited to
+    /// `max_concurrent_research` at a time, each with the model of the agent
+    /// paired with it (that of its research stage).
+    fn dispatch_research(&self, pending: Vec<(usize, Ambiguity, String)>) -> ResearchBatch {
+        let (event_tx, events) = mpsc::channel(64);
+        let mut tasks = JoinSet::new();
+
+        if let Some(research_tx) = &self.research_tx {
+            // === Async Research via A2A Bridge ===
+            for (index, ambiguity, _) in pending {
+                let research_tx = research_tx.clone();
+                let control = self.control.clone();
+                let token = self.control.unknown_token(&ambiguity.id);
+                tasks.spawn(async move {
+                    if let Err(e) = control.wait_while_paused(&token).await {
+                        return (index, Err(e));
+                    }
+                    let (response_tx, response_rx) = oneshot::channel();
+                    let mission = super::a2a_bridge::ResearchMission {
+                        unknown_id: ambiguity.id.clone(),
+                        question: ambiguity.question,
+                        context: ambiguity.context.unwrap_or_default(),
+                        cancel: token,
+                        response_tx,
+                    };
+                    if research_tx.send(mission).await.is_err() {
+                        return (
+                            index,
+                            Err(anyhow::anyhow!("Failed to dispatch research mission")),
+                        );
+                    }
+                    let result = match response_rx.await {
+                        Ok(result) => result.context("Researcher failed"),
+                        Err(_) => Err(anyhow::anyhow!("Research channel closed unexpectedly")),
                     };
+                    (index, result)
+                });
+            }
+        } else {
+            // === Local research tasks (fallback) ===
+            let semaphore = Arc::new(Semaphore::new(self.research_concurrency()));
+            for (index, ambiguity, agent) in pending {
+                let semaphore = Arc::clone(&semaphore);
+                let control = self.control.clone();
+                let token = self.control.unknown_token(&ambiguity.id);
+                let config = self.get_model_config(&agent).with_cancel(token.clone());
+                let event_tx = event_tx.clone();
+                tasks.spawn(async move {
+                    let _permit = semaphore.acquire_owned().await;
+                    if let Err(e) = control.wait_while_paused(&token).await {
+                        return (index, Err(e));
+                    }
+                    let _ = event_tx
+                        .send(
+                            SwarmEvent::new(SwarmEventKind::AgentStarted, &agent)
+                                .with_unknown(&ambiguity.id),
+                        )
+                        .await;
 
-                    // Use inbox if configured, otherwise fall back to old approval channel
-                    if self.command_rx.is_some() {
-                        match self.ask_user(interaction).await {
-                            Ok(response) => {
-                                if response.selected_option.as_deref() == Some("Reject") {
-                                    continue; // Loop back to re-run architect
-                                }
-                                // Approve or Modify both continue to critic
-                            }
-                            Err(e) => {
-                                tracing::warn!("Inbox interaction failed: {}, continuing...", e);
-                            }
-                        }
-                    } else {
-                        // Fall back to old approval channel
-                        let approval = self
-                            .request_approval(ApprovalRequest {
-                                decision_id: format!("arch-{}-{}", ambiguity.id, attempts),
-                                agent_id: "architect".to_string(),
-                                summary: format!(
-                                    "Approve architect decision for {}?",
-                                    ambiguity.id
-                                ),
-                            })
-                            .await;
+                    let result = cancellable(
+                        &token,
+                        &format!("Research for {}", ambiguity.id),
+                        ResearcherSkill::run(
+                            &ambiguity.id,
+                            &ambiguity.question,
+                            ambiguity.context.as_deref().unwrap_or(""),
+                            &config,
+                        ),
+                    )
+                    .await
+                    .context("Researcher failed");
+
+                    let event = match &result {
+                        Ok(_) => SwarmEvent::new(SwarmEventKind::AgentCompleted, &agent),
+                        Err(e) => SwarmEvent::new(SwarmEventKind::AgentFailed, &agent)
+                            .with_data(serde_json::json!({ "error": format!("{:#}", e) })),
+                    };
+                    let _ = event_tx.send(event.with_unknown(&ambiguity.id)).await;
+                    (index, result)
+                });
+            }
+        }
+
+        ResearchBatch { tasks, events }
+    }
 
-                        match approval {
-                            Some(resp) if !resp.approved => {
+    /// Wait for the next research result, emitting progress events meanwhile
+    ///
+    /// Returns `None` once every mission of the batch has finished.
+    async fn next_research(
+        &mut self,
+        batch: &mut ResearchBatch,
+    ) -> Result<Option<(usize, Result<ResearchOutput>)>> {
+        loop {
+            // Progress is drained first so an unknown's events precede its result
+            let event = tokio::select! {
+                biased;
+                Some(event) = batch.events.recv() => event,
+                Some(progress) = recv_progress(&mut self.progress_rx) => research_event(&progress),
+                joined = batch.tasks.join_next() => {
+                    return match joined {
+                        None => Ok(None),
+                        Some(Ok((index, result))) => Ok(Some((index, result))),
+                        Some(Err(e)) => Err(anyhow::anyhow!("Research task failed: {}", e)),
+                    };
+                }
+            };
+            self.emit(event).await;
+        }
+    }
+
+    /// Report dependency cycles between unknowns as an inbox decision
+    ///
+    /// The unknowns of a cycle are decided in listed order unless the user
+    /// aborts. Without an inbox the decision is recorded and the run goes on.
+    async fn report_dependency_cycles(&mut self, cycles: &[Vec<String>]) -> Result<()> {
+        use crate::state::{Interaction, InteractionKind, InteractionManager, InteractionStatus};
+        use chrono::Utc;
+
+        let listed = cycles
+            .iter()
+            .map(|cycle| cycle.join(", "))
+            .collect::<Vec<_>>()
+            .join("; ");
+        let interaction = Interaction {
+            id: format!("int-cycle-{}", self.run_id),
+            thread_id: self.run_id.clone(),
+            kind: InteractionKind::Decision,
+            status: InteractionStatus::Pending,
+            from_agent: "coordinator".to_string(),
+            title: "Unknowns depend on each other".to_string(),
+            description: format!(
+                "These unknowns form a dependency cycle: {}. They will be decided in the \
+                 order listed, each seeing the decisions approved before it.",
+                listed
+            ),
+            options: vec!["Decide in listed order".to_string(), "Abort".to_string()],
+            schema: None,
+            created_at: Utc::now(),
+            resolved_at: None,
+            response: None,
+        };
+
+        if self.resolved_rx.is_none() {
+            tracing::warn!("Dependency cycle between unknowns: {}", listed);
+            if let Err(e) = InteractionManager::new(&self.db).save(&interaction) {
+                tracing::warn!("Failed to record dependency cycle: {}", e);
+            }
+            self.emit(
+                SwarmEvent::new(SwarmEventKind::InteractionRequired, "coordinator").with_data(
+                    serde_json::json!({
+                        "interaction_id": interaction.id,
+                        "title": interaction.title,
+                        "kind": interaction.kind
+                    }),
+                ),
+            )
+            .await;
+            return Ok(());
+        }
+
+        let response = self.ask_user(interaction).await?;
+        if response.selected_option.as_deref() == Some("Abort") {
+            anyhow::bail!("Aborted on dependency cycle between unknowns: {}", listed);
+        }
+        Ok(())
+    }
+
+    /// Walk one researched unknown through the decision stages on its path
+    ///
+    /// Returns the accepted decision (if every review on the path approved it,
+    /// or the user overrode a rejection) and the last verdict.
+    async fn decide_unknown(
+        &mut self,
+        ambiguity: &Ambiguity,
+        research: &ResearchOutput,
+        depends_on: &[ArchitectOutput],
+    ) -> Result<(Option<ArchitectOutput>, CriticOutput)> {
+        let graph = self.pipeline.graph().clone();
+        let context = StageContext {
+            mode: self.config.mode,
+            ambiguity: Some(ambiguity),
+        };
+        let token = self.control.unknown_token(&ambiguity.id);
+        let what = format!("Unknown {}", ambiguity.id);
+        let research_json = serde_json::to_string_pretty(research)?;
+
+        let mut stage = graph.unknown_entry(&context);
+        if let Some(research_stage) = stage.filter(|s| s.skill == StageSkill::Research) {
+            stage = graph.unknown_next(research_stage, &context);
+        }
+        let mut decision: Option<ArchitectOutput> = None;
+        let mut verdict: Option<CriticOutput> = None;
+        // Last rejected review, handed to the architect when it decides again
+        let mut feedback: Option<CriticOutput> = None;
+        let mut rejections: HashMap<&str, u32> = HashMap::new();
+        let mut attempt = 0;
+
+        while let Some(current) = stage {
+            attempt += 1;
+            self.pipeline.enter(current);
+            self.pause_point(&token, &what).await?;
+            self.enforce_budget().await?;
+
+            let agent = current.agent();
+            match current.skill {
+                StageSkill::Architect => {
+                    self.emit(
+                        SwarmEvent::new(SwarmEventKind::AgentStarted, agent)
+                            .with_unknown(&ambiguity.id),
+                    )
+                    .await;
+                    let config = self.get_model_config(agent).with_cancel(token.clone());
+                    let output = cancellable(
+                        &token,
+                        &what,
+                        ArchitectSkill::run(
+                            &ambiguity.id,
+                            &research_json,
+                            depends_on,
+                            feedback.as_ref(),
+                            "", // Would load spec here
+                            self.config.mode.as_str(),
+                            &config,
+                        ),
+                    )
+                    .await
+                    .context("Architect failed")?;
+
+                    self.emit(
+                        SwarmEvent::new(SwarmEventKind::AgentCompleted, agent)
+                            .with_unknown(&ambiguity.id),
+                    )
+                    .await;
+
+                    let approved = self
+                        .approve_stage(
+                            current,
+                            &ambiguity.id,
+                            attempt,
+                            format!("Approve: {}", output.chosen_option),
+                            output.rationale.clone(),
+                        )
+                        .await;
+                    decision = Some(output);
+                    if !approved {
+                        continue; // Decide again
+                    }
+                }
+                StageSkill::Review => {
+                    let Some(decided) = &decision else {
+                        anyhow::bail!("Stage '{}' has no decision to review", current.id);
+                    };
+                    let output = self
+                        .review_decision(current, &ambiguity.id, decided, &token)
+                        .await?;
+
+                    // An approval gate makes the user the last reviewer
+                    let approved = output.verdict == "approved"
+                        && self
+                            .approve_stage(
+                                current,
+                                &ambiguity.id,
+                                attempt,
+                                format!("Approve review: {}", output.summary),
+                                decided.rationale.clone(),
+                            )
+                            .await;
+                    verdict = Some(output);
+                    if !approved {
+                        feedback = verdict.clone();
+                        let count = rejections.entry(current.id.as_str()).or_default();
+                        *count += 1;
+                        let attempts = *count;
+                        match &current.on_reject {
+                            Some(on_reject) if attempts < on_reject.max_iterations => {
+                                // Loop back to the architect
+                                self.emit(
+                                    SwarmEvent::new(SwarmEventKind::CriticRejected, agent)
+                                        .with_unknown(&ambiguity.id),
+                                )
+                                .await;
+                                stage = graph.stage(&on_reject.goto);
                                 continue;
                             }
-                            _ => {}
+                            on_reject => {
+                                let max = on_reject.as_ref().map_or(1, |r| r.max_iterations);
+                                let verdict = verdict.take().unwrap_or_else(approved_verdict);
+                                return self
+                                    .reviews_exhausted(
+                                        current, ambiguity, decision, verdict, attempts, max,
+                                    )
+                                    .await;
+                            }
                         }
                     }
                 }
+                _ => anyhow::bail!("Stage '{}' cannot run for an unknown", current.id),
+            }
+            stage = graph.unknown_next(current, &context);
+        }
 
-                // Stage 4: Critic review
-                self.emit(
-                    SwarmEvent::new(SwarmEventKind::AgentStarted, "critic")
-                        .with_unknown(&ambiguity.id),
-                )
+        Ok((decision, verdict.unwrap_or_else(approved_verdict)))
+    }
+
+    /// Review a decision at a review stage
+    ///
+    /// Stages using the default critic are reviewed by the configured review
+    /// panel, when there is one: every critic reviews the decision at once and
+    /// the panel's quorum folds their reviews into the verdict.
+    async fn review_decision(
+        &mut self,
+        stage: &StageSpec,
+        unknown_id: &str,
+        decision: &ArchitectOutput,
+        token: &CancellationToken,
+    ) -> Result<CriticOutput> {
+        let what = format!("Unknown {}", unknown_id);
+        let decision_json = serde_json::to_string_pretty(decision)?;
+        let focus = stage
+            .focus
+            .as_deref()
+            .map(|focus| format!("Review focus: {}", focus))
+            .unwrap_or_default();
+        let mode = self.config.mode.as_str();
+
+        let panel = self
+            .config
+            .review_panel
+            .clone()
+            .filter(|panel| stage.agent.is_none() && !panel.critics.is_empty());
+        let Some(panel) = panel else {
+            let agent = stage.agent();
+            self.emit(
+                SwarmEvent::new(SwarmEventKind::AgentStarted, agent).with_unknown(unknown_id),
+            )
+            .await;
+            let config = self.get_model_config(agent).with_cancel(token.clone());
+            let output = cancellable(
+                token,
+                &what,
+                CriticSkill::run(&decision_json, &focus, mode, &config),
+            )
+            .await
+            .context("Critic failed")?;
+            self.emit(
+                SwarmEvent::new(SwarmEventKind::AgentCompleted, agent).with_unknown(unknown_id),
+            )
+            .await;
+            return Ok(output);
+        };
+
+        let mut tasks = JoinSet::new();
+        for (index, critic) in panel.critics.iter().enumerate() {
+            self.emit(
+                SwarmEvent::new(SwarmEventKind::AgentStarted, &critic.id).with_unknown(unknown_id),
+            )
+            .await;
+            let config = self.get_model_config(&critic.id).with_cancel(token.clone());
+            let spec_context = match &critic.persona {
+                Some(persona) => format!("{}\nReview persona: {}", focus, persona),
+                None => focus.clone(),
+            };
+            let decision_json = decision_json.clone();
+            tasks.spawn(async move {
+                let review = CriticSkill::run(&decision_json, &spec_context, mode, &config).await;
+                (index, review)
+            });
+        }
+        let mut finished = cancellable(token, &what, async move {
+            let mut finished = Vec::new();
+            while let Some(joined) = tasks.join_next().await {
+                finished.push(joined?);
+            }
+            Ok(finished)
+        })
+        .await?;
+        finished.sort_by_key(|(index, _)| *index);
+
+        let mut reviews = Vec::new();
+        for ((_, review), critic) in finished.into_iter().zip(&panel.critics) {
+            let review = review.with_context(|| format!("Critic {} failed", critic.id))?;
+            self.emit(
+                SwarmEvent::new(SwarmEventKind::AgentCompleted, &critic.id)
+                    .with_unknown(unknown_id)
+                    .with_data(serde_json::json!({
+                        "verdict": review.verdict,
+                        "confidence": review.confidence,
+                    })),
+            )
+            .await;
+            reviews.push((critic.id.clone(), review));
+        }
+
+        let verdict = consensus::aggregate(panel.quorum, &reviews);
+        self.emit(
+            SwarmEvent::new(SwarmEventKind::ReviewConsensus, "coordinator")
+                .with_unknown(unknown_id)
+                .with_data(serde_json::json!({
+                    "quorum": panel.quorum,
+                    "verdict": verdict.verdict,
+                    "votes": reviews
+                        .iter()
+                        .map(|(id, review)| serde_json::json!({"critic": id, "verdict": review.verdict}))
+                        .collect::<Vec<_>>(),
+                })),
+        )
+        .await;
+        Ok(verdict)
+    }
+
+    /// Give up on an unknown whose review rejected it too often
+    ///
+    /// With `require_critic_approval` the user may still accept the decision.
+    async fn reviews_exhausted(
+        &mut self,
+        stage: &StageSpec,
+        ambiguity: &Ambiguity,
+        decision: Option<ArchitectOutput>,
+        verdict: CriticOutput,
+        attempts: u32,
+        max: u32,
+    ) -> Result<(Option<ArchitectOutput>, CriticOutput)> {
+        self.emit(
+            SwarmEvent::new(SwarmEventKind::CriticRejected, stage.agent())
+                .with_unknown(&ambiguity.id)
+                .with_data(serde_json::json!({"attempts": attempts, "max": max})),
+        )
+        .await;
+
+        // Request human approval if configured
+        if self.config.require_critic_approval {
+            let approval = self
+                .request_approval(ApprovalRequest {
+                    decision_id: format!("{}-{}-{}", stage.id, ambiguity.id, attempts),
+                    agent_id: stage.agent().to_string(),
+                    summary: format!("Critic rejected {} times. Override and approve?", attempts),
+                })
                 .await;
 
-                let decision_json = serde_json::to_string_pretty(&decision)?;
-                let verdict = CriticSkill::run(
-                    &decision_json,
-                    "",
-                    &self.config.mode,
-                    &self.get_model_config("critic"),
-                )
-                .await
-                .context("Critic failed")?;
+            if let Some(resp) = approval {
+                if resp.approved {
+                    // User overrode, accept decision
+                    return Ok((decision, verdict));
+                }
+            }
+        }
 
-                self.emit(
-                    SwarmEvent::new(SwarmEventKind::AgentCompleted, "critic")
-                        .with_unknown(&ambiguity.id),
-                )
+        Ok((None, verdict))
+    }
+
+    /// Ask the user to accept a stage's output when the stage has an approval gate
+    ///
+    /// Uses the inbox when it is connected, otherwise the approval channel.
+    /// Returns whether the output was accepted; a rejected stage runs again.
+    async fn approve_stage(
+        &mut self,
+        stage: &StageSpec,
+        thread_id: &str,
+        attempt: u32,
+        title: String,
+        description: String,
+    ) -> bool {
+        use crate::state::{Interaction, InteractionKind, InteractionStatus};
+        use chrono::Utc;
+
+        if !stage.approval {
+            return true;
+        }
+
+        if self.resolved_rx.is_some() {
+            let interaction = Interaction {
+                id: format!("int-{}-{}-{}", stage.id, thread_id, attempt),
+                thread_id: thread_id.to_string(),
+                kind: InteractionKind::Decision,
+                status: InteractionStatus::Pending,
+                from_agent: stage.agent().to_string(),
+                title,
+                description,
+                options: vec![
+                    "Approve".to_string(),
+                    "Reject".to_string(),
+                    "Modify".to_string(),
+                ],
+                schema: None,
+                created_at: Utc::now(),
+                resolved_at: None,
+                response: None,
+            };
+            match self.ask_user(interaction).await {
+                // Approve or Modify both accept the output
+                Ok(response) => response.selected_option.as_deref() != Some("Reject"),
+                Err(e) => {
+                    tracing::warn!("Inbox interaction failed: {}, continuing...", e);
+                    true
+                }
+            }
+        } else {
+            // Fall back to old approval channel
+            let approval = self
+                .request_approval(ApprovalRequest {
+                    decision_id: format!("{}-{}-{}", stage.id, thread_id, attempt),
+                    agent_id: stage.agent().to_string(),
+                    summary: format!("Approve {} output for {}?", stage.id, thread_id),
+                })
+                .await;
+            !matches!(approval, Some(resp) if !resp.approved)
+        }
+    }
+
+    /// Run the stages that follow the unknowns: atomize the approved
+    /// decisions and generate the feature's mission
+    ///
+    /// Outputs are stored on the feature, which moves to `Planned` once it
+    /// has a mission.
+    async fn plan_feature(
+        &mut self,
+        goal: &str,
+        decisions: &[ArchitectOutput],
+    ) -> Result<(String, Option<AtomizerOutput>, Option<MissionPrompt>)> {
+        use crate::state::PipelineStage as FeatureStage;
+
+        let fm = FeatureManager::new(&self.db);
+        let feature_id = match &self.feature_id {
+            Some(id) => id.clone(),
+            None => {
+                let mut feature = fm.create(goal)?;
+                feature.description = Some(goal.to_string());
+                fm.save(&feature)?;
+                self.feature_id = Some(feature.id.clone());
+                if let Err(e) = RunManager::new(&self.db).set_feature(&self.run_id, &feature.id) {
+                    tracing::warn!("Failed to link run to feature: {}", e);
+                }
+                feature.id
+            }
+        };
+        let decisions_json = serde_json::to_string_pretty(decisions)?;
+
+        let graph = self.pipeline.graph().clone();
+        let context = StageContext {
+            mode: self.config.mode,
+            ambiguity: None,
+        };
+        let mut plan: Option<AtomizerOutput> = None;
+        let mut mission: Option<MissionPrompt> = None;
+        let mut stage = graph.after_unknowns();
+        let mut attempt = 0;
+
+        while let Some(current) = stage {
+            attempt += 1;
+            self.pipeline.enter(current);
+            self.pause_point(&self.control.token(), "Run").await?;
+            self.enforce_budget().await?;
+
+            let agent = current.agent();
+            self.emit(SwarmEvent::new(SwarmEventKind::AgentStarted, agent))
                 .await;
+            let config = self.get_model_config(agent).with_feature(&feature_id);
+
+            let approved = match current.skill {
+                StageSkill::Atomize => {
+                    let output = AtomizerSkill::run(goal, &decisions_json, &config)
+                        .await
+                        .context("Atomizer failed")?;
+                    fm.set_plan(&feature_id, &output)?;
 
-                if verdict.verdict == "approved" {
-                    decisions.push(decision);
-                    verdicts.push(verdict);
-                    break;
-                } else if attempts >= max_attempts {
-                    // Max rejections reached
                     self.emit(
-                        SwarmEvent::new(SwarmEventKind::CriticRejected, "critic")
-                            .with_unknown(&ambiguity.id)
-                            .with_data(
-                                serde_json::json!({"attempts": attempts, "max": max_attempts}),
-                            ),
+                        SwarmEvent::new(SwarmEventKind::AgentCompleted, agent).with_data(
+                            serde_json::json!({"feature_id": feature_id, "modules": output.modules.len()}),
+                        ),
                     )
                     .await;
 
-                    // Request human approval if configured
-                    if self.config.require_critic_approval {
-                        let approval = self
-                            .request_approval(ApprovalRequest {
-                                decision_id: format!("crit-{}-{}", ambiguity.id, attempts),
-                                agent_id: "critic".to_string(),
-                                summary: format!(
-                                    "Critic rejected {} times. Override and approve?",
-                                    attempts
-                                ),
-                            })
-                            .await;
-
-                        match approval {
-                            Some(resp) if resp.approved => {
-                                // User overrode, accept decision
-                                decisions.push(decision);
-                            }
-                            _ => {}
-                        }
-                    }
+                    let title = format!("Approve plan: {} modules", output.modules.len());
+                    let description = output.feature_name.clone();
+                    plan = Some(output);
+                    self.approve_stage(current, &feature_id, attempt, title, description)
+                        .await
+                }
+                StageSkill::Mission => {
+                    let Some(plan) = &plan else {
+                        anyhow::bail!("Stage '{}' has no plan to turn into a mission", current.id);
+                    };
+                    let plan_json = serde_json::to_string_pretty(plan)?;
+                    let mut output =
+                        TaskmasterSkill::run(goal, &decisions_json, &plan_json, &config)
+                            .await
+                            .context("Taskmaster failed")?;
+                    output.fill_drafting_missions(plan);
+                    fm.set_mission(&feature_id, &output)?;
 
-                    verdicts.push(verdict);
-                    break;
-                } else {
-                    // Loop back to architect
                     self.emit(
-                        SwarmEvent::new(SwarmEventKind::CriticRejected, "critic")
-                            .with_unknown(&ambiguity.id),
+                        SwarmEvent::new(SwarmEventKind::AgentCompleted, agent).with_data(
+                            serde_json::json!({
+                                "feature_id": feature_id,
+                                "tasks": output.tasks.len(),
+                                "drafting_missions": output.drafting_missions.len(),
+                            }),
+                        ),
                     )
                     .await;
+
+                    let title = format!("Approve mission: {} tasks", output.tasks.len());
+                    let description = output.objective.clone();
+                    mission = Some(output);
+                    self.approve_stage(current, &feature_id, attempt, title, description)
+                        .await
                 }
+                _ => anyhow::bail!("Stage '{}' cannot run after the unknowns", current.id),
+            };
+            if approved {
+                stage = graph.next(current, &context);
             }
+        }
 
-            research_results.push(research);
+        if mission.is_some() {
+            fm.update_stage(&feature_id, FeatureStage::Planned)?;
         }
+        Ok((feature_id, plan, mission))
+    }
 
-        let success = verdicts.iter().all(|v| v.verdict == "approved");
+    /// Run the swarm on a goal and carry the plan through to merged code
+    ///
+    /// Plans the feature with [`run`](Self::run), drafts its files in a new
+    /// worktree, has the Builder repair them until `cargo check` and
+    /// `cargo test` pass, then merges the feature branch. When the mode asks
+    /// for red-team tests they are written before drafting and must pass,
+    /// unmodified, before the merge gate. Each step moves the
+    /// feature to its next stage and emits `FeatureStageChanged`.
+    ///
+    /// Cancelling the feature (or aborting the run) removes its worktree and
+    /// marks it failed; an aborted run then returns a [`Cancelled`] error.
+    pub async fn execute(&mut self, goal: &str) -> Result<ExecutionResult> {
+        let swarm = self.run(goal).await?;
+        let mut result = ExecutionResult {
+            swarm,
+            files_drafted: Vec::new(),
+            build: None,
+            merged: false,
+            conflicts: Vec::new(),
+            error: None,
+        };
 
-        self.emit(SwarmEvent::new(
-            if success {
-                SwarmEventKind::PipelineCompleted
-            } else {
-                SwarmEventKind::PipelineFailed
-            },
-            "coordinator",
-        ))
+        let (Some(feature_id), Some(mission)) = (
+            result.swarm.feature_id.clone(),
+            result.swarm.mission.clone(),
+        ) else {
+            result.error = Some("Planning did not produce a mission".to_string());
+            return Ok(result);
+        };
+
+        let token = self.control.feature_token(&feature_id);
+        let execution = cancellable(
+            &token,
+            &format!("Feature {}", feature_id),
+            self.execute_mission(&feature_id, &mission, &mut result),
+        )
         .await;
+        if let Err(e) = execution {
+            use crate::state::PipelineStage as FeatureStage;
 
-        // Update project state
-        project_state.active_agent = None;
-        project_state.phase = if success { "execution_ready" } else { "failed" }.to_string();
-        let _ = project_state.save(&self.db);
+            if token.is_cancelled() {
+                tear_down_feature(&self.db, &feature_id);
+                self.emit_feature_stage(&feature_id, FeatureStage::Failed)
+                    .await;
+                if self.control.is_cancelled() {
+                    self.record_cancelled().await;
+                    return Err(Cancelled("Run".to_string()).into());
+                }
+                result.error = Some(Cancelled(format!("Feature {}", feature_id)).to_string());
+                return Ok(result);
+            }
 
-        Ok(SwarmResult {
-            unknowns,
-            research: research_results,
-            decisions,
-            verdicts,
-            events: self.events.clone(),
-            success,
-        })
+            let _ = FeatureManager::new(&self.db).set_failed(&feature_id, &e.to_string());
+            self.emit_feature_stage(&feature_id, FeatureStage::Failed)
+                .await;
+            result.error = Some(e.to_string());
+        }
+
+        Ok(result)
+    }
+
+    /// Worktree, drafting, build verification and merge for a planned feature
+    async fn execute_mission(
+        &mut self,
+        feature_id: &str,
+        mission: &MissionPrompt,
+        result: &mut ExecutionResult,
+    ) -> Result<()> {
+        use crate::state::PipelineStage as FeatureStage;
+        use crate::tools::git;
+
+        let fm = FeatureManager::new(&self.db);
+        let project_root = std::env::current_dir()?;
+
+        self.set_feature_stage(feature_id, FeatureStage::Building)
+            .await?;
+        let worktree_path = git::create_worktree(&project_root, feature_id)?;
+        fm.set_worktree(feature_id, worktree_path.clone())?;
+
+        let policy = self.policy();
+        let red_team = if policy.red_team_tests {
+            self.enforce_budget().await?;
+            self.emit(
+                SwarmEvent::new(SwarmEventKind::AgentStarted, "red_team")
+                    .with_data(serde_json::json!({"feature_id": feature_id})),
+            )
+            .await;
+            let config = self
+                .get_model_config("red_team")
+                .with_feature(feature_id)
+                .with_cancel(self.control.feature_token(feature_id));
+            let suite =
+                write_red_team_tests(&fm.load(feature_id)?, &worktree_path, policy, &config)
+                    .await?;
+            self.emit(
+                SwarmEvent::new(SwarmEventKind::AgentCompleted, "red_team").with_data(
+                    serde_json::json!({
+                        "feature_id": feature_id,
+                        "tests": suite.tests.iter().map(|t| &t.path).collect::<Vec<_>>(),
+                    }),
+                ),
+            )
+            .await;
+            Some(suite)
+        } else {
+            None
+        };
+
+        let drafts = self
+            .execute_drafting_phase(mission.drafting_missions.clone(), &worktree_path)
+            .await?;
+        result.files_drafted = drafts.into_iter().map(|d| d.file_path).collect();
+
+        self.set_feature_stage(feature_id, FeatureStage::Testing)
+            .await?;
+        let build = self
+            .build_until_green(feature_id, mission, &worktree_path, red_team.as_ref())
+            .await?;
+        let passed = build.build_passed && build.tests_passed;
+        let summary = build.summary.clone();
+        result.build = Some(build);
+        if !passed {
+            anyhow::bail!("Builder could not get check and tests passing: {}", summary);
+        }
+
+        commit_feature(&worktree_path, feature_id, &mission.feature_name)?;
+
+        match self.merge_feature(&project_root, feature_id).await? {
+            git::MergeResult::Success => result.merged = true,
+            git::MergeResult::Conflicts(files) => {
+                result.error = Some(merge_conflict_error(&files));
+                result.conflicts = files;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Merge a feature's committed branch and record where the feature ended up
+    ///
+    /// A conflicted merge fails the feature, naming the conflicting files in
+    /// its error and in the stage change event.
+    async fn merge_feature(
+        &mut self,
+        project_root: &std::path::Path,
+        feature_id: &str,
+    ) -> Result<crate::tools::git::MergeResult> {
+        use crate::state::PipelineStage as FeatureStage;
+        use crate::tools::git;
+
+        self.set_feature_stage(feature_id, FeatureStage::Merging)
+            .await?;
+        let merge = git::merge_worktree(project_root, feature_id)?;
+        match &merge {
+            git::MergeResult::Success => {
+                self.set_feature_stage(feature_id, FeatureStage::Complete)
+                    .await?;
+                let _ = git::delete_worktree(project_root, feature_id);
+            }
+            git::MergeResult::Conflicts(files) => {
+                FeatureManager::new(&self.db)
+                    .set_failed(feature_id, &merge_conflict_error(files))?;
+                self.emit(
+                    SwarmEvent::new(SwarmEventKind::FeatureStageChanged, "coordinator").with_data(
+                        serde_json::json!({
+                            "feature_id": feature_id,
+                            "stage": FeatureStage::Failed,
+                            "conflicts": files,
+                        }),
+                    ),
+                )
+                .await;
+            }
+        }
+        Ok(merge)
+    }
+
+    /// Run the Builder until `cargo check` and `cargo test` pass in the worktree
+    ///
+    /// Both come from the Builder's own runs in the worktree. Each retry is
+    /// told which errors or test failures the previous run left behind.
+    /// A red-team suite is checked before the merge gate, as part of it.
+    async fn build_until_green(
+        &mut self,
+        feature_id: &str,
+        mission: &MissionPrompt,
+        worktree_path: &std::path::Path,
+        red_team: Option<&RedTeamOutput>,
+    ) -> Result<BuilderOutput> {
+        let max_attempts = self
+            .config
+            .max_build_attempts
+            .unwrap_or(DEFAULT_BUILD_ATTEMPTS)
+            .max(1);
+        let fix_iterations = self.fix_iterations();
+        let policy = self.policy();
+        let token = self.control.feature_token(feature_id);
+        let what = format!("Feature {}", feature_id);
+        let config = self
+            .get_model_config("builder")
+            .with_feature(feature_id)
+            .with_cancel(token.clone());
+        let mut last_errors = Vec::new();
+        let mut attempt = 0;
+
+        loop {
+            attempt += 1;
+            self.pause_point(&token, &what).await?;
+            self.enforce_budget().await?;
+            self.emit(
+                SwarmEvent::new(SwarmEventKind::AgentStarted, "builder")
+                    .with_data(serde_json::json!({"feature_id": feature_id, "attempt": attempt})),
+            )
+            .await;
+
+            let mut prompt = builder_mission(mission, &last_errors, policy.forbid_unwrap);
+            if let Some(suite) = red_team {
+                prompt.push_str(&red_team_brief(suite));
+            }
+            let output = BuilderSkill::run(&prompt, worktree_path, fix_iterations, &config)
+                .await
+                .context("Builder failed")?;
+            FeatureManager::new(&self.db).set_build_result(
+                feature_id,
+                output.iterations,
+                &output.errors,
+            )?;
+
+            self.emit(
+                SwarmEvent::new(SwarmEventKind::AgentCompleted, "builder").with_data(
+                    serde_json::json!({
+                        "feature_id": feature_id,
+                        "attempt": attempt,
+                        "build_passed": output.build_passed,
+                        "tests_passed": output.tests_passed,
+                        "iterations": output.iterations,
+                        "errors": output.errors.len(),
+                    }),
+                ),
+            )
+            .await;
+
+            if output.build_passed && output.tests_passed {
+                let mut problems = match red_team {
+                    Some(suite) => {
+                        cancellable(&token, &what, red_team_problems(suite, worktree_path)).await?
+                    }
+                    None => Vec::new(),
+                };
+                if problems.is_empty() {
+                    problems = self
+                        .merge_gate(feature_id, mission, worktree_path, &token)
+                        .await?;
+                }
+                if problems.is_empty() {
+                    return Ok(output);
+                }
+                self.emit(
+                    SwarmEvent::new(SwarmEventKind::MergeGateFailed, "coordinator").with_data(
+                        serde_json::json!({
+                            "feature_id": feature_id,
+                            "attempt": attempt,
+                            "mode": policy.mode,
+                            "problems": problems,
+                        }),
+                    ),
+                )
+                .await;
+                if attempt >= max_attempts {
+                    anyhow::bail!(
+                        "Feature failed the {} merge gate: {}",
+                        policy.mode,
+                        problems.join("; ")
+                    );
+                }
+                last_errors = problems;
+                continue;
+            }
+            if attempt >= max_attempts {
+                return Ok(output);
+            }
+            last_errors = output.errors;
+        }
+    }
+
+    /// Check a green build against the mode's merge gate
+    ///
+    /// Returns what has to be fixed before the feature may be merged.
+    async fn merge_gate(
+        &mut self,
+        feature_id: &str,
+        mission: &MissionPrompt,
+        worktree_path: &std::path::Path,
+        token: &CancellationToken,
+    ) -> Result<Vec<String>> {
+        let policy = self.policy();
+        if !policy.gates_merge() {
+            return Ok(Vec::new());
+        }
+        let what = format!("Feature {}", feature_id);
+        self.pause_point(token, &what).await?;
+        self.enforce_budget().await?;
+        if policy.critic_reviews_diffs {
+            self.emit(
+                SwarmEvent::new(SwarmEventKind::AgentStarted, "critic")
+                    .with_data(serde_json::json!({"feature_id": feature_id})),
+            )
+            .await;
+        }
+
+        let constraints = ConstraintSkill::with_limits(
+            mission.constraints.max_file_lines as usize,
+            mission.constraints.max_function_lines as usize,
+        );
+        let critic = self
+            .get_model_config("critic")
+            .with_feature(feature_id)
+            .with_cancel(token.clone());
+        let problems = cancellable(
+            token,
+            &what,
+            merge_gate_problems(
+                policy,
+                constraints,
+                worktree_path,
+                &format!("{}\n\n{}", mission.feature_name, mission.objective),
+                &critic,
+            ),
+        )
+        .await?;
+
+        if policy.critic_reviews_diffs {
+            self.emit(
+                SwarmEvent::new(SwarmEventKind::AgentCompleted, "critic").with_data(
+                    serde_json::json!({"feature_id": feature_id, "problems": problems.len()}),
+                ),
+            )
+            .await;
+        }
+        Ok(problems)
+    }
+
+    /// Move a feature to `stage` and announce it
+    async fn set_feature_stage(
+        &mut self,
+        feature_id: &str,
+        stage: crate::state::PipelineStage,
+    ) -> Result<()> {
+        FeatureManager::new(&self.db).update_stage(feature_id, stage.clone())?;
+        self.emit_feature_stage(feature_id, stage).await;
+        Ok(())
+    }
+
+    async fn emit_feature_stage(&mut self, feature_id: &str, stage: crate::state::PipelineStage) {
+        self.emit(
+            SwarmEvent::new(SwarmEventKind::FeatureStageChanged, "coordinator")
+                .with_data(serde_json::json!({"feature_id": feature_id, "stage": stage})),
+        )
+        .await;
     }
 
     /// Execute the Speed Demon drafting phase
@@ -728,6 +2221,8 @@ impl Coordinator {
         if total == 0 {
             return Ok(Vec::new());
         }
+        self.pause_point(&self.control.token(), "Run").await?;
+        self.enforce_budget().await?;
 
         // Emit start event
         self.emit(
@@ -822,154 +2317,715 @@ impl Coordinator {
 
     /// Run multiple features in parallel with concurrency control
     ///
-    /// Uses a Semaphore to limit concurrent feature processing.
+    /// Uses a Semaphore to limit concurrent feature processing. A feature
+    /// waits while the run is paused before it starts; cancelling it (or
+    /// aborting the run) stops its Builder and removes its worktree.
+    ///
+    /// A feature starts only once everything it `depends_on` is merged, so
+    /// its worktree branches from the updated main. Features in a dependency
+    /// cycle, or whose prerequisites failed or are outside the batch and not
+    /// complete, are moved to `Blocked` along with their dependents. Results
+    /// are in the order of `feature_ids`.
     pub async fn run_features_parallel(
         &self,
         feature_ids: Vec<String>,
     ) -> Result<Vec<FeatureResult>> {
-        use crate::state::{FeatureManager, PipelineStage};
+        use crate::state::PipelineStage;
         use crate::tools::git;
 
         let semaphore = Arc::new(Semaphore::new(self.config.max_concurrent_features));
         let builder_config = Arc::new(self.get_model_config("builder"));
+        let critic_config = self.get_model_config("critic");
+        let red_team_config = self.get_model_config("red_team");
+        let fix_iterations = self.fix_iterations();
+        let policy = self.policy();
         let event_tx = self.event_tx.clone();
         let db = Arc::clone(&self.db);
 
-        let mut handles = Vec::new();
+        let fm = FeatureManager::new(&db);
+        let mut schedule = FeatureSchedule::new(FeatureGraph::new(
+            &feature_ids
+                .iter()
+                .map(|id| {
+                    let depends_on = fm.load(id).map(|f| f.depends_on).unwrap_or_default();
+                    (id.clone(), depends_on)
+                })
+                .collect::<Vec<_>>(),
+        ));
+        let mut results: Vec<Option<FeatureResult>> = vec![None; feature_ids.len()];
+
+        // Prerequisites that can never be merged in this batch
+        let mut blocked = Vec::new();
+        for cycle in schedule.graph().cycles().to_vec() {
+            let path = cycle
+                .iter()
+                .chain(cycle.first())
+                .map(|&i| feature_ids[i].as_str())
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            let reason = format!("Dependency cycle: {}", path);
+            blocked.extend(cycle.iter().map(|&i| (i, reason.clone())));
+            let upstream = format!("Depends on dependency cycle {}", path);
+            blocked.extend(
+                schedule
+                    .block(&cycle)
+                    .into_iter()
+                    .map(|i| (i, upstream.clone())),
+            );
+        }
+        for index in 0..feature_ids.len() {
+            if schedule.status(index) != FeatureStatus::Pending {
+                continue;
+            }
+            let missing = schedule
+                .graph()
+                .external(index)
+                .iter()
+                .find(|dep| {
+                    !fm.load(dep)
+                        .is_ok_and(|f| f.stage == PipelineStage::Complete)
+                })
+                .cloned();
+            if let Some(dep) = missing {
+                let reason = format!("Depends on {}, which is not complete", dep);
+                blocked.push((index, reason.clone()));
+                blocked.extend(
+                    schedule
+                        .block(&[index])
+                        .into_iter()
+                        .map(|i| (i, reason.clone())),
+                );
+            }
+        }
+        self.report_blocked(&feature_ids, blocked, &mut results)
+            .await;
+
+        let mut tasks = JoinSet::new();
+        let mut running = HashMap::new();
+
+        loop {
+            for index in schedule.start_ready() {
+                let feature_id = feature_ids[index].clone();
+                let permit = semaphore.clone().acquire_owned().await?;
+
+                // Don't start new features once the run is over budget
+                if let Some(reason) = self.budget.exceeded(&self.usage.totals()) {
+                    let _ = FeatureManager::new(&db)
+                        .set_failed(&feature_id, &format!("Budget exceeded: {}", reason));
+                    let task = tasks.spawn(async move {
+                        FeatureResult {
+                            feature_id,
+                            success: false,
+                            error: Some(format!("Budget exceeded: {}", reason)),
+                        }
+                    });
+                    running.insert(task.id(), index);
+                    continue;
+                }
+
+                let token = self.control.feature_token(&feature_id);
+                let builder_config = Arc::new(
+                    builder_config
+                        .as_ref()
+                        .clone()
+                        .with_feature(&feature_id)
+                        .with_cancel(token.clone()),
+                );
+                let critic_config = critic_config
+                    .clone()
+                    .with_feature(&feature_id)
+                    .with_cancel(token.clone());
+                let red_team_config = red_team_config
+                    .clone()
+                    .with_feature(&feature_id)
+                    .with_cancel(token.clone());
+                let control = self.control.clone();
+                let event_tx = event_tx.clone();
+                let db = Arc::clone(&db);
+
+                let task = tasks.spawn(async move {
+                    let _permit = permit; // Hold permit until task completes
+                    let fm = FeatureManager::new(&db);
+
+                    if let Err(e) = control.wait_while_paused(&token).await {
+                        let _ = fm.set_failed(&feature_id, "Cancelled");
+                        return FeatureResult {
+                            feature_id,
+                            success: false,
+                            error: Some(e.to_string()),
+                        };
+                    }
 
-        for feature_id in feature_ids {
-            let permit = semaphore.clone().acquire_owned().await?;
-            let builder_config = builder_config.clone();
-            let event_tx = event_tx.clone();
-            let feature_id = feature_id.clone();
-            let db = Arc::clone(&db);
+                    // Load feature
+                    let feature = match fm.load(&feature_id) {
+                        Ok(f) => f,
+                        Err(_) => {
+                            return FeatureResult {
+                                feature_id,
+                                success: false,
+                                error: Some("Failed to load feature".to_string()),
+                            };
+                        }
+                    };
 
-            let handle = tokio::spawn(async move {
-                let _permit = permit; // Hold permit until task completes
-                let fm = FeatureManager::new(&db);
+                    // Update stage to Building
+                    let _ = fm.update_stage(&feature_id, PipelineStage::Building);
+
+                    // Create worktree for this feature
+                    let project_root = std::env::current_dir().unwrap_or_default();
+                    let worktree_path = match git::create_worktree(&project_root, &feature_id) {
+                        Ok(path) => path,
+                        Err(e) => {
+                            let _ = fm.set_failed(&feature_id, &e.to_string());
+                            return FeatureResult {
+                                feature_id,
+                                success: false,
+                                error: Some(e.to_string()),
+                            };
+                        }
+                    };
+                    let _ = fm.set_worktree(&feature_id, worktree_path.clone());
+
+                    let mut mission = feature
+                        .description
+                        .clone()
+                        .unwrap_or_else(|| format!("Implement feature: {}", feature.title));
+
+                    // Red Team writes the tests the Builder has to pass
+                    let red_team = if policy.red_team_tests {
+                        if let Some(tx) = &event_tx {
+                            let _ = tx
+                                .send(SwarmEvent::new(SwarmEventKind::AgentStarted, "red_team"))
+                                .await;
+                        }
+                        let suite = cancellable(
+                            &token,
+                            &format!("Feature {}", feature_id),
+                            write_red_team_tests(
+                                &feature,
+                                &worktree_path,
+                                policy,
+                                &red_team_config,
+                            ),
+                        )
+                        .await;
+                        if token.is_cancelled() {
+                            tear_down_feature(&db, &feature_id);
+                            return FeatureResult {
+                                feature_id: feature_id.clone(),
+                                success: false,
+                                error: Some(
+                                    Cancelled(format!("Feature {}", feature_id)).to_string(),
+                                ),
+                            };
+                        }
+                        match suite {
+                            Ok(suite) => {
+                                if let Some(tx) = &event_tx {
+                                    let _ = tx
+                                        .send(SwarmEvent::new(
+                                            SwarmEventKind::AgentCompleted,
+                                            "red_team",
+                                        ))
+                                        .await;
+                                }
+                                mission.push_str(&red_team_brief(&suite));
+                                Some(suite)
+                            }
+                            Err(e) => {
+                                let error = format!("Red Team failed: {:#}", e);
+                                let _ = fm.set_failed(&feature_id, &error);
+                                return FeatureResult {
+                                    feature_id,
+                                    success: false,
+                                    error: Some(error),
+                                };
+                            }
+                        }
+                    } else {
+                        None
+                    };
 
-                // Load feature
-                let feature = match fm.load(&feature_id) {
-                    Ok(f) => f,
-                    Err(_) => {
-                        return FeatureResult {
-                            feature_id,
-                            success: false,
-                            error: Some("Failed to load feature".to_string()),
-                        };
+                    // Emit event if channel available
+                    if let Some(tx) = &event_tx {
+                        let _ = tx
+                            .send(SwarmEvent::new(SwarmEventKind::AgentStarted, "builder"))
+                            .await;
                     }
-                };
 
-                // Update stage to Building
-                let _ = fm.update_stage(&feature_id, PipelineStage::Building);
+                    // Run Builder agent in worktree
+
+                    let builder_result = cancellable(
+                        &token,
+                        &format!("Feature {}", feature_id),
+                        BuilderSkill::run(
+                            &mission,
+                            &worktree_path,
+                            fix_iterations,
+                            builder_config.as_ref(),
+                        ),
+                    )
+                    .await;
 
-                // Create worktree for this feature
-                let project_root = std::env::current_dir().unwrap_or_default();
-                let worktree_path = match git::create_worktree(&project_root, &feature_id) {
-                    Ok(path) => path,
-                    Err(e) => {
-                        let _ = fm.set_failed(&feature_id, &e.to_string());
+                    if token.is_cancelled() {
+                        tear_down_feature(&db, &feature_id);
                         return FeatureResult {
-                            feature_id,
+                            feature_id: feature_id.clone(),
                             success: false,
-                            error: Some(e.to_string()),
+                            error: Some(Cancelled(format!("Feature {}", feature_id)).to_string()),
                         };
                     }
-                };
-                let _ = fm.set_worktree(&feature_id, worktree_path.clone());
-
-                // Emit event if channel available
-                if let Some(tx) = &event_tx {
-                    let _ = tx
-                        .send(SwarmEvent::new(SwarmEventKind::AgentStarted, "builder"))
-                        .await;
-                }
 
-                // Run Builder agent in worktree
-                let mission = feature
-                    .description
-                    .clone()
-                    .unwrap_or_else(|| format!("Implement feature: {}", feature.title));
+                    if let Ok(output) = &builder_result {
+                        let _ = fm.set_build_result(&feature_id, output.iterations, &output.errors);
+                    }
 
-                let builder_result =
-                    BuilderSkill::run(&mission, &worktree_path, builder_config.as_ref()).await;
+                    match builder_result {
+                        Ok(output) => {
+                            if let Some(error) = build_failure(&output) {
+                                // Check or tests still failing after iterations
+                                let _ = fm.set_failed(&feature_id, &error);
+                                return FeatureResult {
+                                    feature_id,
+                                    success: false,
+                                    error: Some(error),
+                                };
+                            }
+                            let _ = fm.update_stage(&feature_id, PipelineStage::Testing);
+                        }
+                        Err(e) => {
+                            let _ = fm.set_failed(&feature_id, &e.to_string());
+                            return FeatureResult {
+                                feature_id,
+                                success: false,
+                                error: Some(e.to_string()),
+                            };
+                        }
+                    }
 
-                match builder_result {
-                    Ok(output) if output.success => {
-                        let _ = fm.update_stage(&feature_id, PipelineStage::Testing);
+                    // Red-team tests must pass as written
+                    if let Some(suite) = &red_team {
+                        let problems = red_team_problems(suite, &worktree_path).await;
+                        let error = match problems {
+                            Ok(problems) if problems.is_empty() => None,
+                            Ok(problems) => Some(format!(
+                                "Feature failed its red-team tests: {}",
+                                problems.join("; ")
+                            )),
+                            Err(e) => Some(e.to_string()),
+                        };
+                        if let Some(error) = error {
+                            let _ = fm.set_failed(&feature_id, &error);
+                            return FeatureResult {
+                                feature_id,
+                                success: false,
+                                error: Some(error),
+                            };
+                        }
                     }
-                    Ok(output) => {
-                        // Build failed after iterations
-                        let _ = fm.set_failed(&feature_id, &output.summary);
+
+                    // Mode merge gate (constraints, critic review of the diff)
+                    let gate = cancellable(
+                        &token,
+                        &format!("Feature {}", feature_id),
+                        merge_gate_problems(
+                            policy,
+                            ConstraintSkill::default(),
+                            &worktree_path,
+                            &mission,
+                            &critic_config,
+                        ),
+                    )
+                    .await;
+                    if token.is_cancelled() {
+                        tear_down_feature(&db, &feature_id);
                         return FeatureResult {
-                            feature_id,
+                            feature_id: feature_id.clone(),
                             success: false,
-                            error: Some(output.summary),
+                            error: Some(Cancelled(format!("Feature {}", feature_id)).to_string()),
                         };
                     }
-                    Err(e) => {
-                        let _ = fm.set_failed(&feature_id, &e.to_string());
+                    let gate_error = match gate {
+                        Ok(problems) if problems.is_empty() => None,
+                        Ok(problems) => Some(format!(
+                            "Feature failed the {} merge gate: {}",
+                            policy.mode,
+                            problems.join("; ")
+                        )),
+                        Err(e) => Some(e.to_string()),
+                    };
+                    if let Some(error) = gate_error {
+                        let _ = fm.set_failed(&feature_id, &error);
                         return FeatureResult {
                             feature_id,
                             success: false,
-                            error: Some(e.to_string()),
+                            error: Some(error),
                         };
                     }
-                }
 
-                // TODO: Run tests (RedTeam agent)
+                    // Merge back
+                    let _ = fm.update_stage(&feature_id, PipelineStage::Merging);
+                    let merge_result = commit_feature(&worktree_path, &feature_id, &feature.title)
+                        .and_then(|()| git::merge_worktree(&project_root, &feature_id));
 
-                // Merge back
-                let merge_result = git::merge_worktree(&project_root, &feature_id);
+                    match merge_result {
+                        Ok(git::MergeResult::Success) => {
+                            let _ = fm.update_stage(&feature_id, PipelineStage::Complete);
+                            let _ = git::delete_worktree(&project_root, &feature_id);
 
-                match merge_result {
-                    Ok(git::MergeResult::Success) => {
-                        let _ = fm.update_stage(&feature_id, PipelineStage::Complete);
-                        let _ = git::delete_worktree(&project_root, &feature_id);
-
-                        FeatureResult {
-                            feature_id,
-                            success: true,
-                            error: None,
+                            FeatureResult {
+                                feature_id,
+                                success: true,
+                                error: None,
+                            }
                         }
-                    }
-                    Ok(git::MergeResult::Conflicts(files)) => {
-                        let _ = fm.update_stage(&feature_id, PipelineStage::Merging);
-                        FeatureResult {
-                            feature_id,
-                            success: false,
-                            error: Some(format!("Merge conflicts in: {:?}", files)),
+                        Ok(git::MergeResult::Conflicts(files)) => {
+                            let _ = fm.update_stage(&feature_id, PipelineStage::Merging);
+                            FeatureResult {
+                                feature_id,
+                                success: false,
+                                error: Some(format!("Merge conflicts in: {:?}", files)),
+                            }
+                        }
+                        Err(e) => {
+                            let _ = fm.set_failed(&feature_id, &e.to_string());
+                            FeatureResult {
+                                feature_id,
+                                success: false,
+                                error: Some(e.to_string()),
+                            }
                         }
                     }
-                    Err(e) => {
-                        let _ = fm.set_failed(&feature_id, &e.to_string());
+                });
+                running.insert(task.id(), index);
+            }
+
+            // Wait for a feature to finish; its dependents may start next
+            let Some(joined) = tasks.join_next_with_id().await else {
+                break;
+            };
+            let (index, result) = match joined {
+                Ok((id, result)) => match running.remove(&id) {
+                    Some(index) => (index, result),
+                    None => continue,
+                },
+                Err(e) => match running.remove(&e.id()) {
+                    Some(index) => (
+                        index,
                         FeatureResult {
-                            feature_id,
+                            feature_id: feature_ids[index].clone(),
                             success: false,
                             error: Some(e.to_string()),
-                        }
-                    }
-                }
-            });
+                        },
+                    ),
+                    None => continue,
+                },
+            };
+
+            let reason = format!("Prerequisite {} failed", feature_ids[index]);
+            let blocked = schedule
+                .finish(index, result.success)
+                .into_iter()
+                .map(|i| (i, reason.clone()))
+                .collect();
+            results[index] = Some(result);
+            self.report_blocked(&feature_ids, blocked, &mut results)
+                .await;
+        }
+
+        Ok(results.into_iter().flatten().collect())
+    }
 
-            handles.push(handle);
+    /// Move features to `Blocked` and record why as their results
+    async fn report_blocked(
+        &self,
+        feature_ids: &[String],
+        blocked: Vec<(usize, String)>,
+        results: &mut [Option<FeatureResult>],
+    ) {
+        let fm = FeatureManager::new(&self.db);
+        for (index, reason) in blocked {
+            let feature_id = &feature_ids[index];
+            let _ = fm.set_blocked(feature_id, &reason);
+            if let Some(tx) = &self.event_tx {
+                let _ = tx
+                    .send(
+                        SwarmEvent::new(SwarmEventKind::FeatureStageChanged, "coordinator")
+                            .with_data(serde_json::json!({
+                                "feature_id": feature_id,
+                                "stage": crate::state::PipelineStage::Blocked,
+                                "reason": reason,
+                            })),
+                    )
+                    .await;
+            }
+            results[index] = Some(FeatureResult {
+                feature_id: feature_id.clone(),
+                success: false,
+                error: Some(format!("Blocked: {}", reason)),
+            });
         }
+    }
+}
 
-        // Await all feature tasks
-        let mut results = Vec::new();
-        for handle in handles {
-            match handle.await {
-                Ok(result) => results.push(result),
-                Err(e) => results.push(FeatureResult {
-                    feature_id: "unknown".to_string(),
-                    success: false,
-                    error: Some(e.to_string()),
+/// What keeps a built feature out of main under `policy`
+///
+/// Changed files are checked with `constraints` (blocking on `unwrap()` when
+/// the policy forbids it); once they pass, the Critic reviews the diff if the
+/// policy asks for it. Empty when the feature may be merged.
+async fn merge_gate_problems(
+    policy: ModePolicy,
+    constraints: ConstraintSkill,
+    worktree_path: &std::path::Path,
+    mission: &str,
+    critic: &ModelConfig,
+) -> Result<Vec<String>> {
+    if !policy.gates_merge() {
+        return Ok(Vec::new());
+    }
+    let (files, diff) = crate::tools::git::staged_changes(worktree_path)?;
+
+    let mut problems = Vec::new();
+    if policy.enforce_constraints {
+        let constraints = constraints.with_forbid_unwrap(policy.forbid_unwrap);
+        problems.extend(
+            constraints
+                .check_files(worktree_path, &files)?
+                .into_iter()
+                .map(|v| match v.line {
+                    Some(line) => format!("{}:{}: {}", v.file, line, v.message),
+                    None => format!("{}: {}", v.file, v.message),
                 }),
+        );
+    }
+
+    if policy.critic_reviews_diffs && problems.is_empty() {
+        let verdict = CriticSkill::review_diff(&diff, mission, policy.mode.as_str(), critic)
+            .await
+            .context("Critic failed")?;
+        if verdict.verdict != "approved" {
+            problems.push(format!("Critic ({}): {}", verdict.verdict, verdict.summary));
+            problems.extend(
+                verdict
+                    .concerns
+                    .iter()
+                    .filter(|c| c.severity == "blocking" || c.severity == "major")
+                    .map(|c| match &c.suggested_fix {
+                        Some(fix) => format!("{} (fix: {})", c.description, fix),
+                        None => c.description.clone(),
+                    }),
+            );
+        }
+    }
+    Ok(problems)
+}
+
+/// Verdict of an unknown whose path has no review stage
+fn approved_verdict() -> CriticOutput {
+    CriticOutput {
+        verdict: "approved".to_string(),
+        summary: "No review stage for this unknown".to_string(),
+        concerns: Vec::new(),
+        confidence: 1.0,
+    }
+}
+
+/// Have the Red Team write a feature's test suite into its worktree
+async fn write_red_team_tests(
+    feature: &crate::state::Feature,
+    worktree_path: &std::path::Path,
+    policy: ModePolicy,
+    config: &ModelConfig,
+) -> Result<RedTeamOutput> {
+    let mission = feature
+        .mission
+        .as_ref()
+        .context("Red-team tests need the feature's mission (plan the feature first)")?;
+    let suite = RedTeamSkill::run(mission, feature.plan.as_ref(), policy.mode.as_str(), config)
+        .await
+        .context("Red Team failed")?;
+    suite.write_to(worktree_path)?;
+    Ok(suite)
+}
+
+/// Tells the Builder which red-team tests it has to pass
+fn red_team_brief(suite: &RedTeamOutput) -> String {
+    let mut brief = "\n\nRed-team tests to pass (do not modify them):\n".to_string();
+    for test in &suite.tests {
+        brief.push_str(&format!("- {}\n", test.path));
+    }
+    brief
+}
+
+/// Why a built feature does not pass its red-team suite
+///
+/// The suite's files must be exactly as the Red Team wrote them, and
+/// `cargo test --test <file>` must run at least one test of each file with
+/// none failing. Empty when it passes.
+async fn red_team_problems(
+    suite: &RedTeamOutput,
+    worktree_path: &std::path::Path,
+) -> Result<Vec<String>> {
+    let modified = suite.modified_in(worktree_path);
+    if !modified.is_empty() {
+        return Ok(modified
+            .into_iter()
+            .map(|path| format!("{} was modified by the Builder", path))
+            .collect());
+    }
+
+    let mut problems = Vec::new();
+    for test in &suite.tests {
+        let summary =
+            crate::tools::terminal::run_cargo_test_target(worktree_path, test.target()).await?;
+        let failures = summary.failures();
+        if failures.is_empty() && summary.passed == 0 {
+            problems.push(format!("{}: no tests ran", test.path));
+        }
+        problems.extend(
+            failures
+                .into_iter()
+                .map(|failure| format!("{}: {}", test.path, failure)),
+        );
+    }
+    Ok(problems)
+}
+
+/// Why a Builder run must not be merged: the check or the tests still fail
+fn build_failure(output: &BuilderOutput) -> Option<String> {
+    if output.success && output.build_passed && output.tests_passed {
+        return None;
+    }
+    if output.errors.is_empty() {
+        return Some(output.summary.clone());
+    }
+    Some(format!("{}: {}", output.summary, output.errors.join("; ")))
+}
+
+fn merge_conflict_error(files: &[String]) -> String {
+    format!("Merge conflicts in: {}", files.join(", "))
+}
+
+/// Commit a built feature's worktree to its branch, ready to merge
+///
+/// A worktree without changes is an error: merging the branch would mark
+/// the feature complete without anything reaching the main branch.
+fn commit_feature(worktree_path: &std::path::Path, feature_id: &str, title: &str) -> Result<()> {
+    let message = format!("feat({}): {}", feature_id, title);
+    if !crate::tools::git::commit_worktree(worktree_path, &message)? {
+        anyhow::bail!("Feature {} left nothing to commit", feature_id);
+    }
+    Ok(())
+}
+
+/// Remove a cancelled feature's worktree and branch, and mark it failed
+fn tear_down_feature(db: &CatalystDb, feature_id: &str) {
+    let fm = FeatureManager::new(db);
+    match std::env::current_dir() {
+        Ok(project_root) => {
+            if let Err(e) = crate::tools::git::delete_worktree(&project_root, feature_id) {
+                tracing::warn!("Failed to remove worktree of {}: {}", feature_id, e);
+            }
+        }
+        Err(e) => tracing::warn!("Failed to remove worktree of {}: {}", feature_id, e),
+    }
+    let _ = fm.clear_worktree(feature_id);
+    let _ = fm.set_failed(feature_id, "Cancelled");
+}
+
+/// Apply run control commands as they arrive and pass resolved interactions on
+async fn route_commands(
+    mut command_rx: mpsc::Receiver<CoordinatorCommand>,
+    control: RunControl,
+    resolved_tx: mpsc::Sender<String>,
+) {
+    while let Some(command) = command_rx.recv().await {
+        match command {
+            CoordinatorCommand::InteractionResolved(id) => {
+                let _ = resolved_tx.send(id).await;
             }
+            CoordinatorCommand::Abort => control.cancel(),
+            CoordinatorCommand::Pause => control.pause(),
+            CoordinatorCommand::Resume => control.resume(),
+            CoordinatorCommand::SkipUnknown(id) => control.skip_unknown(&id),
+            CoordinatorCommand::CancelFeature(id) => control.cancel_feature(&id),
+        }
+    }
+}
+
+/// Next progress update from the research agent (`None` when there is none)
+async fn recv_progress(
+    progress_rx: &mut Option<mpsc::Receiver<super::a2a_bridge::ResearchProgress>>,
+) -> Option<super::a2a_bridge::ResearchProgress> {
+    match progress_rx {
+        Some(progress_rx) => progress_rx.recv().await,
+        None => None,
+    }
+}
+
+/// Swarm event for a research progress update
+fn research_event(progress: &super::a2a_bridge::ResearchProgress) -> SwarmEvent {
+    use super::a2a_bridge::ResearchProgress;
+
+    match progress {
+        ResearchProgress::Started { unknown_id } => {
+            SwarmEvent::new(SwarmEventKind::ResearchStarted, "researcher").with_unknown(unknown_id)
         }
+        ResearchProgress::Status {
+            unknown_id,
+            message,
+        } => SwarmEvent::new(SwarmEventKind::ResearchProgress, "researcher")
+            .with_unknown(unknown_id)
+            .with_data(serde_json::json!({ "message": message })),
+        ResearchProgress::Completed { unknown_id } => {
+            SwarmEvent::new(SwarmEventKind::ResearchCompleted, "researcher")
+                .with_unknown(unknown_id)
+        }
+        ResearchProgress::Failed { unknown_id, error } => {
+            SwarmEvent::new(SwarmEventKind::AgentFailed, "researcher")
+                .with_unknown(unknown_id)
+                .with_data(serde_json::json!({ "error": error }))
+        }
+    }
+}
 
-        Ok(results)
+/// Builder instructions for a mission whose files are already drafted
+fn builder_mission(
+    mission: &MissionPrompt,
+    previous_errors: &[String],
+    forbid_unwrap: bool,
+) -> String {
+    let mut prompt = format!(
+        "# MISSION: {}\n\n{}\n\nThe files below have been drafted in the worktree. \
+         Verify them with run_check and run_test, and fix them until both pass.\n\n## Tasks\n",
+        mission.feature_name, mission.objective
+    );
+    for task in &mission.tasks {
+        prompt.push_str(&format!(
+            "{}. {} `{}`: {}\n",
+            task.number, task.action, task.file_path, task.implementation
+        ));
+    }
+    prompt.push_str(&format!(
+        "\n## Constraints\n- Max file length: {} lines\n- Max function length: {} lines\n",
+        mission.constraints.max_file_lines, mission.constraints.max_function_lines
+    ));
+    if forbid_unwrap {
+        prompt.push_str("- No `unwrap()`: handle every error\n");
+    }
+    if !mission.verification.is_empty() {
+        prompt.push_str("\n## Verification\n");
+        for check in &mission.verification {
+            prompt.push_str(&format!("- {}\n", check));
+        }
+    }
+    if !previous_errors.is_empty() {
+        prompt.push_str("\n## Errors left by the previous attempt\n");
+        for error in previous_errors {
+            prompt.push_str(&format!("- {}\n", error));
+        }
     }
+    prompt
 }
 
 /// Result of processing a single feature
@@ -987,7 +3043,840 @@ mod tests {
     #[test]
     fn test_coordinator_config_default() {
         let config = CoordinatorConfig::default();
-        assert_eq!(config.mode, "lab");
+        assert_eq!(config.mode, ProjectMode::Lab);
         assert_eq!(config.max_rejections, 3);
     }
+
+    #[test]
+    fn test_local_provider_base_url_resolution() {
+        let db_path = ".catalyst/test_coordinator_local.db";
+        let db = Arc::new(CatalystDb::open_at(db_path).unwrap());
+
+        let mut config = CoordinatorConfig {
+            base_url: Some("https://api.example.com/v1".to_string()),
+            local_base_url: Some("http://gpu-box:8000/v1".to_string()),
+            ..CoordinatorConfig::default()
+        };
+        config
+            .per_agent_providers
+            .insert("drafter".to_string(), LlmProvider::Local);
+        config
+            .per_agent_providers
+            .insert("builder".to_string(), LlmProvider::OpenAI);
+        let coordinator = Coordinator::new(config, db);
+
+        let drafter = coordinator.get_model_config("drafter");
+        assert_eq!(drafter.provider, LlmProvider::Local);
+        assert_eq!(drafter.model, "llama3.2");
+        assert_eq!(drafter.base_url.as_deref(), Some("http://gpu-box:8000/v1"));
+
+        let builder = coordinator.get_model_config("builder");
+        assert_eq!(
+            builder.base_url.as_deref(),
+            Some("https://api.example.com/v1")
+        );
+
+        assert_eq!(coordinator.get_model_config("architect").base_url, None);
+
+        drop(coordinator);
+        let _ = std::fs::remove_file(db_path);
+    }
+
+    #[test]
+    fn test_per_agent_generation_params() {
+        let db_path = ".catalyst/test_coordinator_generation.db";
+        let db = Arc::new(CatalystDb::open_at(db_path).unwrap());
+
+        let mut config = CoordinatorConfig {
+            generation: GenerationParams::default()
+                .with_temperature(0.7)
+                .with_max_tokens(8192),
+            ..CoordinatorConfig::default()
+        };
+        config.per_agent_generation.insert(
+            "critic".to_string(),
+            GenerationParams::default().with_temperature(0.0),
+        );
+        let coordinator = Coordinator::new(config, db);
+
+        let critic = coordinator.get_model_config("critic").generation;
+        assert_eq!(critic.temperature, Some(0.0));
+        assert_eq!(critic.max_tokens, Some(8192));
+
+        let drafter = coordinator.get_model_config("drafter").generation;
+        assert_eq!(drafter.temperature, Some(0.7));
+
+        drop(coordinator);
+        let _ = std::fs::remove_file(db_path);
+    }
+
+    #[test]
+    fn test_builder_mission_lists_tasks_and_errors() {
+        use crate::skills::taskmaster_skill::{MissionConstraints, MissionTask};
+
+        let mission = MissionPrompt {
+            feature_name: "Health check".to_string(),
+            objective: "Expose /health".to_string(),
+            tasks: vec![MissionTask {
+                number: 1,
+                action: "Create".to_string(),
+                file_path: "src/health.rs".to_string(),
+                implementation: "Return 200 OK".to_string(),
+                hints: vec![],
+            }],
+            constraints: MissionConstraints {
+                max_file_lines: 150,
+                max_function_lines: 30,
+                required_patterns: vec![],
+                forbidden_patterns: vec![],
+            },
+            drafting_missions: vec![],
+            existing_signatures: vec![],
+            verification: vec!["cargo test passes".to_string()],
+        };
+
+        let first = builder_mission(&mission, &[], false);
+        assert!(first.contains("1. Create `src/health.rs`: Return 200 OK"));
+        assert!(first.contains("- cargo test passes"));
+        assert!(!first.contains("previous attempt"));
+
+        let retry = builder_mission(
+            &mission,
+            &["E0425: cannot find `Router`".to_string()],
+            false,
+        );
+        assert!(retry.contains("- E0425: cannot find `Router`"));
+    }
+
+    #[tokio::test]
+    async fn test_resume_skips_checkpointed_unknowns() {
+        let db_path = ".catalyst/test_coordinator_resume.db";
+        let _ = std::fs::remove_file(db_path);
+        let db = Arc::new(CatalystDb::open_at(db_path).unwrap());
+
+        let runs = RunManager::new(&db);
+        runs.start("run-resume", "Add caching").unwrap();
+        runs.set_unknowns(
+            "run-resume",
+            &serde_json::json!({"ambiguities": [
+                {"id": "U-1", "category": "Infrastructure", "question": "Which cache?", "criticality": "HIGH"},
+                {"id": "U-2", "category": "Logic", "question": "Eviction?", "criticality": "LOW"}
+            ]}),
+        )
+        .unwrap();
+        for id in ["U-1", "U-2"] {
+            let research = serde_json::json!({"unknown_id": id, "options": [], "summary": "notes"});
+            let verdict = serde_json::json!({
+                "verdict": "rejected",
+                "summary": "Too vague",
+                "concerns": [{"severity": "major", "description": "No TTL"}],
+                "confidence": 0.9
+            });
+            runs.save_checkpoint("run-resume", id, CheckpointStage::Research, &research)
+                .unwrap();
+            runs.save_checkpoint("run-resume", id, CheckpointStage::Verdict, &verdict)
+                .unwrap();
+        }
+
+        // Every unknown is finished, so no LLM is called
+        let mut coordinator = Coordinator::new(CoordinatorConfig::default(), Arc::clone(&db));
+        let result = coordinator.resume("run-resume").await.unwrap();
+        assert_eq!(coordinator.run_id(), "run-resume");
+        assert_eq!(result.research.len(), 2);
+        assert_eq!(result.verdicts.len(), 2);
+        assert!(!result.success);
+        assert!(result
+            .events
+            .iter()
+            .any(|e| e.kind == SwarmEventKind::RunResumed));
+        assert_eq!(runs.load("run-resume").unwrap().status, RunStatus::Failed);
+
+        drop(coordinator);
+        drop(runs);
+        drop(db);
+        let _ = std::fs::remove_file(db_path);
+    }
+
+    /// Coordinator config replaying a scripted cassette: the parser returns
+    /// `ambiguities` and the Critic always gives `verdict`
+    fn scripted_config(
+        cassette_path: &std::path::Path,
+        ambiguities: serde_json::Value,
+        verdict: &str,
+    ) -> CoordinatorConfig {
+        use crate::models::replay::{Cassette, CassetteEntry, WILDCARD_HASH};
+        use radkit::models::{Content, LlmResponse, TokenUsage};
+
+        let mut cassette = Cassette::default();
+        for (agent, output) in [
+            (
+                "unknowns_parser",
+                serde_json::json!({ "ambiguities": ambiguities }),
+            ),
+            (
+                "researcher",
+                serde_json::json!({"unknown_id": "U-1", "options": [], "summary": "notes"}),
+            ),
+            (
+                "architect",
+                serde_json::json!({
+                    "unknown_id": "U-1",
+                    "chosen_option": "moka",
+                    "rationale": "In-process",
+                    "spec_updates": [],
+                    "dependencies": []
+                }),
+            ),
+            (
+                "critic",
+                serde_json::json!({
+                    "verdict": verdict,
+                    "summary": "Reviewed",
+                    "concerns": [{"severity": "major", "description": "No TTL"}],
+                    "confidence": 0.9
+                }),
+            ),
+        ] {
+            cassette.record(CassetteEntry {
+                agent: agent.to_string(),
+                prompt_hash: WILDCARD_HASH.to_string(),
+                turn: 0,
+                call: 0,
+                response: LlmResponse::new(
+                    Content::from_text(output.to_string()),
+                    TokenUsage::empty(),
+                ),
+            });
+        }
+        cassette.save(cassette_path).unwrap();
+
+        CoordinatorConfig {
+            global_provider: LlmProvider::Mock,
+            global_model: Some("replay".to_string()),
+            cassette: Some(CassetteConfig {
+                path: cassette_path.to_path_buf(),
+                mode: crate::models::CassetteMode::Replay,
+            }),
+            max_rejections: 1,
+            require_critic_approval: false,
+            ..CoordinatorConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_research_keeps_unknown_order() {
+        let db_path = ".catalyst/test_coordinator_research.db";
+        let cassette_path = std::env::temp_dir().join("catalyst_test_concurrent_research.json");
+        let _ = std::fs::remove_file(db_path);
+
+        let config = CoordinatorConfig {
+            max_concurrent_research: Some(2),
+            ..scripted_config(
+                &cassette_path,
+                serde_json::json!([
+                    {"id": "U-1", "category": "Infrastructure", "question": "Which cache?", "criticality": "HIGH", "depends_on": []},
+                    {"id": "U-2", "category": "Logic", "question": "Eviction?", "criticality": "LOW", "depends_on": []},
+                    {"id": "U-3", "category": "Security", "question": "Auth?", "criticality": "LOW", "depends_on": []}
+                ]),
+                "rejected",
+            )
+        };
+        let db = Arc::new(CatalystDb::open_at(db_path).unwrap());
+        let mut coordinator = Coordinator::new(config, Arc::clone(&db)).with_research_agent();
+        let result = coordinator.run("Add caching").await.unwrap();
+
+        assert_eq!(result.research.len(), 3);
+        assert_eq!(result.verdicts.len(), 3);
+        assert!(!result.success);
+
+        // Every unknown is researched before its Architect starts
+        for id in ["U-1", "U-2", "U-3"] {
+            let position = |kind: SwarmEventKind, agent: &str| {
+                result.events.iter().position(|e| {
+                    e.kind == kind && e.agent == agent && e.unknown_id.as_deref() == Some(id)
+                })
+            };
+            let researched = position(SwarmEventKind::ResearchCompleted, "researcher").unwrap();
+            let architect = position(SwarmEventKind::AgentStarted, "architect").unwrap();
+            assert!(researched < architect, "{} decided before its research", id);
+        }
+
+        let checkpoints = RunManager::new(&db)
+            .checkpoints(coordinator.run_id())
+            .unwrap();
+        assert_eq!(
+            checkpoints
+                .iter()
+                .filter(|c| c.stage == CheckpointStage::Research)
+                .count(),
+            3
+        );
+
+        drop(coordinator);
+        drop(db);
+        let _ = std::fs::remove_file(db_path);
+        let _ = std::fs::remove_file(cassette_path);
+    }
+
+    #[tokio::test]
+    async fn test_unknowns_decided_after_their_dependencies() {
+        use crate::state::InteractionManager;
+
+        let db_path = ".catalyst/test_coordinator_dependencies.db";
+        let cassette_path = std::env::temp_dir().join("catalyst_test_unknown_dependencies.json");
+        let _ = std::fs::remove_file(db_path);
+
+        let config = scripted_config(
+            &cassette_path,
+            serde_json::json!([
+                {"id": "U-1", "category": "Infrastructure", "question": "Which ORM?", "criticality": "HIGH", "depends_on": ["U-2"]},
+                {"id": "U-2", "category": "Infrastructure", "question": "Which database?", "criticality": "HIGH", "depends_on": []},
+                {"id": "U-3", "category": "Logic", "question": "Retry?", "criticality": "LOW", "depends_on": ["U-4"]},
+                {"id": "U-4", "category": "Logic", "question": "Timeouts?", "criticality": "LOW", "depends_on": ["U-3"]}
+            ]),
+            "approved",
+        );
+        let db = Arc::new(CatalystDb::open_at(db_path).unwrap());
+        let mut coordinator = Coordinator::new(config, Arc::clone(&db));
+        let result = coordinator.run("Store orders").await.unwrap();
+        assert_eq!(result.decisions.len(), 4);
+
+        let architect_order: Vec<&str> = result
+            .events
+            .iter()
+            .filter(|e| e.kind == SwarmEventKind::AgentStarted && e.agent == "architect")
+            .filter_map(|e| e.unknown_id.as_deref())
+            .collect();
+        assert_eq!(architect_order, vec!["U-2", "U-1", "U-3", "U-4"]);
+
+        // Only U-1 and U-4 (after U-3 in the cycle) see an approved dependency
+        let prompts: Vec<String> = crate::state::TranscriptManager::new(&db)
+            .for_run(coordinator.run_id(), Some("architect"))
+            .unwrap()
+            .into_iter()
+            .filter_map(|entry| entry.messages.first().map(|m| m.text.clone()))
+            .collect();
+        let with_context = prompts
+            .iter()
+            .filter(|p| p.contains("Approved Decisions This Depends On"))
+            .count();
+        assert_eq!(with_context, 2);
+
+        let cycle = InteractionManager::new(&db)
+            .load(&format!("int-cycle-{}", coordinator.run_id()))
+            .unwrap();
+        assert!(cycle.description.contains("U-3, U-4"));
+
+        drop(coordinator);
+        drop(db);
+        let _ = std::fs::remove_file(db_path);
+        let _ = std::fs::remove_file(cassette_path);
+    }
+
+    #[tokio::test]
+    async fn test_skipped_unknown_and_aborted_run() {
+        let db_path = ".catalyst/test_coordinator_control.db";
+        let cassette_path = std::env::temp_dir().join("catalyst_test_run_control.json");
+        let _ = std::fs::remove_file(db_path);
+
+        let config = scripted_config(
+            &cassette_path,
+            serde_json::json!([
+                {"id": "U-1", "category": "Infrastructure", "question": "Which cache?", "criticality": "HIGH", "depends_on": []},
+                {"id": "U-2", "category": "Infrastructure", "question": "Eviction?", "criticality": "LOW", "depends_on": ["U-1"]}
+            ]),
+            "approved",
+        );
+        let db = Arc::new(CatalystDb::open_at(db_path).unwrap());
+
+        // A skipped unknown is left undecided; its dependents still go ahead
+        let mut coordinator = Coordinator::new(config.clone(), Arc::clone(&db));
+        coordinator.control().skip_unknown("U-1");
+        let result = coordinator.run("Cache prices").await.unwrap();
+        assert_eq!(result.skipped, vec!["U-1".to_string()]);
+        assert_eq!(result.decisions.len(), 1);
+        assert_eq!(result.decisions[0].unknown_id, "U-1"); // Scripted output
+        assert!(result
+            .events
+            .iter()
+            .any(|e| e.kind == SwarmEventKind::UnknownSkipped
+                && e.unknown_id.as_deref() == Some("U-1")));
+
+        // An aborted run stops with `Cancelled` and can be resumed later
+        let mut coordinator = Coordinator::new(config, Arc::clone(&db));
+        coordinator.control().cancel();
+        let error = coordinator.run("Cache prices").await.unwrap_err();
+        assert!(is_cancelled(&error));
+        let runs = RunManager::new(&db);
+        assert_eq!(
+            runs.load(coordinator.run_id()).unwrap().status,
+            RunStatus::Cancelled
+        );
+        assert_eq!(ProjectState::load(&db).unwrap().phase, "cancelled");
+
+        drop(runs);
+        drop(coordinator);
+        drop(db);
+        let _ = std::fs::remove_file(db_path);
+        let _ = std::fs::remove_file(cassette_path);
+    }
+
+    #[tokio::test]
+    async fn test_speed_run_skips_unknowns_parser() {
+        let db_path = ".catalyst/test_coordinator_speed_run.db";
+        let cassette_path = std::env::temp_dir().join("catalyst_test_speed_run.json");
+        let _ = std::fs::remove_file(db_path);
+
+        let config = CoordinatorConfig {
+            mode: ProjectMode::SpeedRun,
+            ..scripted_config(
+                &cassette_path,
+                serde_json::json!([
+                    {"id": "U-1", "category": "Infrastructure", "question": "Which cache?", "criticality": "HIGH", "depends_on": []}
+                ]),
+                "approved",
+            )
+        };
+        let db = Arc::new(CatalystDb::open_at(db_path).unwrap());
+        let mut coordinator = Coordinator::new(config, Arc::clone(&db));
+        assert!(!coordinator.policy().parse_unknowns);
+
+        // Planning fails (no scripted atomizer), but only after skipping ahead to it
+        let result = coordinator.run("Cache prices").await.unwrap();
+        assert!(result.unknowns.ambiguities.is_empty());
+        assert!(result.decisions.is_empty());
+        assert!(!result.events.iter().any(|e| e.agent == "unknowns_parser"));
+        assert!(result
+            .events
+            .iter()
+            .any(|e| e.kind == SwarmEventKind::AgentStarted && e.agent == "atomizer"));
+        assert_eq!(
+            ProjectState::load(&db).unwrap().mode,
+            Some(ProjectMode::SpeedRun)
+        );
+
+        drop(coordinator);
+        drop(db);
+        let _ = std::fs::remove_file(db_path);
+        let _ = std::fs::remove_file(cassette_path);
+    }
+
+    #[tokio::test]
+    async fn test_custom_pipeline_graph() {
+        let db_path = ".catalyst/test_coordinator_pipeline_graph.db";
+        let cassette_path = std::env::temp_dir().join("catalyst_test_pipeline_graph.json");
+        let _ = std::fs::remove_file(db_path);
+
+        // No research or planning; blockers get a second, security review
+        let graph = PipelineGraph::from_toml(
+            r#"
+            start = "parse"
+
+            [[stage]]
+            id = "parse"
+            skill = "parse"
+            next = "architect"
+
+            [[stage]]
+            id = "architect"
+            skill = "architect"
+            next = "critic"
+
+            [[stage]]
+            id = "critic"
+            skill = "review"
+            on_reject = { goto = "architect", max_iterations = 2 }
+
+            [[stage.branch]]
+            when = { criticality = ["BLOCKER"] }
+            goto = "security"
+
+            [[stage]]
+            id = "security"
+            skill = "review"
+            agent = "critic"
+            focus = "security"
+            "#,
+        )
+        .unwrap();
+        let config = CoordinatorConfig {
+            pipeline: Some(graph),
+            ..scripted_config(
+                &cassette_path,
+                serde_json::json!([
+                    {"id": "U-1", "category": "Security", "question": "Auth?", "criticality": "BLOCKER", "depends_on": []},
+                    {"id": "U-2", "category": "Logic", "question": "Eviction?", "criticality": "LOW", "depends_on": []}
+                ]),
+                "approved",
+            )
+        };
+        let db = Arc::new(CatalystDb::open_at(db_path).unwrap());
+        let feature = FeatureManager::new(&db).create("Cache prices").unwrap();
+        let mut coordinator = Coordinator::new(config, Arc::clone(&db)).with_feature(&feature.id);
+
+        let result = coordinator.run("Cache prices").await.unwrap();
+        assert!(result.success);
+        // Approved without planning stages: the feature is not failed
+        let feature = FeatureManager::new(&db).load(&feature.id).unwrap();
+        assert_ne!(feature.stage, crate::state::PipelineStage::Failed);
+        assert!(feature.error.is_none());
+        assert_eq!(result.decisions.len(), 2);
+        assert!(result.research.iter().all(|r| r.options.is_empty()));
+        assert!(result.plan.is_none() && result.mission.is_none());
+        assert!(!result.events.iter().any(|e| e.agent == "researcher"));
+        assert!(!result.events.iter().any(|e| e.agent == "atomizer"));
+        let reviews = |unknown: &str| {
+            result
+                .events
+                .iter()
+                .filter(|e| {
+                    e.kind == SwarmEventKind::AgentStarted
+                        && e.agent == "critic"
+                        && e.unknown_id.as_deref() == Some(unknown)
+                })
+                .count()
+        };
+        assert_eq!(reviews("U-1"), 2);
+        assert_eq!(reviews("U-2"), 1);
+
+        drop(coordinator);
+        drop(db);
+        let _ = std::fs::remove_file(db_path);
+        let _ = std::fs::remove_file(cassette_path);
+    }
+
+    #[tokio::test]
+    async fn test_review_panel_quorum() {
+        use crate::models::replay::{Cassette, CassetteEntry, WILDCARD_HASH};
+        use crate::swarm::consensus::{PanelCritic, Quorum};
+        use radkit::models::{Content, LlmResponse, TokenUsage};
+
+        let db_path = ".catalyst/test_coordinator_review_panel.db";
+        let cassette_path = std::env::temp_dir().join("catalyst_test_review_panel.json");
+        let _ = std::fs::remove_file(db_path);
+
+        // The default critic asks for changes; the security critic approves
+        let base = scripted_config(
+            &cassette_path,
+            serde_json::json!([
+                {"id": "U-1", "category": "Infrastructure", "question": "Which cache?", "criticality": "HIGH", "depends_on": []}
+            ]),
+            "needs_changes",
+        );
+        let mut cassette = Cassette::load(&cassette_path).unwrap();
+        cassette.record(CassetteEntry {
+            agent: "security_critic".to_string(),
+            prompt_hash: WILDCARD_HASH.to_string(),
+            turn: 0,
+            call: 0,
+            response: LlmResponse::new(
+                Content::from_text(
+                    serde_json::json!({
+                        "verdict": "approved",
+                        "summary": "No exposure",
+                        "concerns": [],
+                        "confidence": 0.95
+                    })
+                    .to_string(),
+                ),
+                TokenUsage::empty(),
+            ),
+        });
+        cassette.save(&cassette_path).unwrap();
+
+        let panel = |quorum| ReviewPanel {
+            critics: vec![
+                PanelCritic {
+                    id: "critic".to_string(),
+                    persona: None,
+                },
+                PanelCritic {
+                    id: "security_critic".to_string(),
+                    persona: Some("security".to_string()),
+                },
+            ],
+            quorum,
+        };
+        let db = Arc::new(CatalystDb::open_at(db_path).unwrap());
+
+        // Approvals outweigh the objection
+        let mut coordinator = Coordinator::new(
+            CoordinatorConfig {
+                review_panel: Some(panel(Quorum::ConfidenceWeighted)),
+                ..base.clone()
+            },
+            Arc::clone(&db),
+        );
+        let result = coordinator.run("Cache prices").await.unwrap();
+        assert_eq!(result.decisions.len(), 1);
+        assert_eq!(result.verdicts[0].verdict, "approved");
+        let consensus = result
+            .events
+            .iter()
+            .find(|e| e.kind == SwarmEventKind::ReviewConsensus)
+            .unwrap();
+        assert_eq!(consensus.data.as_ref().unwrap()["verdict"], "approved");
+        assert!(result
+            .events
+            .iter()
+            .any(|e| { e.kind == SwarmEventKind::AgentCompleted && e.agent == "security_critic" }));
+        drop(coordinator);
+
+        // A single objection blocks a unanimous panel and sends the decision back
+        let mut coordinator = Coordinator::new(
+            CoordinatorConfig {
+                review_panel: Some(panel(Quorum::Unanimous)),
+                max_rejections: 2,
+                ..base
+            },
+            Arc::clone(&db),
+        );
+        let result = coordinator.run("Cache prices").await.unwrap();
+        let architect_runs = result
+            .events
+            .iter()
+            .filter(|e| e.kind == SwarmEventKind::AgentStarted && e.agent == "architect")
+            .count();
+        assert_eq!(architect_runs, 2);
+        assert!(result
+            .events
+            .iter()
+            .any(|e| e.kind == SwarmEventKind::CriticRejected));
+
+        drop(coordinator);
+        drop(db);
+        let _ = std::fs::remove_file(db_path);
+        let _ = std::fs::remove_file(cassette_path);
+    }
+
+    #[tokio::test]
+    async fn test_fortress_merge_gate_blocks_unwrap() {
+        let dir = std::env::temp_dir().join("catalyst_test_fortress_merge_gate");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::process::Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(&dir)
+            .output()
+            .unwrap();
+        std::fs::write(
+            dir.join("src/lib.rs"),
+            "pub fn port() -> u16 {\n    \"80\".parse().unwrap()\n}\n",
+        )
+        .unwrap();
+
+        let critic = ModelConfig::default();
+        let gate = |mode| {
+            merge_gate_problems(
+                ModePolicy::for_mode(mode),
+                ConstraintSkill::default(),
+                &dir,
+                "Serve on a port",
+                &critic,
+            )
+        };
+        assert!(gate(ProjectMode::Lab).await.unwrap().is_empty());
+        // Constraint problems are reported before the Critic is consulted
+        let problems = gate(ProjectMode::Fortress).await.unwrap();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].starts_with("src/lib.rs:2:"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_red_team_gate_runs_the_suite() {
+        use crate::skills::red_team_skill::RedTeamTest;
+
+        let dir = std::env::temp_dir().join("catalyst_test_red_team_gate");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(
+            dir.join("Cargo.toml"),
+            "[package]\nname = \"red_team_probe\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+        )
+        .unwrap();
+        // A passing test of the project's own must not turn the gate green
+        let lib = |body: &str| {
+            format!(
+                "pub fn double(x: u32) -> u32 {{ {} }}\n\n#[test]\nfn unrelated() {{}}\n",
+                body
+            )
+        };
+        std::fs::write(dir.join("src/lib.rs"), lib("x + x + 1")).unwrap();
+
+        let suite = RedTeamOutput {
+            tests: vec![RedTeamTest {
+                path: "tests/double_red_team.rs".to_string(),
+                content:
+                    "#[test]\nfn doubles() {\n    assert_eq!(red_team_probe::double(2), 4);\n}\n"
+                        .to_string(),
+                covers: vec![],
+            }],
+            summary: "Attacks double".to_string(),
+        };
+        suite.write_to(&dir).unwrap();
+
+        let problems = red_team_problems(&suite, &dir).await.unwrap();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].starts_with("tests/double_red_team.rs: doubles failed"));
+
+        std::fs::write(dir.join("src/lib.rs"), lib("x * 2")).unwrap();
+        assert!(red_team_problems(&suite, &dir).await.unwrap().is_empty());
+
+        // A suite without tests does not pass either
+        std::fs::write(dir.join("tests/double_red_team.rs"), "").unwrap();
+        let empty = RedTeamOutput {
+            tests: vec![RedTeamTest {
+                content: String::new(),
+                ..suite.tests[0].clone()
+            }],
+            ..suite.clone()
+        };
+        assert_eq!(
+            red_team_problems(&empty, &dir).await.unwrap(),
+            vec!["tests/double_red_team.rs: no tests ran"]
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_dependents_branch_from_merged_prerequisites() {
+        use crate::tools::git;
+        use std::process::Command;
+
+        let dir = std::env::temp_dir().join("catalyst_test_merge_prerequisite");
+        let run = |args: &[&str]| {
+            let output = Command::new("git")
+                .args(args)
+                .current_dir(&dir)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "git {:?} failed", args);
+        };
+
+        let (prerequisite, dependent) = ("merge-probe-schema", "merge-probe-api");
+        let cleanup = || {
+            let _ = git::delete_worktree(&dir, prerequisite);
+            let _ = git::delete_worktree(&dir, dependent);
+            let _ = std::fs::remove_dir_all(&dir);
+        };
+        cleanup();
+        std::fs::create_dir_all(&dir).unwrap();
+        run(&["init", "-q", "-b", "main"]);
+        run(&["config", "user.email", "test@example.com"]);
+        run(&["config", "user.name", "Test"]);
+        std::fs::write(dir.join("README.md"), "probe\n").unwrap();
+        run(&["add", "-A"]);
+        run(&["commit", "-q", "-m", "init"]);
+
+        let worktree = git::create_worktree(&dir, prerequisite).unwrap();
+        // A feature that changed nothing is not merged
+        assert!(commit_feature(&worktree, prerequisite, "Schema").is_err());
+
+        std::fs::write(
+            worktree.join("schema.sql"),
+            "CREATE TABLE t (id INTEGER);\n",
+        )
+        .unwrap();
+        commit_feature(&worktree, prerequisite, "Schema").unwrap();
+        assert!(matches!(
+            git::merge_worktree(&dir, prerequisite).unwrap(),
+            git::MergeResult::Success
+        ));
+
+        let worktree = git::create_worktree(&dir, dependent).unwrap();
+        let has_schema = worktree.join("schema.sql").exists();
+        cleanup();
+        assert!(has_schema);
+    }
+
+    #[test]
+    fn test_failing_tests_block_the_merge() {
+        let output = |success, tests_passed, errors: Vec<&str>| BuilderOutput {
+            success,
+            summary: "Implemented limiter".to_string(),
+            files_modified: vec![],
+            errors: errors.into_iter().map(str::to_string).collect(),
+            build_passed: true,
+            tests_passed,
+            iterations: 1,
+        };
+
+        assert_eq!(build_failure(&output(true, true, vec![])), None);
+        // Tests decide, whatever the model claims
+        assert_eq!(
+            build_failure(&output(true, false, vec!["limits failed: 3 != 2"])).as_deref(),
+            Some("Implemented limiter: limits failed: 3 != 2")
+        );
+        assert!(build_failure(&output(false, true, vec![])).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_merge_conflicts_fail_the_feature() {
+        use crate::state::PipelineStage as FeatureStage;
+        use crate::tools::git;
+        use std::process::Command;
+
+        let db_path = ".catalyst/test_coordinator_merge_conflict.db";
+        let dir = std::env::temp_dir().join("catalyst_test_merge_conflict");
+        let _ = std::fs::remove_file(db_path);
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let run = |args: &[&str]| {
+            let output = Command::new("git")
+                .args(args)
+                .current_dir(&dir)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "git {:?} failed", args);
+        };
+        run(&["init", "-q", "-b", "main"]);
+        run(&["config", "user.email", "test@example.com"]);
+        run(&["config", "user.name", "Test"]);
+        std::fs::write(dir.join("limits.toml"), "rpm = 10\n").unwrap();
+        run(&["add", "-A"]);
+        run(&["commit", "-q", "-m", "init"]);
+
+        let db = Arc::new(CatalystDb::open_at(db_path).unwrap());
+        let feature = FeatureManager::new(&db).create("Raise limits").unwrap();
+        let mut coordinator = Coordinator::new(CoordinatorConfig::default(), Arc::clone(&db));
+
+        // The feature and main both change the same line
+        let worktree = git::create_worktree(&dir, &feature.id).unwrap();
+        std::fs::write(worktree.join("limits.toml"), "rpm = 20\n").unwrap();
+        commit_feature(&worktree, &feature.id, "Raise limits").unwrap();
+        std::fs::write(dir.join("limits.toml"), "rpm = 30\n").unwrap();
+        run(&["commit", "-q", "-am", "tune"]);
+
+        let merge = coordinator.merge_feature(&dir, &feature.id).await;
+        let stored = FeatureManager::new(&db).load(&feature.id).unwrap();
+        let _ = git::delete_worktree(&dir, &feature.id);
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(matches!(
+            merge.unwrap(),
+            git::MergeResult::Conflicts(files) if files == vec!["limits.toml".to_string()]
+        ));
+        assert_eq!(stored.stage, FeatureStage::Failed);
+        assert_eq!(
+            stored.error.as_deref(),
+            Some("Merge conflicts in: limits.toml")
+        );
+        let stages: Vec<_> = coordinator
+            .events
+            .iter()
+            .filter(|e| e.kind == SwarmEventKind::FeatureStageChanged)
+            .filter_map(|e| e.data.as_ref())
+            .collect();
+        assert_eq!(stages.len(), 2);
+        assert_eq!(stages[0]["stage"], serde_json::json!(FeatureStage::Merging));
+        assert_eq!(stages[1]["stage"], serde_json::json!(FeatureStage::Failed));
+        assert_eq!(stages[1]["conflicts"], serde_json::json!(["limits.toml"]));
+
+        drop(coordinator);
+        drop(db);
+        let _ = std::fs::remove_file(db_path);
+    }
 }
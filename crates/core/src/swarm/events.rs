@@ -4,6 +4,9 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+use crate::models::{RepairEvent, RepairObserver, RetryEvent, RetryObserver};
 
 /// Kind of swarm event
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -17,10 +20,18 @@ pub enum SwarmEventKind {
     AgentCompleted,
     /// Agent failed
     AgentFailed,
+    /// LLM call failed transiently; retrying or falling back to another provider
+    AgentRetrying,
+    /// Structured output failed validation; re-prompting with the errors
+    AgentRepairing,
     /// Data passed between agents
     DataPassed,
     /// Critic rejected, looping back
     CriticRejected,
+    /// Review panel's verdict, with each critic's vote
+    ReviewConsensus,
+    /// Interrupted run continued from its checkpoints
+    RunResumed,
     /// Pipeline completed
     PipelineCompleted,
     /// Pipeline failed
@@ -47,6 +58,26 @@ pub enum SwarmEventKind {
     DraftingProgress,
     /// All files drafted, ready for bulk write
     DraftingCompleted,
+    // === Execution events ===
+    /// Feature moved to another pipeline stage (worktree, build, merge)
+    FeatureStageChanged,
+    // === Budget events ===
+    /// Run exceeded its token/cost budget (pausing or aborting)
+    BudgetExceeded,
+    // === Spec events ===
+    /// Approved decision was merged into the project documents (with any conflicts)
+    SpecUpdated,
+    /// Built feature failed its mode's merge gate (constraints or critic review)
+    MergeGateFailed,
+    // === Run control events ===
+    /// Run is holding at a stage boundary until resumed
+    RunPaused,
+    /// Paused run continued
+    RunContinued,
+    /// Unknown was skipped and left without a decision
+    UnknownSkipped,
+    /// Run was aborted; work in flight was stopped
+    RunCancelled,
 }
 
 /// An event in the swarm
@@ -66,6 +97,9 @@ pub struct SwarmEvent {
     /// Related unknown ID if applicable
     #[serde(default)]
     pub unknown_id: Option<String>,
+    /// Run that produced the event (set by the coordinator)
+    #[serde(default)]
+    pub run_id: Option<String>,
 }
 
 impl SwarmEvent {
@@ -78,6 +112,7 @@ impl SwarmEvent {
             agent: agent.to_string(),
             data: None,
             unknown_id: None,
+            run_id: None,
         }
     }
 
@@ -94,8 +129,43 @@ impl SwarmEvent {
     }
 }
 
+/// Forwards LLM retries and output repairs to the event channel
+/// (`AgentRetrying` / `AgentRepairing`)
+#[derive(Debug)]
+pub struct LlmEventForwarder {
+    tx: Option<mpsc::Sender<SwarmEvent>>,
+}
+
+impl LlmEventForwarder {
+    pub fn new(tx: Option<mpsc::Sender<SwarmEvent>>) -> Self {
+        Self { tx }
+    }
+
+    fn forward(&self, kind: SwarmEventKind, agent: &str, data: serde_json::Value) {
+        let Some(tx) = &self.tx else {
+            return;
+        };
+        // The observers are synchronous, so drop the event rather than block when the channel is full
+        let _ = tx.try_send(SwarmEvent::new(kind, agent).with_data(data));
+    }
+}
+
+impl RetryObserver for LlmEventForwarder {
+    fn on_retry(&self, event: RetryEvent) {
+        let data = serde_json::to_value(&event).unwrap_or_default();
+        self.forward(SwarmEventKind::AgentRetrying, &event.agent_id, data);
+    }
+}
+
+impl RepairObserver for LlmEventForwarder {
+    fn on_repair(&self, event: RepairEvent) {
+        let data = serde_json::to_value(&event).unwrap_or_default();
+        self.forward(SwarmEventKind::AgentRepairing, &event.agent_id, data);
+    }
+}
+
 /// Generate a simple UUID v4
-fn uuid_v4() -> String {
+pub(crate) fn uuid_v4() -> String {
     use std::time::{Duration, SystemTime, UNIX_EPOCH};
     let nanos = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -0,0 +1,265 @@
+//! # Feature Dependencies
+//!
+//! Order in which `run_features_parallel` builds a batch of features. A
+//! feature that `depends_on` others starts once they are merged, so its
+//! worktree branches from a main that already contains their code.
+//!
+//! Unlike unknowns, features in a dependency cycle have no fallback order:
+//! whichever ran first would be built without code it relies on. Cycle
+//! members are blocked instead, and so is every feature that depends on a
+//! blocked or failed one.
+
+use super::unknown_graph::strongly_connected;
+use std::collections::HashMap;
+
+/// Dependencies between the features of a batch, by index into the batch
+#[derive(Debug, Clone)]
+pub struct FeatureGraph {
+    /// Dependencies inside the batch (self-references dropped)
+    deps: Vec<Vec<usize>>,
+    /// Dependencies on features outside the batch, by ID
+    external: Vec<Vec<String>>,
+    /// Members of each cycle, in batch order
+    cycles: Vec<Vec<usize>>,
+}
+
+impl FeatureGraph {
+    /// Build the graph from each feature's ID and `depends_on`
+    pub fn new(features: &[(String, Vec<String>)]) -> Self {
+        let index: HashMap<&str, usize> = features
+            .iter()
+            .enumerate()
+            .map(|(i, (id, _))| (id.as_str(), i))
+            .collect();
+
+        let mut deps = Vec::with_capacity(features.len());
+        let mut external = Vec::with_capacity(features.len());
+        for (i, (_, depends_on)) in features.iter().enumerate() {
+            let mut inside = Vec::new();
+            let mut outside = Vec::new();
+            for id in depends_on {
+                match index.get(id.as_str()) {
+                    Some(&dep) if dep != i => inside.push(dep),
+                    Some(_) => {}
+                    None => outside.push(id.clone()),
+                }
+            }
+            inside.sort_unstable();
+            inside.dedup();
+            outside.sort();
+            outside.dedup();
+            deps.push(inside);
+            external.push(outside);
+        }
+
+        let mut cycles: Vec<Vec<usize>> = strongly_connected(&deps)
+            .into_iter()
+            .filter(|members| members.len() > 1)
+            .map(|mut members| {
+                members.sort_unstable();
+                members
+            })
+            .collect();
+        cycles.sort();
+
+        Self {
+            deps,
+            external,
+            cycles,
+        }
+    }
+
+    /// Features of the batch that must be merged before `index` starts
+    pub fn dependencies(&self, index: usize) -> &[usize] {
+        &self.deps[index]
+    }
+
+    /// Features outside the batch that `index` depends on
+    pub fn external(&self, index: usize) -> &[String] {
+        &self.external[index]
+    }
+
+    /// Dependency cycles found in the batch
+    pub fn cycles(&self) -> &[Vec<usize>] {
+        &self.cycles
+    }
+
+    /// Features that depend on `index`, directly or through others, in batch order
+    pub fn dependents(&self, index: usize) -> Vec<usize> {
+        let mut found = vec![false; self.deps.len()];
+        let mut queue = vec![index];
+        while let Some(node) = queue.pop() {
+            for (i, deps) in self.deps.iter().enumerate() {
+                if !found[i] && i != index && deps.contains(&node) {
+                    found[i] = true;
+                    queue.push(i);
+                }
+            }
+        }
+        (0..found.len()).filter(|&i| found[i]).collect()
+    }
+}
+
+/// Where a feature of the batch stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeatureStatus {
+    Pending,
+    Running,
+    Merged,
+    Failed,
+    Blocked,
+}
+
+/// Tracks which features of a batch may start as others finish
+#[derive(Debug, Clone)]
+pub struct FeatureSchedule {
+    graph: FeatureGraph,
+    status: Vec<FeatureStatus>,
+}
+
+impl FeatureSchedule {
+    pub fn new(graph: FeatureGraph) -> Self {
+        let status = vec![FeatureStatus::Pending; graph.deps.len()];
+        Self { graph, status }
+    }
+
+    pub fn graph(&self) -> &FeatureGraph {
+        &self.graph
+    }
+
+    pub fn status(&self, index: usize) -> FeatureStatus {
+        self.status[index]
+    }
+
+    /// Pending features whose dependencies are all merged, now marked running
+    pub fn start_ready(&mut self) -> Vec<usize> {
+        let ready: Vec<usize> = (0..self.status.len())
+            .filter(|&i| {
+                self.status[i] == FeatureStatus::Pending
+                    && self.graph.deps[i]
+                        .iter()
+                        .all(|&dep| self.status[dep] == FeatureStatus::Merged)
+            })
+            .collect();
+        for &i in &ready {
+            self.status[i] = FeatureStatus::Running;
+        }
+        ready
+    }
+
+    /// Record how a running feature ended
+    ///
+    /// Returns the pending features blocked because it did not merge.
+    pub fn finish(&mut self, index: usize, merged: bool) -> Vec<usize> {
+        if merged {
+            self.status[index] = FeatureStatus::Merged;
+            Vec::new()
+        } else {
+            self.status[index] = FeatureStatus::Failed;
+            self.block_dependents(index)
+        }
+    }
+
+    /// Block pending features
+    ///
+    /// Returns the pending features blocked along with them (their dependents).
+    pub fn block(&mut self, indices: &[usize]) -> Vec<usize> {
+        for &i in indices {
+            if self.status[i] == FeatureStatus::Pending {
+                self.status[i] = FeatureStatus::Blocked;
+            }
+        }
+        let mut blocked: Vec<usize> = indices
+            .iter()
+            .flat_map(|&i| self.block_dependents(i))
+            .collect();
+        blocked.sort_unstable();
+        blocked
+    }
+
+    fn block_dependents(&mut self, index: usize) -> Vec<usize> {
+        let blocked: Vec<usize> = self
+            .graph
+            .dependents(index)
+            .into_iter()
+            .filter(|&i| self.status[i] == FeatureStatus::Pending)
+            .collect();
+        for &i in &blocked {
+            self.status[i] = FeatureStatus::Blocked;
+        }
+        blocked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(features: &[(&str, &[&str])]) -> FeatureGraph {
+        FeatureGraph::new(
+            &features
+                .iter()
+                .map(|(id, deps)| (id.to_string(), deps.iter().map(|d| d.to_string()).collect()))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    #[test]
+    fn test_graph_cycles_and_external_dependencies() {
+        let graph = graph(&[
+            ("f-a", &["f-c"]),
+            ("f-b", &["f-a"]),
+            ("f-c", &["f-b", "f-c"]),
+            ("f-d", &["f-c", "f-merged"]),
+            ("f-e", &[]),
+        ]);
+
+        assert_eq!(graph.cycles(), &[vec![0, 1, 2]]);
+        assert_eq!(graph.dependencies(2), &[1]);
+        assert_eq!(graph.external(3), &["f-merged".to_string()]);
+        assert_eq!(graph.dependents(0), vec![1, 2, 3]);
+        assert!(graph.dependents(4).is_empty());
+    }
+
+    #[test]
+    fn test_schedule_waits_for_merges_and_cascades_failures() {
+        let mut schedule = FeatureSchedule::new(graph(&[
+            ("f-model", &[]),
+            ("f-api", &["f-model"]),
+            ("f-ui", &["f-api"]),
+            ("f-docs", &[]),
+            ("f-cli", &["f-model"]),
+        ]));
+
+        assert_eq!(schedule.start_ready(), vec![0, 3]);
+        assert!(schedule.start_ready().is_empty());
+
+        assert!(schedule.finish(0, true).is_empty());
+        assert_eq!(schedule.start_ready(), vec![1, 4]);
+
+        // A failed prerequisite blocks everything downstream of it
+        assert_eq!(schedule.finish(1, false), vec![2]);
+        assert_eq!(schedule.status(2), FeatureStatus::Blocked);
+        assert!(schedule.start_ready().is_empty());
+
+        assert!(schedule.finish(3, true).is_empty());
+        assert!(schedule.finish(4, true).is_empty());
+        assert!(schedule.start_ready().is_empty());
+    }
+
+    #[test]
+    fn test_blocking_a_cycle_blocks_its_dependents() {
+        let mut schedule = FeatureSchedule::new(graph(&[
+            ("f-a", &["f-b"]),
+            ("f-b", &["f-a"]),
+            ("f-c", &["f-b"]),
+            ("f-d", &[]),
+        ]));
+
+        let cycle = schedule.graph().cycles()[0].clone();
+        assert_eq!(schedule.block(&cycle), vec![2]);
+        assert_eq!(schedule.status(0), FeatureStatus::Blocked);
+        assert_eq!(schedule.status(1), FeatureStatus::Blocked);
+        assert_eq!(schedule.start_ready(), vec![3]);
+    }
+}
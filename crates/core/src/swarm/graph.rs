@@ -0,0 +1,865 @@
+//! # Pipeline Graphs
+//!
+//! The stages of a run as a graph of skills, loaded from
+//! `.catalyst/pipeline.toml` when the project has one and checked before a
+//! run starts.
+//!
+//! ```toml
+//! start = "parse"
+//!
+//! [[stage]]
+//! id = "parse"
+//! skill = "parse"
+//! next = "research"
+//!
+//! [[stage]]
+//! id = "research"
+//! skill = "research"
+//! next = "architect"
+//!
+//! [[stage]]
+//! id = "architect"
+//! skill = "architect"
+//! next = "critic"
+//!
+//! [[stage]]
+//! id = "critic"
+//! skill = "review"
+//! next = "atomize"
+//! on_reject = { goto = "architect", max_iterations = 3 }
+//!
+//! # Blockers also get a security review
+//! [[stage.branch]]
+//! when = { criticality = ["BLOCKER"] }
+//! goto = "security"
+//!
+//! [[stage]]
+//! id = "security"
+//! skill = "review"
+//! agent = "security_critic"
+//! focus = "security"
+//! approval = true
+//! next = "atomize"
+//! on_reject = { goto = "architect", max_iterations = 2 }
+//!
+//! [[stage]]
+//! id = "atomize"
+//! skill = "atomize"
+//! next = "mission"
+//!
+//! [[stage]]
+//! id = "mission"
+//! skill = "mission"
+//! ```
+//!
+//! A run parses the goal into unknowns, walks every unknown through the
+//! research, architect and review stages on its path, then plans the feature
+//! with the atomize and mission stages. Branches are taken when their
+//! condition (project mode, or the unknown's criticality and category) holds;
+//! a review that rejects a decision sends it back to an architect stage until
+//! its `max_iterations` are used up.
+
+use crate::skills::parse_skill::{Ambiguity, AmbiguityCategory, Criticality};
+use crate::state::io::get_runtime_path;
+use crate::state::ProjectMode;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use super::pipeline::PipelineStage;
+
+/// Name of the pipeline definition under `.catalyst/`
+pub const PIPELINE_FILE: &str = "pipeline.toml";
+
+/// Skill a stage runs, by skill ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StageSkill {
+    /// Unknowns Parser (run once, first)
+    Parse,
+    /// Researcher (per unknown)
+    Research,
+    /// Architect decision (per unknown)
+    Architect,
+    /// Critic review of the decision (per unknown)
+    Review,
+    /// Atomizer plan of the feature
+    Atomize,
+    /// Taskmaster mission prompt
+    Mission,
+}
+
+impl StageSkill {
+    pub fn id(&self) -> &'static str {
+        match self {
+            Self::Parse => "parse",
+            Self::Research => "research",
+            Self::Architect => "architect",
+            Self::Review => "review",
+            Self::Atomize => "atomize",
+            Self::Mission => "mission",
+        }
+    }
+
+    /// Agent whose model settings a stage uses unless it names its own
+    pub fn agent(&self) -> &'static str {
+        match self {
+            Self::Parse => "unknowns_parser",
+            Self::Research => "researcher",
+            Self::Architect => "architect",
+            Self::Review => "critic",
+            Self::Atomize => "atomizer",
+            Self::Mission => "taskmaster",
+        }
+    }
+
+    /// Whether the skill runs once per unknown
+    pub fn per_unknown(&self) -> bool {
+        matches!(self, Self::Research | Self::Architect | Self::Review)
+    }
+
+    /// Status stage reported while the skill runs
+    pub fn pipeline_stage(&self) -> PipelineStage {
+        match self {
+            Self::Parse => PipelineStage::UnknownsParsing,
+            Self::Research => PipelineStage::Researching,
+            Self::Architect => PipelineStage::Architecting,
+            Self::Review => PipelineStage::Critiquing,
+            Self::Atomize => PipelineStage::Atomizing,
+            Self::Mission => PipelineStage::TaskGeneration,
+        }
+    }
+
+    /// Position in the run; edges may only lead to later skills
+    fn rank(&self) -> u8 {
+        match self {
+            Self::Parse => 0,
+            Self::Research => 1,
+            Self::Architect => 2,
+            Self::Review => 3,
+            Self::Atomize => 4,
+            Self::Mission => 5,
+        }
+    }
+}
+
+/// When a branch is taken; every listed field has to match
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Condition {
+    /// Project modes the branch applies in
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mode: Vec<ProjectMode>,
+    /// Criticalities of the unknown
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub criticality: Vec<Criticality>,
+    /// Categories of the unknown
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub category: Vec<AmbiguityCategory>,
+}
+
+impl Condition {
+    pub fn matches(&self, context: &StageContext) -> bool {
+        let unknown = |matches: &dyn Fn(&Ambiguity) -> bool| context.ambiguity.is_some_and(matches);
+        (self.mode.is_empty() || self.mode.contains(&context.mode))
+            && (self.criticality.is_empty()
+                || unknown(&|a| self.criticality.contains(&a.criticality)))
+            && (self.category.is_empty() || unknown(&|a| self.category.contains(&a.category)))
+    }
+
+    /// Whether the condition looks at an unknown
+    fn about_unknown(&self) -> bool {
+        !self.criticality.is_empty() || !self.category.is_empty()
+    }
+}
+
+/// Conditional edge to another stage
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Branch {
+    pub when: Condition,
+    pub goto: String,
+}
+
+/// Where a review stage sends a decision it rejects
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RejectLoop {
+    /// Architect stage that decides again
+    pub goto: String,
+    /// Rejections by this stage before the unknown is left undecided
+    pub max_iterations: u32,
+}
+
+/// One stage of a pipeline graph
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StageSpec {
+    pub id: String,
+    pub skill: StageSkill,
+    /// Agent whose model settings to use (the skill's own agent by default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+    /// What a review concentrates on, e.g. "security"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub focus: Option<String>,
+    /// Ask the user to approve the stage's output before moving on
+    #[serde(default)]
+    pub approval: bool,
+    /// Taken instead of `next` when their condition holds (first match wins)
+    #[serde(default, rename = "branch", skip_serializing_if = "Vec::is_empty")]
+    pub branches: Vec<Branch>,
+    /// Following stage; the walk ends after this stage when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_reject: Option<RejectLoop>,
+}
+
+impl StageSpec {
+    fn new(id: &str, skill: StageSkill, next: Option<&str>) -> Self {
+        Self {
+            id: id.to_string(),
+            skill,
+            agent: None,
+            focus: None,
+            approval: false,
+            branches: Vec::new(),
+            next: next.map(str::to_string),
+            on_reject: None,
+        }
+    }
+
+    /// Agent whose model settings this stage uses
+    pub fn agent(&self) -> &str {
+        self.agent.as_deref().unwrap_or(self.skill.agent())
+    }
+
+    /// Every stage this one can lead to (`None` ends the walk), rejections aside
+    fn targets(&self) -> impl Iterator<Item = Option<&str>> {
+        self.branches
+            .iter()
+            .map(|b| Some(b.goto.as_str()))
+            .chain(std::iter::once(self.next.as_deref()))
+    }
+}
+
+/// What branch conditions are checked against
+#[derive(Debug, Clone, Copy)]
+pub struct StageContext<'a> {
+    pub mode: ProjectMode,
+    /// Unknown being walked, if any
+    pub ambiguity: Option<&'a Ambiguity>,
+}
+
+/// A run's stages and the edges between them
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PipelineGraph {
+    /// ID of the first stage
+    pub start: String,
+    #[serde(rename = "stage")]
+    pub stages: Vec<StageSpec>,
+}
+
+impl Default for PipelineGraph {
+    fn default() -> Self {
+        Self::standard(3, false)
+    }
+}
+
+impl PipelineGraph {
+    /// Parse → research → architect ⟷ critic → atomize → mission
+    pub fn standard(max_rejections: u32, architect_approval: bool) -> Self {
+        let mut architect = StageSpec::new("architect", StageSkill::Architect, Some("critic"));
+        architect.approval = architect_approval;
+        let mut critic = StageSpec::new("critic", StageSkill::Review, Some("atomize"));
+        critic.on_reject = Some(RejectLoop {
+            goto: "architect".to_string(),
+            max_iterations: max_rejections.max(1),
+        });
+        Self {
+            start: "parse".to_string(),
+            stages: vec![
+                StageSpec::new("parse", StageSkill::Parse, Some("research")),
+                StageSpec::new("research", StageSkill::Research, Some("architect")),
+                architect,
+                critic,
+                StageSpec::new("atomize", StageSkill::Atomize, Some("mission")),
+                StageSpec::new("mission", StageSkill::Mission, None),
+            ],
+        }
+    }
+
+    /// Parse and validate a TOML definition
+    pub fn from_toml(source: &str) -> Result<Self> {
+        let graph: Self = toml::from_str(source).context("Invalid pipeline definition")?;
+        graph.validate()?;
+        Ok(graph)
+    }
+
+    /// Load and validate the definition at `path`, if there is one
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let source = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::from_toml(&source)
+            .with_context(|| format!("Invalid pipeline in {}", path.display()))
+            .map(Some)
+    }
+
+    /// Load the project's `.catalyst/pipeline.toml`, if there is one
+    pub fn load_project() -> Result<Option<Self>> {
+        Self::load(&get_runtime_path().join(PIPELINE_FILE))
+    }
+
+    pub fn stage(&self, id: &str) -> Option<&StageSpec> {
+        self.stages.iter().find(|s| s.id == id)
+    }
+
+    /// Stage `stage` leads to in `context` (`None` ends the walk)
+    pub fn next(&self, stage: &StageSpec, context: &StageContext) -> Option<&StageSpec> {
+        let target = stage
+            .branches
+            .iter()
+            .find(|b| b.when.matches(context))
+            .map(|b| b.goto.as_str())
+            .or(stage.next.as_deref())?;
+        self.stage(target)
+    }
+
+    /// The Unknowns Parser stage, when the graph starts with one
+    pub fn parse_stage(&self) -> Option<&StageSpec> {
+        self.stage(&self.start)
+            .filter(|s| s.skill == StageSkill::Parse)
+    }
+
+    /// First stage an unknown goes through (research or architect)
+    pub fn unknown_entry(&self, context: &StageContext) -> Option<&StageSpec> {
+        self.next(self.parse_stage()?, context)
+            .filter(|s| s.skill.per_unknown())
+    }
+
+    /// Stage after `stage` for this unknown, `None` once it leaves the unknown stages
+    pub fn unknown_next(&self, stage: &StageSpec, context: &StageContext) -> Option<&StageSpec> {
+        self.next(stage, context).filter(|s| s.skill.per_unknown())
+    }
+
+    /// First run-level stage after the unknowns (`None` ends the run there)
+    ///
+    /// Validation makes every unknown leave for the same stage.
+    pub fn after_unknowns(&self) -> Option<&StageSpec> {
+        let Some(parse) = self.parse_stage() else {
+            return self.stage(&self.start);
+        };
+        let exits = self.unknown_exits(parse);
+        exits
+            .into_iter()
+            .next()
+            .flatten()
+            .and_then(|id| self.stage(id))
+    }
+
+    /// Run-level targets (`None` = end) reached when leaving the unknown stages
+    fn unknown_exits<'a>(&'a self, parse: &'a StageSpec) -> Vec<Option<&'a str>> {
+        let mut exits = Vec::new();
+        let sources =
+            std::iter::once(parse).chain(self.stages.iter().filter(|s| s.skill.per_unknown()));
+        for source in sources {
+            for target in source.targets() {
+                let leaves = match target.and_then(|id| self.stage(id)) {
+                    Some(stage) => !stage.skill.per_unknown(),
+                    None => target.is_none(),
+                };
+                if leaves && !exits.contains(&target) {
+                    exits.push(target);
+                }
+            }
+        }
+        exits
+    }
+
+    /// Check the graph is one the coordinator can run
+    ///
+    /// Every problem found is reported in one error.
+    pub fn validate(&self) -> Result<()> {
+        let errors = self.problems();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            anyhow::bail!("{}", errors.join("; "))
+        }
+    }
+
+    fn problems(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        if self.stages.is_empty() {
+            return vec!["pipeline has no stages".to_string()];
+        }
+
+        let mut ids = HashSet::new();
+        for stage in &self.stages {
+            if stage.id.trim().is_empty() {
+                errors.push("stage with an empty id".to_string());
+            } else if !ids.insert(stage.id.as_str()) {
+                errors.push(format!("duplicate stage '{}'", stage.id));
+            }
+        }
+        if self.stage(&self.start).is_none() {
+            errors.push(format!("start stage '{}' does not exist", self.start));
+        }
+
+        // Edges: known targets, always moving on to a later skill
+        for stage in &self.stages {
+            for target in stage.targets().flatten() {
+                let Some(to) = self.stage(target) else {
+                    errors.push(format!(
+                        "stage '{}' leads to unknown stage '{}'",
+                        stage.id, target
+                    ));
+                    continue;
+                };
+                let review_chain =
+                    stage.skill == StageSkill::Review && to.skill == StageSkill::Review;
+                if to.skill.rank() <= stage.skill.rank() && !review_chain {
+                    errors.push(format!(
+                        "stage '{}' ({}) cannot lead to '{}' ({})",
+                        stage.id,
+                        stage.skill.id(),
+                        to.id,
+                        to.skill.id()
+                    ));
+                }
+            }
+            let unknown_edges = stage.skill == StageSkill::Parse || stage.skill.per_unknown();
+            if !unknown_edges && stage.branches.iter().any(|b| b.when.about_unknown()) {
+                errors.push(format!(
+                    "stage '{}' branches on an unknown, but runs once per run",
+                    stage.id
+                ));
+            }
+            if stage.approval && stage.skill == StageSkill::Research {
+                errors.push(format!(
+                    "stage '{}': research stages cannot have approval gates",
+                    stage.id
+                ));
+            }
+            if stage.skill == StageSkill::Parse && stage.id != self.start {
+                errors.push(format!(
+                    "parse stage '{}' must be the start stage",
+                    stage.id
+                ));
+            }
+        }
+        if !errors.is_empty() {
+            // The checks below follow edges, so they need a well-formed graph
+            return errors;
+        }
+
+        if let Some(cycle) = self.review_cycle() {
+            errors.push(format!("review stages form a cycle: {}", cycle.join(" → ")));
+            return errors;
+        }
+
+        // Rejection loops go back to an architect stage upstream
+        for stage in &self.stages {
+            let Some(on_reject) = &stage.on_reject else {
+                continue;
+            };
+            if stage.skill != StageSkill::Review {
+                errors.push(format!(
+                    "stage '{}': only review stages can reject",
+                    stage.id
+                ));
+                continue;
+            }
+            if on_reject.max_iterations == 0 {
+                errors.push(format!(
+                    "stage '{}': max_iterations must be at least 1",
+                    stage.id
+                ));
+            }
+            match self.stage(&on_reject.goto) {
+                Some(target) if target.skill == StageSkill::Architect => {
+                    if !self.reaches(&target.id, &stage.id) {
+                        errors.push(format!(
+                            "stage '{}' rejects to '{}', which does not lead to it",
+                            stage.id, target.id
+                        ));
+                    }
+                }
+                Some(target) => errors.push(format!(
+                    "stage '{}' rejects to '{}', which is not an architect stage",
+                    stage.id, target.id
+                )),
+                None => errors.push(format!(
+                    "stage '{}' rejects to unknown stage '{}'",
+                    stage.id, on_reject.goto
+                )),
+            }
+        }
+
+        // A review needs a decision on every path into it, and an unknown
+        // may only leave its stages once it has one
+        let decided = self.decided();
+        let predecessors = self.predecessors();
+        for stage in self.stages.iter().filter(|s| s.skill == StageSkill::Review) {
+            if !decided[stage.id.as_str()] {
+                errors.push(format!(
+                    "review stage '{}' can be reached without an architect decision",
+                    stage.id
+                ));
+            }
+        }
+        for stage in self.stages.iter().filter(|s| s.skill.per_unknown()) {
+            let leaves = stage.targets().any(|target| {
+                target
+                    .and_then(|id| self.stage(id))
+                    .is_none_or(|to| !to.skill.per_unknown())
+            });
+            if leaves && !decided[stage.id.as_str()] {
+                errors.push(format!(
+                    "unknowns can leave stage '{}' without an architect decision",
+                    stage.id
+                ));
+            }
+        }
+
+        let has_unknown_stages = self.stages.iter().any(|s| s.skill.per_unknown());
+        match self.parse_stage() {
+            Some(parse) if has_unknown_stages => {
+                if parse.targets().any(|t| {
+                    t.and_then(|id| self.stage(id))
+                        .is_none_or(|s| !s.skill.per_unknown())
+                }) {
+                    errors.push(format!(
+                        "parse stage '{}' must lead to research or architect stages",
+                        parse.id
+                    ));
+                }
+                let exits = self.unknown_exits(parse);
+                if exits.len() > 1 {
+                    let listed: Vec<&str> = exits.iter().map(|e| e.unwrap_or("end")).collect();
+                    errors.push(format!(
+                        "unknowns must all continue to the same stage, not {}",
+                        listed.join(" or ")
+                    ));
+                }
+            }
+            Some(parse) => errors.push(format!(
+                "parse stage '{}' has no research or architect stages after it",
+                parse.id
+            )),
+            None if has_unknown_stages => errors.push(
+                "research, architect and review stages need a parse stage to start from"
+                    .to_string(),
+            ),
+            None => {}
+        }
+
+        for stage in self
+            .stages
+            .iter()
+            .filter(|s| s.skill == StageSkill::Mission)
+        {
+            let from = &predecessors[stage.id.as_str()];
+            if stage.id == self.start || from.iter().any(|p| p.skill != StageSkill::Atomize) {
+                errors.push(format!(
+                    "mission stage '{}' must follow an atomize stage",
+                    stage.id
+                ));
+            }
+        }
+
+        let reachable = self.reachable();
+        for stage in &self.stages {
+            if !reachable.contains(stage.id.as_str()) {
+                errors.push(format!(
+                    "stage '{}' is unreachable from '{}'",
+                    stage.id, self.start
+                ));
+            }
+        }
+
+        errors
+    }
+
+    /// Stages with an edge into each stage
+    fn predecessors(&self) -> HashMap<&str, Vec<&StageSpec>> {
+        let mut predecessors: HashMap<&str, Vec<&StageSpec>> = self
+            .stages
+            .iter()
+            .map(|s| (s.id.as_str(), Vec::new()))
+            .collect();
+        for stage in &self.stages {
+            for target in stage.targets().flatten() {
+                if let Some(from) = predecessors.get_mut(target) {
+                    if !from.iter().any(|p| p.id == stage.id) {
+                        from.push(stage);
+                    }
+                }
+            }
+        }
+        predecessors
+    }
+
+    /// Whether every path into (and through) each stage passes an architect
+    fn decided(&self) -> HashMap<&str, bool> {
+        let predecessors = self.predecessors();
+        let mut order: Vec<&StageSpec> = self.stages.iter().collect();
+        // Ranks only grow along edges, and review chains are acyclic, so
+        // repeated passes in rank order settle every stage
+        order.sort_by_key(|s| s.skill.rank());
+        let mut decided: HashMap<&str, bool> =
+            self.stages.iter().map(|s| (s.id.as_str(), false)).collect();
+        for _ in 0..self.stages.len() {
+            for stage in &order {
+                let value = match stage.skill {
+                    StageSkill::Architect => true,
+                    StageSkill::Review => {
+                        let from = &predecessors[stage.id.as_str()];
+                        !from.is_empty() && from.iter().all(|p| decided[p.id.as_str()])
+                    }
+                    _ => false,
+                };
+                decided.insert(stage.id.as_str(), value);
+            }
+        }
+        decided
+    }
+
+    /// Stages reachable from the start (rejection loops included)
+    fn reachable(&self) -> HashSet<&str> {
+        let mut seen = HashSet::new();
+        let mut stack = vec![self.start.as_str()];
+        while let Some(id) = stack.pop() {
+            let Some(stage) = self.stage(id) else {
+                continue;
+            };
+            if !seen.insert(stage.id.as_str()) {
+                continue;
+            }
+            stack.extend(stage.targets().flatten());
+            if let Some(on_reject) = &stage.on_reject {
+                stack.push(on_reject.goto.as_str());
+            }
+        }
+        seen
+    }
+
+    /// Whether `to` can be reached from `from` without a rejection
+    fn reaches(&self, from: &str, to: &str) -> bool {
+        let mut seen = HashSet::new();
+        let mut stack = vec![from];
+        while let Some(id) = stack.pop() {
+            if id == to {
+                return true;
+            }
+            if seen.insert(id) {
+                if let Some(stage) = self.stage(id) {
+                    stack.extend(stage.targets().flatten());
+                }
+            }
+        }
+        false
+    }
+
+    /// Review stages that lead back to themselves
+    fn review_cycle(&self) -> Option<Vec<String>> {
+        for stage in self.stages.iter().filter(|s| s.skill == StageSkill::Review) {
+            for target in stage.targets().flatten() {
+                if self.reaches(target, &stage.id) {
+                    return Some(vec![stage.id.clone(), target.to_string(), stage.id.clone()]);
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ambiguity(criticality: Criticality) -> Ambiguity {
+        Ambiguity {
+            id: "U-1".to_string(),
+            category: AmbiguityCategory::Security,
+            question: "Auth?".to_string(),
+            criticality,
+            context: None,
+            depends_on: Vec::new(),
+        }
+    }
+
+    const SECURITY_REVIEW: &str = r#"
+        start = "parse"
+
+        [[stage]]
+        id = "parse"
+        skill = "parse"
+        next = "architect"
+
+        [[stage.branch]]
+        when = { criticality = ["BLOCKER", "HIGH"] }
+        goto = "research"
+
+        [[stage]]
+        id = "research"
+        skill = "research"
+        next = "architect"
+
+        [[stage]]
+        id = "architect"
+        skill = "architect"
+        next = "critic"
+
+        [[stage]]
+        id = "critic"
+        skill = "review"
+        next = "atomize"
+        on_reject = { goto = "architect", max_iterations = 2 }
+
+        [[stage.branch]]
+        when = { mode = ["fortress"], category = ["Security"] }
+        goto = "security"
+
+        [[stage]]
+        id = "security"
+        skill = "review"
+        agent = "security_critic"
+        focus = "security"
+        approval = true
+        next = "atomize"
+        on_reject = { goto = "architect", max_iterations = 1 }
+
+        [[stage]]
+        id = "atomize"
+        skill = "atomize"
+        next = "mission"
+
+        [[stage]]
+        id = "mission"
+        skill = "mission"
+    "#;
+
+    #[test]
+    fn test_standard_graph_is_valid() {
+        let graph = PipelineGraph::standard(3, true);
+        graph.validate().unwrap();
+        assert_eq!(graph.parse_stage().unwrap().id, "parse");
+        assert_eq!(graph.after_unknowns().unwrap().id, "atomize");
+        assert!(graph.stage("architect").unwrap().approval);
+    }
+
+    #[test]
+    fn test_branches_follow_mode_and_unknown() {
+        let graph = PipelineGraph::from_toml(SECURITY_REVIEW).unwrap();
+        let low = ambiguity(Criticality::Low);
+        let blocker = ambiguity(Criticality::Blocker);
+        let lab = |a| StageContext {
+            mode: ProjectMode::Lab,
+            ambiguity: Some(a),
+        };
+
+        // Only critical unknowns are researched
+        assert_eq!(graph.unknown_entry(&lab(&low)).unwrap().id, "architect");
+        assert_eq!(graph.unknown_entry(&lab(&blocker)).unwrap().id, "research");
+
+        // Security unknowns get a second review in fortress mode
+        let critic = graph.stage("critic").unwrap();
+        assert!(graph.unknown_next(critic, &lab(&low)).is_none());
+        let fortress = StageContext {
+            mode: ProjectMode::Fortress,
+            ambiguity: Some(&low),
+        };
+        let security = graph.unknown_next(critic, &fortress).unwrap();
+        assert_eq!(security.agent(), "security_critic");
+        assert_eq!(graph.after_unknowns().unwrap().id, "atomize");
+    }
+
+    #[test]
+    fn test_invalid_graphs_are_rejected_at_load() {
+        let bad_skill = SECURITY_REVIEW.replace("skill = \"mission\"", "skill = \"deploy\"");
+        assert!(PipelineGraph::from_toml(&bad_skill).is_err());
+
+        let dangling = SECURITY_REVIEW.replace("goto = \"security\"", "goto = \"audit\"");
+        let error = PipelineGraph::from_toml(&dangling).unwrap_err();
+        assert!(format!("{:#}", error).contains("unknown stage 'audit'"));
+
+        // Reviewing before deciding, and looping back to a non-architect
+        let graph = PipelineGraph::from_toml(
+            r#"
+            start = "parse"
+            [[stage]]
+            id = "parse"
+            skill = "parse"
+            next = "critic"
+            [[stage]]
+            id = "critic"
+            skill = "review"
+            on_reject = { goto = "parse", max_iterations = 0 }
+            "#,
+        );
+        let error = format!("{:#}", graph.unwrap_err());
+        assert!(error.contains("without an architect decision"));
+        assert!(error.contains("max_iterations must be at least 1"));
+        assert!(error.contains("not an architect stage"));
+
+        // Going backwards is only possible through a rejection
+        let backwards = SECURITY_REVIEW.replace(
+            "id = \"mission\"\n        skill = \"mission\"",
+            "id = \"mission\"\n        skill = \"mission\"\n        next = \"architect\"",
+        );
+        let error = format!("{:#}", PipelineGraph::from_toml(&backwards).unwrap_err());
+        assert!(error.contains("cannot lead to 'architect'"));
+    }
+
+    #[test]
+    fn test_graph_without_research_or_unknowns() {
+        // Internal tools: decide straight away
+        let graph = PipelineGraph::from_toml(
+            r#"
+            start = "parse"
+            [[stage]]
+            id = "parse"
+            skill = "parse"
+            next = "architect"
+            [[stage]]
+            id = "architect"
+            skill = "architect"
+            next = "atomize"
+            [[stage]]
+            id = "atomize"
+            skill = "atomize"
+            "#,
+        )
+        .unwrap();
+        let context = StageContext {
+            mode: ProjectMode::Lab,
+            ambiguity: None,
+        };
+        assert_eq!(graph.unknown_entry(&context).unwrap().id, "architect");
+        assert_eq!(graph.after_unknowns().unwrap().id, "atomize");
+
+        // Planning only
+        let graph = PipelineGraph::from_toml(
+            r#"
+            start = "atomize"
+            [[stage]]
+            id = "atomize"
+            skill = "atomize"
+            next = "mission"
+            [[stage]]
+            id = "mission"
+            skill = "mission"
+            "#,
+        )
+        .unwrap();
+        assert!(graph.parse_stage().is_none());
+        assert_eq!(graph.after_unknowns().unwrap().id, "atomize");
+    }
+}
@@ -10,17 +10,33 @@
 
 pub mod a2a_bridge;
 pub mod architecture_generator;
+pub mod budget;
+pub mod consensus;
+pub mod control;
 pub mod coordinator;
 pub mod events;
+pub mod feature_graph;
+pub mod graph;
 pub mod init;
 pub mod pipeline;
+pub mod policy;
+pub mod transcripts;
+pub mod unknown_graph;
 
 pub use a2a_bridge::{
     spawn_research_agent, ResearchAgentHandle, ResearchMission, ResearchProgress,
 };
+pub use budget::{Budget, BudgetAction, UsageMeter};
+pub use consensus::{PanelCritic, Quorum, ReviewPanel};
+pub use control::{is_cancelled, Cancelled, RunControl};
 pub use coordinator::{
     ApprovalRequest, ApprovalResponse, Coordinator, CoordinatorCommand, CoordinatorConfig,
 };
-pub use events::{SwarmEvent, SwarmEventKind};
+pub use events::{LlmEventForwarder, SwarmEvent, SwarmEventKind};
+pub use feature_graph::{FeatureGraph, FeatureSchedule, FeatureStatus};
+pub use graph::{PipelineGraph, StageSkill, StageSpec};
 pub use init::{detect_project, initialize_project, ScanProgress};
 pub use pipeline::{Pipeline, PipelineStage};
+pub use policy::ModePolicy;
+pub use transcripts::TranscriptRecorder;
+pub use unknown_graph::UnknownGraph;
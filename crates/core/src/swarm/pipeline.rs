@@ -1,9 +1,12 @@
 //! # Pipeline Stages
 //!
-//! Defines the stages of the agent pipeline.
+//! Defines the stages of the agent pipeline and tracks a run's position in
+//! its [`PipelineGraph`].
 
 use serde::{Deserialize, Serialize};
 
+use super::graph::{PipelineGraph, StageSpec};
+
 /// Stage of the pipeline
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -35,15 +38,15 @@ pub struct Pipeline {
     pub critic_rejections: u32,
     /// Maximum critic rejections before failing
     pub max_rejections: u32,
+    /// Stages the run goes through
+    graph: PipelineGraph,
+    /// ID of the graph stage the run is in
+    current: Option<String>,
 }
 
 impl Default for Pipeline {
     fn default() -> Self {
-        Self {
-            stage: PipelineStage::UnknownsParsing,
-            critic_rejections: 0,
-            max_rejections: 3,
-        }
+        Self::from_graph(PipelineGraph::default())
     }
 }
 
@@ -53,18 +56,55 @@ impl Pipeline {
         Self::default()
     }
 
-    /// Advance to the next stage
-    pub fn advance(&mut self) {
-        self.stage = match self.stage {
-            PipelineStage::UnknownsParsing => PipelineStage::Researching,
-            PipelineStage::Researching => PipelineStage::Architecting,
-            PipelineStage::Architecting => PipelineStage::Critiquing,
-            PipelineStage::Critiquing => PipelineStage::Atomizing,
-            PipelineStage::Atomizing => PipelineStage::TaskGeneration,
-            PipelineStage::TaskGeneration => PipelineStage::Complete,
-            PipelineStage::Complete => PipelineStage::Complete,
-            PipelineStage::Failed => PipelineStage::Failed,
+    /// Pipeline positioned at the start of `graph`
+    pub fn from_graph(graph: PipelineGraph) -> Self {
+        let mut pipeline = Self {
+            stage: PipelineStage::Complete,
+            critic_rejections: 0,
+            max_rejections: 3,
+            current: None,
+            graph,
         };
+        if let Some(start) = pipeline.graph.stage(&pipeline.graph.start).cloned() {
+            pipeline.enter(&start);
+        }
+        pipeline
+    }
+
+    pub fn graph(&self) -> &PipelineGraph {
+        &self.graph
+    }
+
+    /// ID of the graph stage the run is in, if it has not finished
+    pub fn current(&self) -> Option<&str> {
+        self.current.as_deref()
+    }
+
+    /// Move to a stage of the graph
+    pub fn enter(&mut self, stage: &StageSpec) {
+        self.current = Some(stage.id.clone());
+        self.stage = stage.skill.pipeline_stage();
+    }
+
+    /// Advance to the next stage along each stage's `next` edge
+    pub fn advance(&mut self) {
+        if matches!(self.stage, PipelineStage::Complete | PipelineStage::Failed) {
+            return;
+        }
+        let next = self
+            .current
+            .as_deref()
+            .and_then(|id| self.graph.stage(id))
+            .and_then(|stage| stage.next.as_deref())
+            .and_then(|id| self.graph.stage(id))
+            .cloned();
+        match next {
+            Some(stage) => self.enter(&stage),
+            None => {
+                self.current = None;
+                self.stage = PipelineStage::Complete;
+            }
+        }
     }
 
     /// Handle critic rejection - loop back to architect
@@ -111,6 +151,37 @@ mod tests {
         assert_eq!(pipeline.stage, PipelineStage::Architecting);
     }
 
+    #[test]
+    fn test_pipeline_follows_its_graph() {
+        let graph = PipelineGraph::from_toml(
+            r#"
+            start = "parse"
+            [[stage]]
+            id = "parse"
+            skill = "parse"
+            next = "architect"
+            [[stage]]
+            id = "architect"
+            skill = "architect"
+            next = "atomize"
+            [[stage]]
+            id = "atomize"
+            skill = "atomize"
+            "#,
+        )
+        .unwrap();
+        let mut pipeline = Pipeline::from_graph(graph);
+        assert_eq!(pipeline.current(), Some("parse"));
+
+        pipeline.advance();
+        assert_eq!(pipeline.stage, PipelineStage::Architecting);
+        pipeline.advance();
+        assert_eq!(pipeline.stage, PipelineStage::Atomizing);
+        pipeline.advance();
+        assert!(pipeline.is_success());
+        assert_eq!(pipeline.current(), None);
+    }
+
     #[test]
     fn test_critic_rejection_loop() {
         let mut pipeline = Pipeline::new();
@@ -0,0 +1,83 @@
+//! # Mode Policies
+//!
+//! What each [`ProjectMode`] changes about a run.
+//!
+//! - **Speed run** goes straight from the goal to planning: no Unknowns
+//!   Parser, so no research, decisions or critic loop, and no constraints on
+//!   the code.
+//! - **Lab** is the standard pipeline.
+//! - **Fortress** adds gates before a feature is merged: the changed files
+//!   must pass the [`ConstraintSkill`](crate::skills::ConstraintSkill) checks
+//!   with no `unwrap()`, and the Critic must approve the code diff. Before
+//!   the Builder starts, the Red Team writes hostile tests under `tests/`;
+//!   they must run and pass, unmodified, before the feature is merged.
+
+use crate::state::ProjectMode;
+use serde::Serialize;
+
+/// Concrete pipeline behaviour of a mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ModePolicy {
+    pub mode: ProjectMode,
+    /// Run the Unknowns Parser (and so research, Architect and Critic)
+    pub parse_unknowns: bool,
+    /// Block merges on file and function length violations
+    pub enforce_constraints: bool,
+    /// Count `unwrap()` in changed files as a blocking constraint violation
+    pub forbid_unwrap: bool,
+    /// Have the Red Team write hostile tests before each feature is built
+    ///
+    /// The Coordinator runs each test file with `cargo test --test` and does
+    /// not merge the feature until all of them pass as written.
+    pub red_team_tests: bool,
+    /// Have the Critic review the code diff before merging
+    pub critic_reviews_diffs: bool,
+}
+
+impl ModePolicy {
+    pub fn for_mode(mode: ProjectMode) -> Self {
+        let fortress = mode == ProjectMode::Fortress;
+        Self {
+            mode,
+            parse_unknowns: mode != ProjectMode::SpeedRun,
+            enforce_constraints: fortress,
+            forbid_unwrap: fortress,
+            red_team_tests: fortress,
+            critic_reviews_diffs: fortress,
+        }
+    }
+
+    /// Whether the constraint or Critic gate runs before a built feature is
+    /// merged (red-team tests are checked separately)
+    pub fn gates_merge(&self) -> bool {
+        self.enforce_constraints || self.critic_reviews_diffs
+    }
+}
+
+impl From<ProjectMode> for ModePolicy {
+    fn from(mode: ProjectMode) -> Self {
+        Self::for_mode(mode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_modes_map_to_policies() {
+        let speed = ModePolicy::for_mode(ProjectMode::SpeedRun);
+        assert!(!speed.parse_unknowns);
+        assert!(!speed.gates_merge());
+
+        let lab = ModePolicy::for_mode(ProjectMode::Lab);
+        assert!(lab.parse_unknowns);
+        assert!(!lab.gates_merge());
+        assert!(!lab.red_team_tests);
+
+        let fortress = ModePolicy::for_mode(ProjectMode::Fortress);
+        assert!(fortress.parse_unknowns);
+        assert!(fortress.enforce_constraints && fortress.forbid_unwrap);
+        assert!(fortress.critic_reviews_diffs && fortress.red_team_tests);
+    }
+}
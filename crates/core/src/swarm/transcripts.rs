@@ -0,0 +1,61 @@
+//! # Run Transcripts
+//!
+//! Transcript recording for a coordinator run.
+//!
+//! The coordinator hands a [`TranscriptRecorder`] to every `ModelConfig` it
+//! builds and tells it about each event it emits. Every LLM exchange is stored
+//! with the run ID and the ID of the latest event of the agent making the call,
+//! so a transcript can be traced back to the step that triggered it.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::models::{LlmExchange, TranscriptSink};
+use crate::state::TranscriptManager;
+
+use super::events::SwarmEvent;
+
+/// Transcript sink for a single run
+#[derive(Debug)]
+pub struct TranscriptRecorder {
+    run_id: String,
+    manager: TranscriptManager,
+    /// Latest event ID per agent
+    latest_events: Mutex<HashMap<String, String>>,
+}
+
+impl TranscriptRecorder {
+    pub fn new(run_id: impl Into<String>, manager: TranscriptManager) -> Self {
+        Self {
+            run_id: run_id.into(),
+            manager,
+            latest_events: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Remember `event` as the current step of its agent
+    pub fn note_event(&self, event: &SwarmEvent) {
+        self.latest_events
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(event.agent.clone(), event.id.clone());
+    }
+}
+
+impl TranscriptSink for TranscriptRecorder {
+    fn record(&self, exchange: LlmExchange) {
+        let event_id = self
+            .latest_events
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&exchange.agent_id)
+            .cloned();
+
+        if let Err(e) = self
+            .manager
+            .record(&self.run_id, event_id.as_deref(), &exchange)
+        {
+            tracing::warn!("Failed to record LLM transcript: {}", e);
+        }
+    }
+}
@@ -0,0 +1,254 @@
+//! # Unknown Dependencies
+//!
+//! Order in which the Architect decides a run's unknowns. An unknown that
+//! `depends_on` others is decided after them, so their approved decisions can
+//! be part of its context.
+//!
+//! Unknowns that depend on each other in a cycle cannot be ordered; the cycle
+//! is reported and its members are decided in the order the parser listed
+//! them.
+
+use crate::skills::parse_skill::Ambiguity;
+use std::collections::HashMap;
+
+/// Dependencies between the unknowns of a run, by index into the ambiguities
+#[derive(Debug, Clone)]
+pub struct UnknownGraph {
+    /// Dependencies as declared (unknown IDs and self-references dropped)
+    declared: Vec<Vec<usize>>,
+    /// Dependencies that are waited for (edges inside a cycle dropped)
+    effective: Vec<Vec<usize>>,
+    /// Position of each unknown in the resolution order
+    rank: Vec<usize>,
+    /// IDs of the unknowns in each cycle, in listed order
+    cycles: Vec<Vec<String>>,
+}
+
+impl UnknownGraph {
+    pub fn new(ambiguities: &[Ambiguity]) -> Self {
+        let index: HashMap<&str, usize> = ambiguities
+            .iter()
+            .enumerate()
+            .map(|(i, a)| (a.id.as_str(), i))
+            .collect();
+
+        let declared: Vec<Vec<usize>> = ambiguities
+            .iter()
+            .enumerate()
+            .map(|(i, ambiguity)| {
+                let mut deps: Vec<usize> = ambiguity
+                    .depends_on
+                    .iter()
+                    .filter_map(|id| index.get(id.as_str()).copied())
+                    .filter(|&dep| dep != i)
+                    .collect();
+                deps.sort_unstable();
+                deps.dedup();
+                deps
+            })
+            .collect();
+
+        let components = strongly_connected(&declared);
+        let mut component_of = vec![0; declared.len()];
+        for (component, members) in components.iter().enumerate() {
+            for &member in members {
+                component_of[member] = component;
+            }
+        }
+
+        let cycles = components
+            .iter()
+            .filter(|members| members.len() > 1)
+            .map(|members| {
+                let mut members = members.clone();
+                members.sort_unstable();
+                members
+                    .into_iter()
+                    .map(|i| ambiguities[i].id.clone())
+                    .collect()
+            })
+            .collect();
+
+        let effective: Vec<Vec<usize>> = declared
+            .iter()
+            .enumerate()
+            .map(|(i, deps)| {
+                deps.iter()
+                    .copied()
+                    .filter(|&dep| component_of[dep] != component_of[i])
+                    .collect()
+            })
+            .collect();
+
+        let mut rank = vec![0; declared.len()];
+        for (position, i) in topological_order(&effective).into_iter().enumerate() {
+            rank[i] = position;
+        }
+
+        Self {
+            declared,
+            effective,
+            rank,
+            cycles,
+        }
+    }
+
+    /// Unknowns that must be decided before `index`
+    pub fn dependencies(&self, index: usize) -> &[usize] {
+        &self.effective[index]
+    }
+
+    /// Every unknown `index` declared a dependency on, including cycle members
+    pub fn declared(&self, index: usize) -> &[usize] {
+        &self.declared[index]
+    }
+
+    /// Position of `index` in the resolution order
+    pub fn rank(&self, index: usize) -> usize {
+        self.rank[index]
+    }
+
+    /// Unknown indices in resolution order
+    pub fn order(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.rank.len()).collect();
+        order.sort_by_key(|&i| self.rank[i]);
+        order
+    }
+
+    /// Dependency cycles found between the unknowns
+    pub fn cycles(&self) -> &[Vec<String>] {
+        &self.cycles
+    }
+}
+
+/// Order in which every node comes after its dependencies, ties in index order
+fn topological_order(deps: &[Vec<usize>]) -> Vec<usize> {
+    let mut placed = vec![false; deps.len()];
+    let mut order = Vec::with_capacity(deps.len());
+    while order.len() < deps.len() {
+        let Some(next) =
+            (0..deps.len()).find(|&i| !placed[i] && deps[i].iter().all(|&dep| placed[dep]))
+        else {
+            break;
+        };
+        placed[next] = true;
+        order.push(next);
+    }
+    order
+}
+
+/// Strongly connected components (Tarjan)
+pub(super) fn strongly_connected(deps: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    struct Tarjan<'a> {
+        deps: &'a [Vec<usize>],
+        index: Vec<Option<usize>>,
+        low: Vec<usize>,
+        on_stack: Vec<bool>,
+        stack: Vec<usize>,
+        next_index: usize,
+        components: Vec<Vec<usize>>,
+    }
+
+    impl Tarjan<'_> {
+        fn visit(&mut self, node: usize) {
+            self.index[node] = Some(self.next_index);
+            self.low[node] = self.next_index;
+            self.next_index += 1;
+            self.stack.push(node);
+            self.on_stack[node] = true;
+
+            for &dep in &self.deps[node] {
+                match self.index[dep] {
+                    None => {
+                        self.visit(dep);
+                        self.low[node] = self.low[node].min(self.low[dep]);
+                    }
+                    Some(dep_index) if self.on_stack[dep] => {
+                        self.low[node] = self.low[node].min(dep_index);
+                    }
+                    Some(_) => {}
+                }
+            }
+
+            if Some(self.low[node]) == self.index[node] {
+                let mut component = Vec::new();
+                while let Some(member) = self.stack.pop() {
+                    self.on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                self.components.push(component);
+            }
+        }
+    }
+
+    let mut tarjan = Tarjan {
+        deps,
+        index: vec![None; deps.len()],
+        low: vec![0; deps.len()],
+        on_stack: vec![false; deps.len()],
+        stack: Vec::new(),
+        next_index: 0,
+        components: Vec::new(),
+    };
+    for node in 0..deps.len() {
+        if tarjan.index[node].is_none() {
+            tarjan.visit(node);
+        }
+    }
+    tarjan.components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skills::parse_skill::{AmbiguityCategory, Criticality};
+
+    fn ambiguity(id: &str, depends_on: &[&str]) -> Ambiguity {
+        Ambiguity {
+            id: id.to_string(),
+            category: AmbiguityCategory::Infrastructure,
+            question: format!("{}?", id),
+            criticality: Criticality::High,
+            context: None,
+            depends_on: depends_on.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_dependencies_come_first() {
+        let graph = UnknownGraph::new(&[
+            ambiguity("ORM", &["DB"]),
+            ambiguity("DB", &[]),
+            ambiguity("Migrations", &["ORM", "DB", "Missing"]),
+            ambiguity("Auth", &[]),
+        ]);
+
+        assert_eq!(graph.order(), vec![1, 0, 2, 3]);
+        assert_eq!(graph.dependencies(2), &[0, 1]);
+        assert!(graph.cycles().is_empty());
+    }
+
+    #[test]
+    fn test_cycles_are_reported_and_listed_order_used() {
+        let graph = UnknownGraph::new(&[
+            ambiguity("A", &["C"]),
+            ambiguity("B", &["A"]),
+            ambiguity("C", &["B"]),
+            ambiguity("D", &["C"]),
+            ambiguity("E", &["E"]),
+        ]);
+
+        assert_eq!(
+            graph.cycles(),
+            &[vec!["A".to_string(), "B".to_string(), "C".to_string()]]
+        );
+        assert_eq!(graph.order(), vec![0, 1, 2, 3, 4]);
+        // Edges inside the cycle are not waited for, but stay declared
+        assert!(graph.dependencies(0).is_empty());
+        assert_eq!(graph.declared(0), &[2]);
+        assert_eq!(graph.dependencies(3), &[2]);
+    }
+}
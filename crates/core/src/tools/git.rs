@@ -133,6 +133,73 @@ pub fn merge_worktree(project_root: &Path, feature_id: &str) -> Result<MergeResu
     Ok(MergeResult::Success)
 }
 
+/// Commit every change in a worktree to its feature branch
+///
+/// Returns `false` when there was nothing to commit.
+pub fn commit_worktree(worktree_path: &Path, message: &str) -> Result<bool> {
+    let add = Command::new("git")
+        .args(["add", "-A"])
+        .current_dir(worktree_path)
+        .output()
+        .context("Failed to run git add")?;
+
+    if !add.status.success() {
+        return Err(anyhow::anyhow!(
+            "git add failed: {}",
+            String::from_utf8_lossy(&add.stderr)
+        ));
+    }
+
+    let commit = Command::new("git")
+        .args(["commit", "-m", message])
+        .current_dir(worktree_path)
+        .output()
+        .context("Failed to run git commit")?;
+
+    if !commit.status.success() {
+        let stdout = String::from_utf8_lossy(&commit.stdout);
+        if stdout.contains("nothing to commit") {
+            return Ok(false);
+        }
+        return Err(anyhow::anyhow!(
+            "git commit failed: {}",
+            String::from_utf8_lossy(&commit.stderr)
+        ));
+    }
+
+    Ok(true)
+}
+
+/// Files changed in a worktree and the diff of those changes
+///
+/// Stages everything (as [`commit_worktree`] would) and reports the staged
+/// changes against the branch's last commit. Deleted files are not listed.
+pub fn staged_changes(worktree_path: &Path) -> Result<(Vec<String>, String)> {
+    let git = |args: &[&str]| -> Result<String> {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(worktree_path)
+            .output()
+            .with_context(|| format!("Failed to run git {}", args[0]))?;
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(
+                "git {} failed: {}",
+                args[0],
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+
+    git(&["add", "-A"])?;
+    let files = git(&["diff", "--cached", "--name-only", "--diff-filter=d"])?
+        .lines()
+        .map(str::to_string)
+        .collect();
+    let diff = git(&["diff", "--cached"])?;
+    Ok((files, diff))
+}
+
 /// Result of a merge operation
 #[derive(Debug)]
 pub enum MergeResult {
@@ -191,6 +258,34 @@ pub fn list_worktrees(project_root: &Path) -> Result<Vec<String>> {
     Ok(feature_worktrees)
 }
 
+/// When the given lines of a file were last committed (Unix seconds)
+///
+/// `file` is relative to `project_root`; `lines` are 1-based. Lines that are
+/// not committed yet are left out.
+pub fn line_commit_times(
+    project_root: &Path,
+    file: &Path,
+    lines: &[u32],
+) -> Result<Vec<(u32, i64)>> {
+    let repo = Repository::open(project_root)
+        .with_context(|| format!("Failed to open repository at {:?}", project_root))?;
+    let blame = repo
+        .blame_file(file, None)
+        .with_context(|| format!("Failed to blame {:?}", file))?;
+
+    Ok(lines
+        .iter()
+        .filter_map(|&line| {
+            let hunk = blame.get_line(line as usize)?;
+            if hunk.final_commit_id().is_zero() {
+                return None;
+            }
+            let seconds = hunk.final_signature().when().seconds();
+            Some((line, seconds))
+        })
+        .collect())
+}
+
 /// Get the worktree path for a feature
 pub fn get_worktree_path(feature_id: &str) -> PathBuf {
     get_runtime_path().join("worktrees").join(feature_id)
@@ -206,4 +301,37 @@ mod tests {
         assert!(path.to_string_lossy().contains(".catalyst"));
         assert!(path.to_string_lossy().contains("worktrees"));
     }
+
+    #[test]
+    fn test_commit_worktree() {
+        let dir = std::env::temp_dir().join("catalyst_test_commit_worktree");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .current_dir(&dir)
+                .output()
+                .unwrap()
+        };
+        git(&["init", "-q"]);
+        git(&["config", "user.email", "test@example.com"]);
+        git(&["config", "user.name", "Test"]);
+
+        std::fs::write(dir.join("lib.rs"), "pub fn answer() -> u32 { 42 }\n").unwrap();
+        assert!(commit_worktree(&dir, "feat: add answer").unwrap());
+        assert!(!commit_worktree(&dir, "feat: nothing").unwrap());
+
+        let log = git(&["log", "--oneline"]);
+        assert!(String::from_utf8_lossy(&log.stdout).contains("feat: add answer"));
+
+        std::fs::write(dir.join("lib.rs"), "pub fn answer() -> u32 { 43 }\n").unwrap();
+        std::fs::write(dir.join("new.rs"), "pub fn more() {}\n").unwrap();
+        let (files, diff) = staged_changes(&dir).unwrap();
+        assert_eq!(files, vec!["lib.rs".to_string(), "new.rs".to_string()]);
+        assert!(diff.contains("+pub fn answer() -> u32 { 43 }"));
+        assert!(commit_worktree(&dir, "feat: more").unwrap());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
@@ -257,6 +257,14 @@ fn extract_item_symbol(item: &syn::Item, path: &Path) -> Option<SymbolInfo> {
             line: 0,
             signature: None,
         }),
+        syn::Item::Mod(m) => Some(SymbolInfo {
+            name: m.ident.to_string(),
+            path: m.ident.to_string(),
+            kind: SymbolKind::Module,
+            file: path.to_path_buf(),
+            line: 0,
+            signature: None,
+        }),
         _ => None,
     }
 }
@@ -51,6 +51,70 @@ pub struct TestSummary {
     pub ignored: u32,
     pub total: u32,
     pub results: Vec<TestResult>,
+    /// Whether cargo exited successfully (false when the tests did not compile)
+    #[serde(default)]
+    pub success: bool,
+    /// End of cargo's stderr when it failed without a failing test
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// Diagnostics of a cargo run, with whether cargo succeeded
+#[derive(Debug, Clone)]
+pub struct CargoReport {
+    pub success: bool,
+    pub diagnostics: Vec<CompilerError>,
+    /// End of cargo's stderr
+    pub stderr: String,
+}
+
+impl CargoReport {
+    /// Error-level diagnostics
+    ///
+    /// A failed run without any (manifest or dependency resolution errors,
+    /// link errors, errors without a span) yields one error carrying the end
+    /// of cargo's stderr, with an empty `file`.
+    pub fn errors(&self) -> Vec<CompilerError> {
+        let mut errors: Vec<CompilerError> = self
+            .diagnostics
+            .iter()
+            .filter(|d| d.level == ErrorLevel::Error)
+            .cloned()
+            .collect();
+        if errors.is_empty() && !self.success {
+            errors.push(CompilerError {
+                file: String::new(),
+                line: 0,
+                column: 0,
+                message: self.stderr.clone(),
+                code: None,
+                level: ErrorLevel::Error,
+            });
+        }
+        errors
+    }
+}
+
+impl TestSummary {
+    /// Failed tests, or why cargo failed without one (e.g. the tests did not compile)
+    pub fn failures(&self) -> Vec<String> {
+        let mut failures: Vec<String> = self
+            .results
+            .iter()
+            .filter(|result| !result.passed)
+            .map(|result| match &result.message {
+                Some(message) => format!("{} failed: {}", result.name, message),
+                None => format!("{} failed", result.name),
+            })
+            .collect();
+        if failures.is_empty() && !self.success {
+            failures.push(format!(
+                "cargo test failed: {}",
+                self.error.as_deref().unwrap_or("no output")
+            ));
+        }
+        failures
+    }
 }
 
 /// Allowed commands whitelist
@@ -75,9 +139,9 @@ const BLOCKED_PATTERNS: &[&str] = &[
     "kill ", "pkill", "killall",
 ];
 
-/// Run cargo check and return structured errors
-pub async fn run_cargo_check(cwd: &Path) -> Result<Vec<CompilerError>> {
-    run_cargo_command(cwd, &["check", "--message-format=json"]).await
+/// Run cargo check and report its diagnostics and exit status
+pub async fn run_cargo_check(cwd: &Path) -> Result<CargoReport> {
+    run_cargo_report(cwd, &["check", "--message-format=json"]).await
 }
 
 /// Run cargo build and return structured errors
@@ -99,16 +163,16 @@ pub async fn run_cargo_clippy(cwd: &Path) -> Result<Vec<CompilerError>> {
 }
 
 /// Run cargo test and return structured summary
+///
+/// Results come from libtest's human output (`test <name> ... ok`), the only
+/// format stable toolchains print.
 pub async fn run_cargo_test(cwd: &Path) -> Result<TestSummary> {
-    let output = Command::new("cargo")
-        .args(["test", "--", "--format=json", "-Z", "unstable-options"])
-        .current_dir(cwd)
-        .output()
-        .context("Failed to run cargo test")?;
+    run_test_command(cwd, &["test"]).await
+}
 
-    // Parse the JSON output
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    parse_test_output(&stdout)
+/// Run a single integration test target (`tests/<target>.rs`)
+pub async fn run_cargo_test_target(cwd: &Path, target: &str) -> Result<TestSummary> {
+    run_test_command(cwd, &["test", "--test", target]).await
 }
 
 /// Run cargo fmt check (returns true if formatted correctly)
@@ -182,6 +246,10 @@ pub fn validate_command(command: &str, args: &[&str]) -> Result<()> {
 // --- Private helpers ---
 
 async fn run_cargo_command(cwd: &Path, args: &[&str]) -> Result<Vec<CompilerError>> {
+    Ok(run_cargo_report(cwd, args).await?.diagnostics)
+}
+
+async fn run_cargo_report(cwd: &Path, args: &[&str]) -> Result<CargoReport> {
     let output = Command::new("cargo")
         .args(args)
         .current_dir(cwd)
@@ -189,7 +257,18 @@ async fn run_cargo_command(cwd: &Path, args: &[&str]) -> Result<Vec<CompilerErro
         .with_context(|| format!("Failed to run cargo {:?}", args))?;
 
     let stdout = String::from_utf8_lossy(&output.stdout);
-    parse_cargo_output(&stdout)
+    Ok(CargoReport {
+        success: output.status.success(),
+        diagnostics: parse_cargo_output(&stdout)?,
+        stderr: stderr_tail(&output.stderr),
+    })
+}
+
+/// Last lines of a command's stderr
+fn stderr_tail(stderr: &[u8]) -> String {
+    let stderr = String::from_utf8_lossy(stderr);
+    let lines: Vec<&str> = stderr.lines().collect();
+    lines[lines.len().saturating_sub(20)..].join("\n")
 }
 
 fn parse_cargo_output(output: &str) -> Result<Vec<CompilerError>> {
@@ -225,48 +304,88 @@ fn parse_cargo_output(output: &str) -> Result<Vec<CompilerError>> {
     Ok(errors)
 }
 
-fn parse_test_output(output: &str) -> Result<TestSummary> {
-    let mut results = Vec::new();
-    let mut passed = 0u32;
-    let mut failed = 0u32;
-    let mut ignored = 0u32;
+async fn run_test_command(cwd: &Path, args: &[&str]) -> Result<TestSummary> {
+    // Backtraces would bury the panic message reported for each failure
+    let output = Command::new("cargo")
+        .args(args)
+        .env("RUST_BACKTRACE", "0")
+        .current_dir(cwd)
+        .output()
+        .with_context(|| format!("Failed to run cargo {:?}", args))?;
+
+    let mut summary = parse_test_output(&String::from_utf8_lossy(&output.stdout));
+    summary.success = output.status.success();
+    if !summary.success && summary.failed == 0 {
+        summary.error = Some(stderr_tail(&output.stderr));
+    }
+    Ok(summary)
+}
+
+fn parse_test_output(output: &str) -> TestSummary {
+    let mut results: Vec<TestResult> = Vec::new();
+    let mut ignored = 0;
+    // Captured output of failed tests ("---- name stdout ----" sections)
+    let mut messages: Vec<(String, Vec<&str>)> = Vec::new();
+    let mut capturing = false;
 
     for line in output.lines() {
-        if let Ok(event) = serde_json::from_str::<TestEvent>(line) {
-            match event.event.as_str() {
-                "ok" => {
-                    passed += 1;
-                    results.push(TestResult {
-                        name: event.name.unwrap_or_default(),
-                        passed: true,
-                        duration_ms: None,
-                        message: None,
-                    });
-                }
-                "failed" => {
-                    failed += 1;
-                    results.push(TestResult {
-                        name: event.name.unwrap_or_default(),
-                        passed: false,
-                        duration_ms: None,
-                        message: event.stdout,
-                    });
-                }
-                "ignored" => {
-                    ignored += 1;
-                }
-                _ => {}
+        if let Some(name) = line
+            .strip_prefix("---- ")
+            .and_then(|rest| rest.strip_suffix(" stdout ----"))
+        {
+            messages.push((name.to_string(), Vec::new()));
+            capturing = true;
+            continue;
+        }
+        if capturing {
+            if line == "failures:" || line.starts_with("test result:") {
+                capturing = false;
+            } else if let Some((_, lines)) = messages.last_mut() {
+                lines.push(line);
             }
+            continue;
         }
+
+        let Some((name, status)) = line
+            .strip_prefix("test ")
+            .and_then(|rest| rest.rsplit_once(" ... "))
+        else {
+            continue;
+        };
+        let passed = match status {
+            "ok" => true,
+            "FAILED" => false,
+            _ if status.starts_with("ignored") => {
+                ignored += 1;
+                continue;
+            }
+            _ => continue,
+        };
+        results.push(TestResult {
+            name: name.to_string(),
+            passed,
+            duration_ms: None,
+            message: None,
+        });
     }
 
-    Ok(TestSummary {
+    for (name, lines) in messages {
+        if let Some(result) = results.iter_mut().find(|r| r.name == name && !r.passed) {
+            result.message = Some(lines.join("\n").trim().to_string());
+        }
+    }
+
+    let passed = results.iter().filter(|r| r.passed).count() as u32;
+    let failed = results.len() as u32 - passed;
+    TestSummary {
         passed,
         failed,
         ignored,
         total: passed + failed + ignored,
         results,
-    })
+        success: failed == 0,
+        error: None,
+    }
 }
 
 // --- Cargo JSON message types ---
@@ -296,17 +415,81 @@ struct DiagnosticSpan {
     column_start: u32,
 }
 
-#[derive(Debug, Deserialize)]
-struct TestEvent {
-    event: String,
-    name: Option<String>,
-    stdout: Option<String>,
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_test_output_reads_libtest_lines() {
+        let output = r#"
+running 4 tests
+test tests::ok ... ok
+test tests::skipped ... ignored, needs a database
+test tests::bad ... FAILED
+test tests::also_ok ... ok
+
+failures:
+
+---- tests::bad stdout ----
+
+thread 'tests::bad' panicked at src/lib.rs:9:5:
+boom
+
+failures:
+    tests::bad
+
+test result: FAILED. 2 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out; finished in 0.00s
+"#;
+        let summary = parse_test_output(output);
+        assert_eq!(
+            (
+                summary.passed,
+                summary.failed,
+                summary.ignored,
+                summary.total
+            ),
+            (2, 1, 1, 4)
+        );
+        assert!(!summary.success);
+        let bad = summary.results.iter().find(|r| !r.passed).unwrap();
+        assert_eq!(bad.name, "tests::bad");
+        assert_eq!(
+            bad.message.as_deref(),
+            Some("thread 'tests::bad' panicked at src/lib.rs:9:5:\nboom")
+        );
+    }
+
+    #[test]
+    fn test_failed_run_without_diagnostics_is_an_error() {
+        let warning = CompilerError {
+            file: "src/lib.rs".to_string(),
+            line: 1,
+            column: 1,
+            message: "unused import".to_string(),
+            code: None,
+            level: ErrorLevel::Warning,
+        };
+        let report = |success| CargoReport {
+            success,
+            diagnostics: vec![warning.clone()],
+            stderr: "error: linking with `cc` failed".to_string(),
+        };
+
+        assert!(report(true).errors().is_empty());
+        let errors = report(false).errors();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].file.is_empty());
+        assert_eq!(errors[0].message, "error: linking with `cc` failed");
+
+        let mut summary = parse_test_output("");
+        summary.success = false;
+        summary.error = Some("error[E0425]: cannot find value `x`".to_string());
+        assert_eq!(
+            summary.failures(),
+            vec!["cargo test failed: error[E0425]: cannot find value `x`"]
+        );
+    }
+
     #[test]
     fn test_validate_allowed_cargo() {
         assert!(validate_command("cargo", &["check"]).is_ok());
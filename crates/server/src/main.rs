@@ -18,10 +18,19 @@ use axum::{
     Router,
 };
 use catalyst_core::memory::{CatalystMemory, MemoryConfig};
-use catalyst_core::models::LlmProvider;
-use catalyst_core::state::CatalystDb;
+use catalyst_core::models::{
+    local, rate_limit, registry, CassetteConfig, CassetteMode, FallbackModel, GenerationParams,
+    LlmProvider, RateLimit,
+};
+use catalyst_core::skills::{GardenHarvest, GardenReport, GardenerSkill};
+use catalyst_core::state::{
+    transcripts, CatalystDb, ProjectMode, RunManager, RunRecord, RunStatus, SpecEdit,
+    SpecEditManager, TranscriptEntry, TranscriptManager, TranscriptSummary, UsageGroup,
+    UsageLedger, UsageTotals,
+};
 use catalyst_core::swarm::{
-    ApprovalRequest, ApprovalResponse, Coordinator, CoordinatorConfig, SwarmEvent,
+    ApprovalRequest, ApprovalResponse, BudgetAction, Coordinator, CoordinatorConfig, ModePolicy,
+    PipelineGraph, ReviewPanel, SwarmEvent,
 };
 use clap::{Parser, Subcommand};
 use futures::{
@@ -35,7 +44,7 @@ use std::process::Stdio;
 use std::{collections::HashMap, convert::Infallible, net::SocketAddr, sync::Arc};
 use tokio::{
     net::TcpListener,
-    sync::{broadcast, mpsc, oneshot, RwLock},
+    sync::{broadcast, mpsc, oneshot, Notify, RwLock},
 };
 use utoipa::{OpenApi, ToSchema};
 
@@ -46,31 +55,274 @@ struct Assets;
 
 /// Application state
 struct AppState {
-    swarm_status: RwLock<SwarmStatus>,
+    /// Submitted runs, their status, events and control channels
+    runs: RwLock<RunQueue>,
+    /// Wakes the scheduler when a run is queued, finishes or is reordered
+    scheduler: Notify,
     event_tx: broadcast::Sender<SwarmEvent>,
     memory: CatalystMemory,
     /// Unified database for all state
     db: Arc<CatalystDb>,
-    // Pending approval requests: decision_id -> oneshot sender
-    pending_approvals: RwLock<HashMap<String, oneshot::Sender<ApprovalResponse>>>,
-    // Channel to send commands to the coordinator (for inbox) - updated per swarm run
-    coordinator_tx: RwLock<Option<mpsc::Sender<catalyst_core::swarm::CoordinatorCommand>>>,
 }
 
 #[derive(Default, Clone, Serialize, ToSchema)]
 struct SwarmStatus {
+    /// Run the status is about (the latest active run unless one is asked for)
+    run_id: Option<String>,
     status: String,
     active_agent: Option<String>,
     pipeline_stage: u8,
+    /// Runs waiting for a slot
+    queued: usize,
+    /// Runs running or paused
+    running: usize,
+    /// Per-provider rate limiter load (filled in on read)
+    rate_limits: Vec<RateLimitStatus>,
+}
+
+#[derive(Clone, Serialize, ToSchema)]
+struct RateLimitStatus {
+    provider: String,
+    /// LLM calls waiting for a slot
+    queue_depth: usize,
+    in_flight: usize,
+    requests_last_minute: usize,
+    tokens_last_minute: u64,
+    requests_per_minute: Option<u32>,
+    tokens_per_minute: Option<u64>,
+    max_in_flight: Option<usize>,
 }
 
 type SharedState = Arc<AppState>;
 
+// === Run Queue ===
+
+/// Runs executing at once unless `max_concurrent_runs` is configured
+const DEFAULT_MAX_CONCURRENT_RUNS: usize = 1;
+/// Events kept per run for inspection (the oldest are dropped first)
+const MAX_RUN_EVENTS: usize = 500;
+/// Finished runs kept in the queue listing
+const MAX_FINISHED_RUNS: usize = 50;
+
+/// A goal submitted to the server and the state of its run
+struct RunEntry {
+    id: String,
+    goal: String,
+    /// "plan", "execute" or "resume"
+    kind: &'static str,
+    /// Higher runs first; ties go to the earlier submission
+    priority: i32,
+    /// Submission order
+    seq: u64,
+    /// queued, running, paused, stopping, complete, failed, error, stopped or
+    /// cancelled
+    status: String,
+    active_agent: Option<String>,
+    error: Option<String>,
+    queued_at: chrono::DateTime<chrono::Utc>,
+    started_at: Option<chrono::DateTime<chrono::Utc>>,
+    finished_at: Option<chrono::DateTime<chrono::Utc>>,
+    events: std::collections::VecDeque<SwarmEvent>,
+    /// Command channel of the run's coordinator
+    commands: mpsc::Sender<catalyst_core::swarm::CoordinatorCommand>,
+    /// Pending approval requests: decision_id -> oneshot sender
+    approvals: HashMap<String, oneshot::Sender<ApprovalResponse>>,
+    /// Coordinator waiting for a slot (taken when the run starts)
+    pending: Option<std::sync::Mutex<(Coordinator, Launch)>>,
+}
+
+impl RunEntry {
+    fn is_queued(&self) -> bool {
+        self.status == "queued"
+    }
+
+    /// Holding a run slot: a stopping run keeps it until its coordinator returns
+    fn is_active(&self) -> bool {
+        matches!(self.status.as_str(), "running" | "paused" | "stopping")
+    }
+
+    fn is_stopping(&self) -> bool {
+        self.status == "stopping"
+    }
+
+    fn is_finished(&self) -> bool {
+        !self.is_queued() && !self.is_active()
+    }
+
+    /// Record an event the run emitted
+    fn note_event(&mut self, event: &SwarmEvent) {
+        use catalyst_core::swarm::SwarmEventKind;
+
+        if event.kind == SwarmEventKind::AgentStarted && self.is_active() && !self.is_stopping() {
+            self.active_agent = Some(event.agent.clone());
+        }
+        if self.events.len() >= MAX_RUN_EVENTS {
+            self.events.pop_front();
+        }
+        self.events.push_back(event.clone());
+    }
+
+    /// Mark the run as over, dropping its unanswered approvals
+    fn finish(&mut self, status: &str, error: Option<String>) {
+        self.status = status.to_string();
+        self.error = error;
+        self.active_agent = None;
+        self.finished_at = Some(chrono::Utc::now());
+        self.approvals.clear();
+        self.pending = None;
+    }
+}
+
+/// Every run the server knows about, in submission order
+#[derive(Default)]
+struct RunQueue {
+    runs: Vec<RunEntry>,
+    next_seq: u64,
+}
+
+impl RunQueue {
+    fn get(&self, id: &str) -> Option<&RunEntry> {
+        self.runs.iter().find(|run| run.id == id)
+    }
+
+    fn get_mut(&mut self, id: &str) -> Option<&mut RunEntry> {
+        self.runs.iter_mut().find(|run| run.id == id)
+    }
+
+    fn active(&self) -> impl Iterator<Item = &RunEntry> {
+        self.runs.iter().filter(|run| run.is_active())
+    }
+
+    /// Queued runs in the order they will start
+    fn queued(&self) -> Vec<&RunEntry> {
+        let mut queued: Vec<&RunEntry> = self.runs.iter().filter(|run| run.is_queued()).collect();
+        queued.sort_by_key(|run| (std::cmp::Reverse(run.priority), run.seq));
+        queued
+    }
+
+    /// Run a request without a run ID is about: the latest active run, or else
+    /// the latest run
+    fn target(&self, id: Option<&str>) -> Option<&RunEntry> {
+        match id {
+            Some(id) => self.get(id),
+            None => self
+                .active()
+                .max_by_key(|run| run.started_at)
+                .or_else(|| self.runs.last()),
+        }
+    }
+
+    fn push(&mut self, mut entry: RunEntry) {
+        entry.seq = self.next_seq;
+        self.next_seq += 1;
+        self.runs.push(entry);
+    }
+
+    /// Forget the oldest finished runs beyond [`MAX_FINISHED_RUNS`]
+    fn prune(&mut self) {
+        let finished = self.runs.iter().filter(|run| run.is_finished()).count();
+        let mut excess = finished.saturating_sub(MAX_FINISHED_RUNS);
+        self.runs.retain(|run| {
+            if excess > 0 && run.is_finished() {
+                excess -= 1;
+                return false;
+            }
+            true
+        });
+    }
+}
+
+/// Start queued runs whenever a slot is free
+///
+/// Woken through [`AppState::scheduler`]; the limit is re-read from the
+/// persisted config each time, so changing it applies to the next start.
+async fn run_scheduler(state: SharedState) {
+    loop {
+        state.scheduler.notified().await;
+        let limit = PersistedConfig::load()
+            .await
+            .max_concurrent_runs
+            .unwrap_or(DEFAULT_MAX_CONCURRENT_RUNS)
+            .max(1);
+
+        let mut queue = state.runs.write().await;
+        let mut running = queue.active().count();
+        while running < limit {
+            let Some(id) = queue.queued().first().map(|run| run.id.clone()) else {
+                break;
+            };
+            let Some(entry) = queue.get_mut(&id) else {
+                break;
+            };
+            let Some((coordinator, launch)) = entry
+                .pending
+                .take()
+                .and_then(|pending| pending.into_inner().ok())
+            else {
+                entry.finish("error", Some("Run lost its coordinator".to_string()));
+                continue;
+            };
+            entry.status = "running".to_string();
+            entry.started_at = Some(chrono::Utc::now());
+            running += 1;
+            println!("▶️  Starting run {}: {}", id, entry.goal);
+            let goal = entry.goal.clone();
+            tokio::spawn(drive_run(state.clone(), id, goal, coordinator, launch));
+        }
+    }
+}
+
+/// Run a coordinator to the end and record how it went
+async fn drive_run(
+    state: SharedState,
+    run_id: String,
+    goal: String,
+    coordinator: Coordinator,
+    launch: Launch,
+) {
+    let mut coordinator = coordinator.with_research_agent();
+    let outcome = match launch {
+        Launch::Plan => coordinator.run(&goal).await.map(|result| result.success),
+        Launch::Execute => coordinator.execute(&goal).await.map(|result| {
+            if let Some(error) = &result.error {
+                eprintln!("❌ Execution stopped: {}", error);
+            }
+            result.merged
+        }),
+        Launch::Resume(id) => coordinator.resume(&id).await.map(|result| result.success),
+    };
+
+    let (status, error) = match outcome {
+        Ok(success) => {
+            println!("✅ Run {} completed: success={}", run_id, success);
+            (if success { "complete" } else { "failed" }, None)
+        }
+        Err(e) if catalyst_core::swarm::is_cancelled(&e) => {
+            println!("⏹️ Run {} stopped", run_id);
+            ("stopped", None)
+        }
+        Err(e) => {
+            eprintln!("❌ Run {} failed: {}", run_id, e);
+            ("error", Some(format!("{:#}", e)))
+        }
+    };
+    {
+        let mut queue = state.runs.write().await;
+        if let Some(entry) = queue.get_mut(&run_id) {
+            entry.finish(status, error);
+        }
+        queue.prune();
+    }
+    state.scheduler.notify_one();
+}
+
 // === API Types ===
 
 #[derive(Deserialize, ToSchema)]
 struct StartSwarmRequest {
     goal: String,
+    /// Queue priority (higher starts first, default 0)
+    priority: Option<i32>,
     settings: Option<ApiSettings>,
 }
 
@@ -79,6 +331,8 @@ struct ApiSettings {
     global_provider: Option<String>,
     global_model: Option<String>,
     base_url: Option<String>,
+    /// Endpoint for the local (OpenAI-compatible) provider
+    local_base_url: Option<String>,
     #[serde(skip)]
     #[schema(ignore)]
     per_agent_providers: Option<HashMap<String, String>>,
@@ -92,9 +346,39 @@ struct ApiSettings {
     require_critic_approval: Option<bool>,
     require_architect_approval: Option<bool>,
     max_concurrent_features: Option<usize>,
+    /// Research missions run at once across the run's unknowns
+    max_concurrent_research: Option<usize>,
     max_rejections: Option<u32>,
     scraper_model: Option<String>,
     searxng_url: Option<String>,
+    /// Cassette name under `.catalyst/cassettes/` (replayed by the mock provider)
+    cassette: Option<String>,
+    /// Record LLM responses into `cassette` instead of replaying
+    record_cassette: Option<bool>,
+    /// Per-run spend cap in USD
+    max_cost_usd: Option<f64>,
+    /// Per-run token cap (input + output)
+    max_tokens: Option<u64>,
+    /// "pause" (ask via inbox) or "abort" when a cap is exceeded
+    budget_action: Option<String>,
+    /// Attempts per provider for transient errors (1 disables retries)
+    max_retries: Option<u32>,
+    /// Fallback models tried in order, as "provider:model" (e.g. "openai:gpt-4o")
+    fallback_models: Option<Vec<String>>,
+    /// Serve repeated LLM calls from the project response cache
+    response_cache: Option<bool>,
+    /// Lifetime of cached responses in seconds
+    cache_ttl_secs: Option<u64>,
+    /// Skip cache lookups for this run (responses are still cached)
+    cache_bypass: Option<bool>,
+    /// Re-prompts allowed when an agent's structured output fails validation
+    max_repairs: Option<u32>,
+    /// Generation parameters for all agents (overrides the persisted ones)
+    #[schema(value_type = Option<Object>)]
+    generation: Option<GenerationParams>,
+    /// Critics reviewing decisions together: {critics: [{id, persona}], quorum}
+    #[schema(value_type = Option<Object>)]
+    review_panel: Option<ReviewPanel>,
 }
 
 #[derive(Serialize, ToSchema)]
@@ -103,11 +387,94 @@ struct ApiResponse {
     message: String,
 }
 
+#[derive(Serialize, ToSchema)]
+struct RunQueuedResponse {
+    success: bool,
+    message: String,
+    run_id: Option<String>,
+    /// Place in the queue (absent once the run has started)
+    position: Option<usize>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct QueuedRunResponse {
+    id: String,
+    goal: String,
+    /// "plan", "execute" or "resume"
+    kind: String,
+    priority: i32,
+    /// queued, running, paused, stopping, complete, failed, error, stopped or
+    /// cancelled
+    status: String,
+    /// Place in the queue (queued runs only)
+    position: Option<usize>,
+    active_agent: Option<String>,
+    error: Option<String>,
+    queued_at: String,
+    started_at: Option<String>,
+    finished_at: Option<String>,
+    /// Decisions waiting for approval
+    pending_approvals: Vec<String>,
+}
+
+impl QueuedRunResponse {
+    fn new(run: &RunEntry, position: Option<usize>) -> Self {
+        Self {
+            id: run.id.clone(),
+            goal: run.goal.clone(),
+            kind: run.kind.to_string(),
+            priority: run.priority,
+            status: run.status.clone(),
+            position,
+            active_agent: run.active_agent.clone(),
+            error: run.error.clone(),
+            queued_at: run.queued_at.to_rfc3339(),
+            started_at: run.started_at.map(|t| t.to_rfc3339()),
+            finished_at: run.finished_at.map(|t| t.to_rfc3339()),
+            pending_approvals: run.approvals.keys().cloned().collect(),
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+struct RunQueueResponse {
+    /// Runs executing at once
+    max_concurrent_runs: usize,
+    /// Active runs, then queued runs in start order, then finished runs (newest first)
+    runs: Vec<QueuedRunResponse>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct QueuedRunDetailResponse {
+    run: QueuedRunResponse,
+    /// Events of the run, oldest first (the most recent 500)
+    #[schema(value_type = Vec<Object>)]
+    events: Vec<SwarmEvent>,
+}
+
+#[derive(Deserialize, ToSchema)]
+struct RunPriorityRequest {
+    priority: i32,
+}
+
+impl RunQueuedResponse {
+    fn rejected(message: String) -> Self {
+        Self {
+            success: false,
+            message,
+            run_id: None,
+            position: None,
+        }
+    }
+}
+
 #[derive(Deserialize, ToSchema)]
 struct ApprovalApiRequest {
     decision_id: String,
     approved: bool,
     feedback: Option<String>,
+    /// Run that asked (default: whichever run has a pending approval with this ID)
+    run_id: Option<String>,
 }
 
 #[derive(Deserialize, ToSchema)]
@@ -118,6 +485,8 @@ struct ApiKeysRequest {
     openrouter: Option<String>,
     grok: Option<String>,
     deepseek: Option<String>,
+    /// Optional key for local servers started with `--api-key`
+    local: Option<String>,
 }
 
 #[derive(Deserialize, ToSchema)]
@@ -174,12 +543,68 @@ struct FeatureResponse {
     title: String,
     stage: String,
     description: Option<String>,
+    /// Features that must be merged before this one is built
+    depends_on: Vec<String>,
+    /// Why the feature failed or is blocked
+    error: Option<String>,
+    created_at: String,
+}
+
+#[derive(Serialize, ToSchema)]
+struct RunResponse {
+    id: String,
+    goal: String,
+    status: String,
+    feature_id: Option<String>,
+    /// Unknowns finished before the run stopped
+    completed_unknowns: usize,
+    /// Unknowns found by the parser (if it finished)
+    total_unknowns: Option<usize>,
+    error: Option<String>,
     created_at: String,
+    updated_at: String,
+}
+
+#[derive(Serialize, ToSchema)]
+struct RunListResponse {
+    runs: Vec<RunResponse>,
+}
+
+#[derive(Deserialize, ToSchema)]
+struct ResumeRunRequest {
+    /// Queue priority (higher starts first, default 0)
+    priority: Option<i32>,
+    settings: Option<ApiSettings>,
+}
+
+impl From<RunRecord> for RunResponse {
+    fn from(run: RunRecord) -> Self {
+        let total_unknowns = run
+            .unknowns
+            .as_ref()
+            .and_then(|u| u.get("ambiguities"))
+            .and_then(|a| a.as_array())
+            .map(Vec::len);
+        Self {
+            id: run.id,
+            goal: run.goal,
+            status: format!("{:?}", run.status).to_lowercase(),
+            feature_id: run.feature_id,
+            completed_unknowns: run.completed_unknowns,
+            total_unknowns,
+            error: run.error,
+            created_at: run.created_at,
+            updated_at: run.updated_at,
+        }
+    }
 }
 
 #[derive(Deserialize, ToSchema)]
 struct IgniteRequest {
     idea_id: String,
+    /// Features that must be merged before this one is built
+    #[serde(default)]
+    depends_on: Vec<String>,
 }
 
 #[derive(Serialize, ToSchema)]
@@ -189,6 +614,25 @@ struct IgniteResponse {
     error: Option<String>,
 }
 
+#[derive(Deserialize, ToSchema, Default)]
+struct GardenRequest {
+    /// Report the issues without filing features or ideas
+    #[serde(default)]
+    dry_run: bool,
+}
+
+#[derive(Serialize, ToSchema)]
+struct GardenResponse {
+    success: bool,
+    /// Issues found, most urgent first
+    #[schema(value_type = Option<Object>)]
+    report: Option<GardenReport>,
+    /// Features and ideas filed (absent on a dry run)
+    #[schema(value_type = Option<Object>)]
+    harvest: Option<GardenHarvest>,
+    error: Option<String>,
+}
+
 // === Snapshot/Rollback API Types ===
 
 #[derive(Serialize, ToSchema)]
@@ -320,6 +764,40 @@ struct DocumentResponse {
     slug: String,
     title: String,
     content: String,
+    /// Sections written by Architect decisions, oldest first
+    edits: Vec<SpecEditResponse>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct SpecEditResponse {
+    id: i64,
+    section: String,
+    content: String,
+    run_id: String,
+    unknown_id: String,
+    /// Option the Architect chose
+    decision: String,
+    /// "applied" or "conflict"
+    status: String,
+    /// Applied edit this one conflicts with
+    conflicts_with: Option<i64>,
+    created_at: String,
+}
+
+impl From<SpecEdit> for SpecEditResponse {
+    fn from(edit: SpecEdit) -> Self {
+        Self {
+            id: edit.id,
+            section: edit.section,
+            content: edit.content,
+            run_id: edit.run_id,
+            unknown_id: edit.unknown_id,
+            decision: edit.decision,
+            status: format!("{:?}", edit.status).to_lowercase(),
+            conflicts_with: edit.conflicts_with,
+            created_at: edit.created_at,
+        }
+    }
 }
 
 #[derive(Deserialize, ToSchema)]
@@ -328,6 +806,136 @@ struct UpdateDocumentRequest {
     content: String,
 }
 
+// === Usage Types ===
+
+#[derive(Deserialize)]
+struct UsageQuery {
+    run_id: Option<String>,
+    limit: Option<usize>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct UsageTotalsResponse {
+    calls: u64,
+    input_tokens: u64,
+    output_tokens: u64,
+    cost_usd: f64,
+}
+
+#[derive(Serialize, ToSchema)]
+struct UsageGroupResponse {
+    key: String,
+    calls: u64,
+    input_tokens: u64,
+    output_tokens: u64,
+    cost_usd: f64,
+}
+
+#[derive(Serialize, ToSchema)]
+struct UsageEntryResponse {
+    run_id: String,
+    feature_id: Option<String>,
+    agent_id: String,
+    provider: String,
+    model: String,
+    input_tokens: u64,
+    output_tokens: u64,
+    cost_usd: f64,
+    created_at: String,
+}
+
+#[derive(Serialize, ToSchema)]
+struct UsageResponse {
+    run_id: Option<String>,
+    total: UsageTotalsResponse,
+    by_agent: Vec<UsageGroupResponse>,
+    by_feature: Vec<UsageGroupResponse>,
+    by_run: Vec<UsageGroupResponse>,
+    recent: Vec<UsageEntryResponse>,
+}
+
+// === Transcript Types ===
+
+#[derive(Deserialize)]
+struct TranscriptQuery {
+    run_id: Option<String>,
+    agent_id: Option<String>,
+    limit: Option<usize>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct TranscriptSummaryResponse {
+    id: i64,
+    run_id: String,
+    agent_id: String,
+    feature_id: Option<String>,
+    event_id: Option<String>,
+    prompt_version: Option<i32>,
+    provider: String,
+    model: String,
+    failed: bool,
+    input_tokens: u64,
+    output_tokens: u64,
+    duration_ms: u64,
+    created_at: String,
+}
+
+#[derive(Serialize, ToSchema)]
+struct TranscriptListResponse {
+    transcripts: Vec<TranscriptSummaryResponse>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct TranscriptResponse {
+    found: bool,
+    /// Full transcript entry (system prompt, messages, tool calls, response)
+    #[schema(value_type = Option<Object>)]
+    transcript: Option<TranscriptEntry>,
+}
+
+impl From<TranscriptSummary> for TranscriptSummaryResponse {
+    fn from(t: TranscriptSummary) -> Self {
+        Self {
+            id: t.id,
+            run_id: t.run_id,
+            agent_id: t.agent_id,
+            feature_id: t.feature_id,
+            event_id: t.event_id,
+            prompt_version: t.prompt_version,
+            provider: t.provider,
+            model: t.model,
+            failed: t.failed,
+            input_tokens: t.input_tokens,
+            output_tokens: t.output_tokens,
+            duration_ms: t.duration_ms,
+            created_at: t.created_at,
+        }
+    }
+}
+
+impl From<UsageTotals> for UsageTotalsResponse {
+    fn from(t: UsageTotals) -> Self {
+        Self {
+            calls: t.calls,
+            input_tokens: t.input_tokens,
+            output_tokens: t.output_tokens,
+            cost_usd: t.cost_usd,
+        }
+    }
+}
+
+impl From<UsageGroup> for UsageGroupResponse {
+    fn from(g: UsageGroup) -> Self {
+        Self {
+            key: g.key,
+            calls: g.totals.calls,
+            input_tokens: g.totals.input_tokens,
+            output_tokens: g.totals.output_tokens,
+            cost_usd: g.totals.cost_usd,
+        }
+    }
+}
+
 #[derive(Parser, Clone)]
 #[command(author, version, about = "Catalyst - Autonomous Coding Agent Swarm")]
 struct Args {
@@ -359,6 +967,46 @@ enum CliCommand {
     Run {
         /// The goal to accomplish
         goal: String,
+        /// Record all LLM responses into `.catalyst/cassettes/<NAME>.json`
+        #[arg(long, value_name = "NAME", conflicts_with = "replay")]
+        record: Option<String>,
+        /// Replay LLM responses from `.catalyst/cassettes/<NAME>.json` (no API calls)
+        #[arg(long, value_name = "NAME")]
+        replay: Option<String>,
+        /// Serve repeated LLM calls from the response cache in `.catalyst/catalyst.db`
+        #[arg(long)]
+        cache: bool,
+        /// Ignore cached responses for this run, but refresh the cache
+        #[arg(long)]
+        refresh_cache: bool,
+        /// Also draft, build and merge the plan (goal to merged code)
+        #[arg(long)]
+        execute: bool,
+        /// Project mode: speed_run, lab or fortress (default: saved config, then lab)
+        #[arg(long)]
+        mode: Option<ProjectMode>,
+    },
+    /// Sweep the project for maintenance work (oversized files, unwraps, dead modules, stale TODOs)
+    Garden {
+        /// Only print the issues; don't file features or ideas
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Resume an interrupted run from its checkpoints
+    Resume {
+        /// Run ID (defaults to the most recent interrupted run)
+        run_id: Option<String>,
+    },
+    /// Dump the LLM transcript of a run as Markdown
+    Transcript {
+        /// Run ID (defaults to the most recent run)
+        run_id: Option<String>,
+        /// Only include calls by this agent
+        #[arg(long)]
+        agent: Option<String>,
+        /// Write to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<std::path::PathBuf>,
     },
 }
 
@@ -374,6 +1022,8 @@ struct PersistedConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     base_url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    local_base_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     mode: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     require_critic_approval: Option<bool>,
@@ -383,6 +1033,12 @@ struct PersistedConfig {
     max_concurrent_features: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
     max_rejections: Option<u32>,
+    /// Runs the server executes at once (the rest wait in the queue)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_concurrent_runs: Option<usize>,
+    /// Minutes between Gardener sweeps while the server runs (unset: only on demand)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    garden_interval_mins: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     scraper_model: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -391,6 +1047,23 @@ struct PersistedConfig {
     per_agent_providers: HashMap<String, String>,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     per_agent_models: HashMap<String, String>,
+    /// Temperature, max tokens, and stop sequences for all agents
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    generation: Option<GenerationParams>,
+    /// Per-agent generation overrides (agent_id -> params)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[schema(value_type = Object)]
+    per_agent_generation: HashMap<String, GenerationParams>,
+    /// Critics reviewing decisions together, and the quorum they decide by
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    review_panel: Option<ReviewPanel>,
+    /// Per-provider limits shared by every run: provider -> {requests_per_minute,
+    /// tokens_per_minute, max_in_flight}
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    rate_limits: Option<HashMap<String, RateLimit>>,
 }
 
 impl PersistedConfig {
@@ -398,7 +1071,10 @@ impl PersistedConfig {
         let path = std::path::PathBuf::from(".catalyst/config.json");
         if path.exists() {
             match tokio::fs::read_to_string(&path).await {
-                Ok(content) => serde_json::from_str(&content).unwrap_or_default(),
+                Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                    eprintln!("⚠️ Ignoring invalid {}: {}", path.display(), e);
+                    Self::default()
+                }),
                 Err(_) => Self::default(),
             }
         } else {
@@ -426,6 +1102,9 @@ impl PersistedConfig {
         if other.base_url.is_some() {
             self.base_url = other.base_url;
         }
+        if other.local_base_url.is_some() {
+            self.local_base_url = other.local_base_url;
+        }
         if other.mode.is_some() {
             self.mode = other.mode;
         }
@@ -441,6 +1120,12 @@ impl PersistedConfig {
         if other.max_rejections.is_some() {
             self.max_rejections = other.max_rejections;
         }
+        if other.max_concurrent_runs.is_some() {
+            self.max_concurrent_runs = other.max_concurrent_runs;
+        }
+        if other.garden_interval_mins.is_some() {
+            self.garden_interval_mins = other.garden_interval_mins;
+        }
         if other.scraper_model.is_some() {
             self.scraper_model = other.scraper_model;
         }
@@ -453,6 +1138,45 @@ impl PersistedConfig {
         for (k, v) in other.per_agent_models {
             self.per_agent_models.insert(k, v);
         }
+        if other.generation.is_some() {
+            self.generation = other.generation;
+        }
+        for (k, v) in other.per_agent_generation {
+            self.per_agent_generation.insert(k, v);
+        }
+        if other.review_panel.is_some() {
+            self.review_panel = other.review_panel;
+        }
+        if other.rate_limits.is_some() {
+            self.rate_limits = other.rate_limits;
+        }
+    }
+
+    /// Set the process-wide provider limits (providers not listed are unlimited)
+    fn apply_rate_limits(&self) {
+        rate_limit::configure_all(&self.rate_limits.clone().unwrap_or_default());
+    }
+
+    /// Copy the persisted generation parameters into a coordinator config
+    fn apply_generation(&self, config: &mut CoordinatorConfig) {
+        if let Some(ref generation) = self.generation {
+            config.generation = generation.clone();
+        }
+        config.per_agent_generation = self.per_agent_generation.clone();
+    }
+
+    /// Use the saved project mode (invalid values are ignored)
+    fn apply_mode(&self, config: &mut CoordinatorConfig) {
+        if let Some(mode) = self.mode.as_deref().and_then(|m| m.parse().ok()) {
+            config.mode = mode;
+        }
+    }
+
+    /// Use the saved review panel, if any
+    fn apply_review_panel(&self, config: &mut CoordinatorConfig) {
+        if self.review_panel.is_some() {
+            config.review_panel = self.review_panel.clone();
+        }
     }
 }
 
@@ -468,6 +1192,7 @@ struct ConfigDefaults {
     mode: &'static str,
     max_concurrent_features: usize,
     max_rejections: u32,
+    max_concurrent_runs: usize,
     require_critic_approval: bool,
     require_architect_approval: bool,
 }
@@ -479,6 +1204,7 @@ impl Default for ConfigDefaults {
             mode: "lab",
             max_concurrent_features: 3,
             max_rejections: 3,
+            max_concurrent_runs: DEFAULT_MAX_CONCURRENT_RUNS,
             require_critic_approval: true,
             require_architect_approval: false,
         }
@@ -493,6 +1219,8 @@ struct ProviderInfo {
     name: String,
     default_model: String,
     supports_base_url: bool,
+    supports_tools: bool,
+    supports_structured_output: bool,
     env_var: String,
 }
 
@@ -501,51 +1229,33 @@ struct ProvidersResponse {
     providers: Vec<ProviderInfo>,
 }
 
+#[derive(Debug, Deserialize)]
+struct ProviderModelsQuery {
+    base_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+struct ProviderModelsResponse {
+    base_url: String,
+    models: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
 fn get_provider_info() -> Vec<ProviderInfo> {
-    vec![
-        ProviderInfo {
-            id: "anthropic".to_string(),
-            name: "Anthropic".to_string(),
-            default_model: "claude-sonnet-4-20250514".to_string(),
-            supports_base_url: false,
-            env_var: "ANTHROPIC_API_KEY".to_string(),
-        },
-        ProviderInfo {
-            id: "openai".to_string(),
-            name: "OpenAI".to_string(),
-            default_model: "gpt-4o".to_string(),
-            supports_base_url: true,
-            env_var: "OPENAI_API_KEY".to_string(),
-        },
-        ProviderInfo {
-            id: "gemini".to_string(),
-            name: "Gemini".to_string(),
-            default_model: "gemini-2.0-flash-exp".to_string(),
-            supports_base_url: false,
-            env_var: "GEMINI_API_KEY".to_string(),
-        },
-        ProviderInfo {
-            id: "openrouter".to_string(),
-            name: "OpenRouter".to_string(),
-            default_model: "anthropic/claude-3.5-sonnet".to_string(),
-            supports_base_url: false,
-            env_var: "OPENROUTER_API_KEY".to_string(),
-        },
-        ProviderInfo {
-            id: "grok".to_string(),
-            name: "Grok".to_string(),
-            default_model: "grok-2".to_string(),
-            supports_base_url: false,
-            env_var: "XAI_API_KEY".to_string(),
-        },
-        ProviderInfo {
-            id: "deepseek".to_string(),
-            name: "DeepSeek".to_string(),
-            default_model: "deepseek-chat".to_string(),
-            supports_base_url: false,
-            env_var: "DEEPSEEK_API_KEY".to_string(),
-        },
-    ]
+    registry::global()
+        .list()
+        .iter()
+        .map(|spec| ProviderInfo {
+            id: spec.name.clone(),
+            name: spec.display_name.clone(),
+            default_model: spec.default_model.clone(),
+            supports_base_url: spec.capabilities.base_url,
+            supports_tools: spec.capabilities.tools,
+            supports_structured_output: spec.capabilities.structured_output,
+            env_var: spec.api_key_env.clone().unwrap_or_default(),
+        })
+        .collect()
 }
 
 // === OpenAPI Definition ===
@@ -559,18 +1269,29 @@ fn get_provider_info() -> Vec<ProviderInfo> {
     ),
     paths(
         get_status,
+        list_queue,
+        get_queued_run,
+        set_run_priority,
+        cancel_queued_run,
         start_swarm,
+        execute_swarm,
         stop_swarm,
+        pause_swarm,
+        resume_swarm,
+        skip_unknown,
+        cancel_feature,
         handle_approval,
         get_config,
         update_config,
         get_providers,
+        list_local_models,
         search_memory,
         list_braindump,
         create_idea,
         list_features,
         delete_feature,
         ignite_feature,
+        garden_project,
         list_snapshots,
         delete_snapshot,
         rollback_to_snapshot,
@@ -578,6 +1299,7 @@ fn get_provider_info() -> Vec<ProviderInfo> {
         reply_to_inbox,
         list_inbox_history,
         get_project_status,
+        get_project_pipeline,
         get_project_profile,
         init_project,
         list_prompts,
@@ -586,12 +1308,23 @@ fn get_provider_info() -> Vec<ProviderInfo> {
         list_documents,
         get_document,
         update_document,
+        get_usage,
+        list_transcripts,
+        get_transcript,
+        list_interrupted_runs,
+        resume_run,
         save_api_keys
     ),
     components(
         schemas(
             SwarmStatus,
+            RateLimitStatus,
             ApiResponse,
+            RunQueuedResponse,
+            QueuedRunResponse,
+            RunQueueResponse,
+            QueuedRunDetailResponse,
+            RunPriorityRequest,
             StartSwarmRequest,
             StopSwarmRequest,
             ApiSettings,
@@ -602,6 +1335,17 @@ fn get_provider_info() -> Vec<ProviderInfo> {
             PersistedConfig,
             ProvidersResponse,
             ProviderInfo,
+            ProviderModelsResponse,
+            UsageResponse,
+            UsageTotalsResponse,
+            UsageGroupResponse,
+            UsageEntryResponse,
+            TranscriptListResponse,
+            TranscriptSummaryResponse,
+            TranscriptResponse,
+            RunResponse,
+            RunListResponse,
+            ResumeRunRequest,
             MemorySearchRequest,
             MemorySearchResponse,
             MemoryResult,
@@ -612,6 +1356,8 @@ fn get_provider_info() -> Vec<ProviderInfo> {
             FeatureResponse,
             IgniteRequest,
             IgniteResponse,
+            GardenRequest,
+            GardenResponse,
             SnapshotResponse,
             RollbackRequest,
             RollbackResponse,
@@ -620,6 +1366,8 @@ fn get_provider_info() -> Vec<ProviderInfo> {
             InboxReplyRequest,
             InboxReplyResponse,
             ProjectStatusResponse,
+            ModePolicyResponse,
+            PipelineResponse,
             InitProjectRequest,
             InitProjectResponse,
             ProfileResponse,
@@ -632,11 +1380,13 @@ fn get_provider_info() -> Vec<ProviderInfo> {
             UpdatePromptResponse,
             DocumentListResponse,
             DocumentResponse,
+            SpecEditResponse,
             UpdateDocumentRequest
         )
     ),
     tags(
         (name = "swarm", description = "Swarm management endpoints"),
+        (name = "queue", description = "Run queue and scheduling"),
         (name = "config", description = "Configuration management"),
         (name = "providers", description = "LLM provider discovery"),
         (name = "memory", description = "Memory search"),
@@ -645,7 +1395,10 @@ fn get_provider_info() -> Vec<ProviderInfo> {
         (name = "inbox", description = "Human-in-the-loop interactions"),
         (name = "project", description = "Project initialization"),
         (name = "prompts", description = "Prompt template management"),
-        (name = "documents", description = "Project document management")
+        (name = "documents", description = "Project document management"),
+        (name = "usage", description = "LLM token and cost ledger"),
+        (name = "transcripts", description = "Recorded LLM transcripts"),
+        (name = "runs", description = "Interrupted and resumable runs")
     )
 )]
 struct ApiDoc;
@@ -657,51 +1410,118 @@ struct ApiDoc;
     get,
     path = "/api/v1/swarm/status",
     tag = "swarm",
+    params(("run_id" = Option<String>, Query, description = "Run to report on (default: the latest active run)")),
     responses(
         (status = 200, description = "Current swarm status", body = SwarmStatus)
     )
 )]
-async fn get_status(State(state): State<SharedState>) -> Json<SwarmStatus> {
-    let status = state.swarm_status.read().await;
-    Json(status.clone())
+async fn get_status(
+    State(state): State<SharedState>,
+    axum::extract::Query(query): axum::extract::Query<RunTargetQuery>,
+) -> Json<SwarmStatus> {
+    let mut status = SwarmStatus::default();
+    {
+        let queue = state.runs.read().await;
+        status.queued = queue.queued().len();
+        status.running = queue.active().count();
+        if let Some(run) = queue.target(query.run_id.as_deref()) {
+            status.run_id = Some(run.id.clone());
+            status.status = run.status.clone();
+            status.active_agent = run.active_agent.clone();
+            status.pipeline_stage = u8::from(run.is_active());
+        }
+    }
+    status.rate_limits = rate_limit::stats()
+        .into_iter()
+        .map(|stats| RateLimitStatus {
+            provider: stats.provider,
+            queue_depth: stats.queued,
+            in_flight: stats.in_flight,
+            requests_last_minute: stats.requests_last_minute,
+            tokens_last_minute: stats.tokens_last_minute,
+            requests_per_minute: stats.limit.requests_per_minute,
+            tokens_per_minute: stats.limit.tokens_per_minute,
+            max_in_flight: stats.limit.max_in_flight,
+        })
+        .collect();
+    Json(status)
 }
 
-/// Start the swarm with a goal
+/// Queue a run of the swarm on a goal
 #[utoipa::path(
     post,
     path = "/api/v1/swarm/start",
     tag = "swarm",
     request_body = StartSwarmRequest,
     responses(
-        (status = 200, description = "Swarm started", body = ApiResponse)
+        (status = 200, description = "Run queued", body = RunQueuedResponse)
     )
 )]
 async fn start_swarm(
     State(state): State<SharedState>,
     Json(req): Json<StartSwarmRequest>,
-) -> Json<ApiResponse> {
-    {
-        let mut status = state.swarm_status.write().await;
-        status.status = "running".to_string();
-        status.active_agent = Some("unknowns_parser".to_string());
-        status.pipeline_stage = 1;
-    }
+) -> Json<RunQueuedResponse> {
+    launch_swarm(state, req, Launch::Plan).await
+}
 
-    println!("🚀 Starting swarm with goal: {}", req.goal);
+/// Queue a run that carries a goal through to merged code
+#[utoipa::path(
+    post,
+    path = "/api/v1/swarm/execute",
+    tag = "swarm",
+    request_body = StartSwarmRequest,
+    responses(
+        (status = 200, description = "Execution queued", body = RunQueuedResponse)
+    )
+)]
+async fn execute_swarm(
+    State(state): State<SharedState>,
+    Json(req): Json<StartSwarmRequest>,
+) -> Json<RunQueuedResponse> {
+    launch_swarm(state, req, Launch::Execute).await
+}
+
+/// What a launched coordinator does with its goal
+enum Launch {
+    /// Plan the goal
+    Plan,
+    /// Plan, then draft, build and merge
+    Execute,
+    /// Continue an interrupted run
+    Resume(String),
+}
+
+/// Abort a CLI run on Ctrl-C, so its worktrees are torn down before exiting
+fn cancel_on_ctrl_c(control: catalyst_core::swarm::RunControl) {
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            eprintln!("⏹️ Cancelling run...");
+            control.cancel();
+        }
+    });
+}
+
+/// Queue a coordinator for `req`
+async fn launch_swarm(
+    state: SharedState,
+    req: StartSwarmRequest,
+    launch: Launch,
+) -> Json<RunQueuedResponse> {
+    println!("🚀 Queueing swarm with goal: {}", req.goal);
 
     // Build config from settings
     let mut config = CoordinatorConfig::default();
+    let persisted = PersistedConfig::load().await;
+    persisted.apply_generation(&mut config);
+    persisted.apply_mode(&mut config);
+    persisted.apply_review_panel(&mut config);
     if let Some(settings) = &req.settings {
         // Map global provider from string to enum
         if let Some(ref p) = settings.global_provider {
-            config.global_provider = match p.as_str() {
-                "anthropic" => LlmProvider::Anthropic,
-                "openai" => LlmProvider::OpenAI,
-                "gemini" => LlmProvider::Gemini,
-                "openrouter" => LlmProvider::OpenRouter,
-                "grok" => LlmProvider::Grok,
-                "deepseek" => LlmProvider::DeepSeek,
-                _ => LlmProvider::Anthropic, // fallback
+            config.global_provider = if registry::global().get(p).is_some() {
+                LlmProvider::from(p.as_str())
+            } else {
+                LlmProvider::Anthropic // fallback
             };
         }
         if let Some(ref m) = settings.global_model {
@@ -710,18 +1530,16 @@ async fn start_swarm(
         if let Some(ref url) = settings.base_url {
             config.base_url = Some(url.clone());
         }
+        if let Some(ref url) = settings.local_base_url {
+            config.local_base_url = Some(url.clone());
+        }
         // Map per-agent providers from strings to enums
         if let Some(ref providers) = settings.per_agent_providers {
             for (agent, provider_str) in providers {
-                let provider = match provider_str.as_str() {
-                    "anthropic" => LlmProvider::Anthropic,
-                    "openai" => LlmProvider::OpenAI,
-                    "gemini" => LlmProvider::Gemini,
-                    "openrouter" => LlmProvider::OpenRouter,
-                    "grok" => LlmProvider::Grok,
-                    "deepseek" => LlmProvider::DeepSeek,
-                    _ => continue, // skip invalid
-                };
+                if registry::global().get(provider_str).is_none() {
+                    continue; // skip invalid
+                }
+                let provider = LlmProvider::from(provider_str.as_str());
                 config.per_agent_providers.insert(agent.clone(), provider);
             }
         }
@@ -732,7 +1550,10 @@ async fn start_swarm(
             config.per_agent_base_urls = urls.clone();
         }
         if let Some(ref mode) = settings.mode {
-            config.mode = mode.clone();
+            match mode.parse() {
+                Ok(mode) => config.mode = mode,
+                Err(e) => eprintln!("⚠️ {}; using {}", e, config.mode),
+            }
         }
         if let Some(crit) = settings.require_critic_approval {
             config.require_critic_approval = crit;
@@ -744,6 +1565,9 @@ async fn start_swarm(
         if let Some(max_conc) = settings.max_concurrent_features {
             config.max_concurrent_features = max_conc;
         }
+        if settings.max_concurrent_research.is_some() {
+            config.max_concurrent_research = settings.max_concurrent_research;
+        }
         if let Some(max_rej) = settings.max_rejections {
             config.max_rejections = max_rej;
         }
@@ -755,6 +1579,61 @@ async fn start_swarm(
             std::env::set_var("SEARXNG_URL", searx);
             config.searxng_url = Some(searx.clone());
         }
+        if let Some(ref name) = settings.cassette {
+            let mode = if settings.record_cassette.unwrap_or(false) {
+                CassetteMode::Record
+            } else {
+                CassetteMode::Replay
+            };
+            config.cassette = Some(CassetteConfig::named(name, mode));
+        }
+        if settings.max_cost_usd.is_some() {
+            config.max_cost_usd = settings.max_cost_usd;
+        }
+        if settings.max_tokens.is_some() {
+            config.max_tokens = settings.max_tokens;
+        }
+        if let Some(ref action) = settings.budget_action {
+            config.budget_action = match action.as_str() {
+                "abort" => BudgetAction::Abort,
+                _ => BudgetAction::Pause,
+            };
+        }
+        if let Some(attempts) = settings.max_retries {
+            config.retry.max_attempts = attempts.max(1);
+        }
+        if let Some(ref fallbacks) = settings.fallback_models {
+            config.fallbacks = fallbacks
+                .iter()
+                .filter_map(|f| FallbackModel::parse(f))
+                .filter(|f| registry::global().get(f.provider.as_str()).is_some())
+                .collect();
+        }
+        if let Some(enabled) = settings.response_cache {
+            config.response_cache = enabled;
+        }
+        if settings.cache_ttl_secs.is_some() {
+            config.cache_ttl_secs = settings.cache_ttl_secs;
+        }
+        if let Some(bypass) = settings.cache_bypass {
+            config.cache_bypass = bypass;
+        }
+        if settings.max_repairs.is_some() {
+            config.max_repairs = settings.max_repairs;
+        }
+        if let Some(ref generation) = settings.generation {
+            config.generation = generation.clone();
+        }
+        if settings.review_panel.is_some() {
+            config.review_panel = settings.review_panel.clone();
+        }
+    }
+    match PipelineGraph::load_project() {
+        Ok(graph) => config.pipeline = graph,
+        Err(e) => {
+            eprintln!("❌ {:#}", e);
+            return Json(RunQueuedResponse::rejected(format!("{:#}", e)));
+        }
     }
 
     // Create channels
@@ -765,93 +1644,386 @@ async fn start_swarm(
     // Create inbox command channel for human-in-the-loop
     let (inbox_tx, inbox_rx) = mpsc::channel::<catalyst_core::swarm::CoordinatorCommand>(10);
 
-    // Store the inbox sender in state so reply_to_inbox can access it
-    *state.coordinator_tx.write().await = Some(inbox_tx);
+    let coordinator = Coordinator::new(config, Arc::clone(&state.db))
+        .with_event_channel(event_mpsc_tx)
+        .with_approval_channel(approval_tx)
+        .with_inbox_channel(inbox_rx);
+    let (run_id, kind) = match &launch {
+        Launch::Plan => (coordinator.run_id().to_string(), "plan"),
+        Launch::Execute => (coordinator.run_id().to_string(), "execute"),
+        Launch::Resume(id) => (id.clone(), "resume"),
+    };
 
-    let broadcast_tx = state.event_tx.clone();
-    let state_clone = state.clone();
-    let goal = req.goal.clone();
+    {
+        let mut queue = state.runs.write().await;
+        if queue.get(&run_id).is_some_and(|run| !run.is_finished()) {
+            return Json(RunQueuedResponse::rejected(format!(
+                "Run {} is already queued or running",
+                run_id
+            )));
+        }
+        queue.runs.retain(|run| run.id != run_id);
+        queue.push(RunEntry {
+            id: run_id.clone(),
+            goal: req.goal.clone(),
+            kind,
+            priority: req.priority.unwrap_or(0),
+            seq: 0,
+            status: "queued".to_string(),
+            active_agent: None,
+            error: None,
+            queued_at: chrono::Utc::now(),
+            started_at: None,
+            finished_at: None,
+            events: Default::default(),
+            commands: inbox_tx,
+            approvals: HashMap::new(),
+            pending: Some(std::sync::Mutex::new((coordinator, launch))),
+        });
+    }
 
-    // Bridge events to broadcast
+    // Bridge events to the run's log and the broadcast
+    let broadcast_tx = state.event_tx.clone();
+    let state_events = state.clone();
+    let id = run_id.clone();
     tokio::spawn(async move {
-        while let Some(event) = event_mpsc_rx.recv().await {
+        while let Some(mut event) = event_mpsc_rx.recv().await {
+            event.run_id.get_or_insert_with(|| id.clone());
+            if let Some(entry) = state_events.runs.write().await.get_mut(&id) {
+                entry.note_event(&event);
+            }
             let _ = broadcast_tx.send(event);
         }
     });
 
     // Handle approval requests
     let state_approval = state.clone();
+    let id = run_id.clone();
     tokio::spawn(async move {
         while let Some((request, responder)) = approval_rx.recv().await {
-            println!("⚠️ Approval needed: {}", request.summary);
-            state_approval
-                .pending_approvals
-                .write()
-                .await
-                .insert(request.decision_id.clone(), responder);
+            println!("⚠️ Approval needed ({}): {}", id, request.summary);
+            if let Some(entry) = state_approval.runs.write().await.get_mut(&id) {
+                entry
+                    .approvals
+                    .insert(request.decision_id.clone(), responder);
+            }
         }
     });
 
-    // Run the coordinator
-    let db = Arc::clone(&state.db);
-    tokio::spawn(async move {
-        let mut coordinator = Coordinator::new(config, db)
-            .with_event_channel(event_mpsc_tx)
-            .with_approval_channel(approval_tx)
-            .with_inbox_channel(inbox_rx)
-            .with_research_agent();
-
-        match coordinator.run(&goal).await {
-            Ok(result) => {
-                println!("✅ Swarm completed: success={}", result.success);
-                let mut status = state_clone.swarm_status.write().await;
-                status.status = if result.success { "complete" } else { "failed" }.to_string();
-                status.active_agent = None;
-            }
-            Err(e) => {
-                eprintln!("❌ Swarm failed: {}", e);
-                let mut status = state_clone.swarm_status.write().await;
-                status.status = "error".to_string();
-                status.active_agent = None;
-            }
+    state.scheduler.notify_one();
+    let position = state
+        .runs
+        .read()
+        .await
+        .queued()
+        .iter()
+        .position(|run| run.id == run_id)
+        .map(|position| position + 1);
+
+    Json(RunQueuedResponse {
+        success: true,
+        message: format!("Run {} queued with goal: {}", run_id, req.goal),
+        run_id: Some(run_id),
+        position,
+    })
+}
+
+/// List runs that stopped before finishing (e.g. the server died mid-run)
+#[utoipa::path(
+    get,
+    path = "/api/v1/runs/interrupted",
+    tag = "runs",
+    responses(
+        (status = 200, description = "Interrupted runs, newest first", body = RunListResponse)
+    )
+)]
+async fn list_interrupted_runs(State(state): State<SharedState>) -> Json<RunListResponse> {
+    let runs = RunManager::new(&state.db)
+        .list_interrupted()
+        .unwrap_or_default();
+    // Runs this server is driving (or has queued) are not interrupted
+    let queue = state.runs.read().await;
+    Json(RunListResponse {
+        runs: runs
+            .into_iter()
+            .filter(|run| queue.get(&run.id).is_none_or(|entry| entry.is_finished()))
+            .map(Into::into)
+            .collect(),
+    })
+}
+
+/// Resume an interrupted run from its checkpoints
+#[utoipa::path(
+    post,
+    path = "/api/v1/runs/{id}/resume",
+    tag = "runs",
+    params(("id" = String, Path, description = "Run ID")),
+    request_body = ResumeRunRequest,
+    responses(
+        (status = 200, description = "Run queued for resumption", body = RunQueuedResponse)
+    )
+)]
+async fn resume_run(
+    State(state): State<SharedState>,
+    axum::extract::Path(id): axum::extract::Path<String>,
+    body: Option<Json<ResumeRunRequest>>,
+) -> Json<RunQueuedResponse> {
+    let run = match RunManager::new(&state.db).load(&id) {
+        Ok(run) => run,
+        Err(e) => return Json(RunQueuedResponse::rejected(e.to_string())),
+    };
+    if run.status == RunStatus::Completed {
+        return Json(RunQueuedResponse::rejected(format!(
+            "Run {} already completed",
+            id
+        )));
+    }
+
+    let (priority, settings) = match body {
+        Some(Json(body)) => (body.priority, body.settings),
+        None => (None, None),
+    };
+    let req = StartSwarmRequest {
+        goal: run.goal,
+        priority,
+        settings,
+    };
+    launch_swarm(state, req, Launch::Resume(id)).await
+}
+
+#[derive(Deserialize)]
+struct RunTargetQuery {
+    /// Run to control (default: the latest active run)
+    run_id: Option<String>,
+}
+
+/// Stop/abort a run (a queued run is taken off the queue)
+#[utoipa::path(
+    post,
+    path = "/api/v1/swarm/stop",
+    tag = "swarm",
+    params(("run_id" = Option<String>, Query, description = "Run to stop (default: the latest active run)")),
+    request_body = StopSwarmRequest,
+    responses(
+        (status = 200, description = "Swarm stopped", body = ApiResponse)
+    )
+)]
+async fn stop_swarm(
+    State(state): State<SharedState>,
+    axum::extract::Query(query): axum::extract::Query<RunTargetQuery>,
+    Json(req): Json<StopSwarmRequest>,
+) -> Json<ApiResponse> {
+    let reason = req.reason.unwrap_or_else(|| "Swarm stopped".to_string());
+    let target = state
+        .runs
+        .read()
+        .await
+        .target(query.run_id.as_deref())
+        .filter(|run| !run.is_finished())
+        .map(|run| run.id.clone());
+    match target {
+        Some(id) => stop_run(&state, &id, reason).await,
+        None => Json(ApiResponse {
+            success: false,
+            message: "No swarm is running".to_string(),
+        }),
+    }
+}
+
+/// Take a queued run off the queue, or abort a running one
+///
+/// An aborted run is "stopping" until its coordinator returns; `drive_run`
+/// then records how it ended and frees its slot.
+async fn stop_run(state: &SharedState, run_id: &str, message: String) -> Json<ApiResponse> {
+    use catalyst_core::swarm::CoordinatorCommand;
+
+    let mut queue = state.runs.write().await;
+    let Some(entry) = queue.get_mut(run_id) else {
+        return Json(ApiResponse {
+            success: false,
+            message: format!("Run {} not found", run_id),
+        });
+    };
+    if entry.is_queued() {
+        entry.finish("cancelled", None);
+        drop(queue);
+        state.scheduler.notify_one();
+    } else if entry.is_active() {
+        entry.status = "stopping".to_string();
+        entry.active_agent = None;
+        let commands = entry.commands.clone();
+        drop(queue);
+        // A coordinator that already returned has nothing left to abort
+        let _ = commands.send(CoordinatorCommand::Abort).await;
+    } else {
+        return Json(ApiResponse {
+            success: false,
+            message: format!("Run {} already finished", run_id),
+        });
+    }
+
+    Json(ApiResponse {
+        success: true,
+        message,
+    })
+}
+
+/// Send a run control command to an active run
+///
+/// On success the run's status becomes `status`, when given.
+async fn send_control(
+    state: &SharedState,
+    run_id: Option<&str>,
+    command: catalyst_core::swarm::CoordinatorCommand,
+    status: Option<&str>,
+    message: String,
+) -> Json<ApiResponse> {
+    let target = state
+        .runs
+        .read()
+        .await
+        .target(run_id)
+        .filter(|run| run.is_active() && !run.is_stopping())
+        .map(|run| (run.id.clone(), run.commands.clone()));
+    let Some((id, commands)) = target else {
+        return Json(ApiResponse {
+            success: false,
+            message: "No swarm is running".to_string(),
+        });
+    };
+    let sent = commands.send(command).await.is_ok();
+    if let Some(status) = status.filter(|_| sent) {
+        let mut queue = state.runs.write().await;
+        // The run may have been stopped or finished while the command was sent
+        if let Some(entry) = queue
+            .get_mut(&id)
+            .filter(|run| run.is_active() && !run.is_stopping())
+        {
+            entry.status = status.to_string();
         }
-    });
+    }
+    Json(ApiResponse {
+        success: sent,
+        message: if sent {
+            message
+        } else {
+            "No swarm is running".to_string()
+        },
+    })
+}
+
+/// Pause a running swarm at its next stage boundary
+#[utoipa::path(
+    post,
+    path = "/api/v1/swarm/pause",
+    tag = "swarm",
+    params(("run_id" = Option<String>, Query, description = "Run to pause (default: the latest active run)")),
+    responses(
+        (status = 200, description = "Swarm pausing", body = ApiResponse)
+    )
+)]
+async fn pause_swarm(
+    State(state): State<SharedState>,
+    axum::extract::Query(query): axum::extract::Query<RunTargetQuery>,
+) -> Json<ApiResponse> {
+    use catalyst_core::swarm::CoordinatorCommand;
+
+    send_control(
+        &state,
+        query.run_id.as_deref(),
+        CoordinatorCommand::Pause,
+        Some("paused"),
+        "Swarm will pause before its next step".to_string(),
+    )
+    .await
+}
+
+/// Continue a paused swarm
+#[utoipa::path(
+    post,
+    path = "/api/v1/swarm/resume",
+    tag = "swarm",
+    params(("run_id" = Option<String>, Query, description = "Run to resume (default: the latest active run)")),
+    responses(
+        (status = 200, description = "Swarm resumed", body = ApiResponse)
+    )
+)]
+async fn resume_swarm(
+    State(state): State<SharedState>,
+    axum::extract::Query(query): axum::extract::Query<RunTargetQuery>,
+) -> Json<ApiResponse> {
+    use catalyst_core::swarm::CoordinatorCommand;
+
+    send_control(
+        &state,
+        query.run_id.as_deref(),
+        CoordinatorCommand::Resume,
+        Some("running"),
+        "Swarm resumed".to_string(),
+    )
+    .await
+}
+
+/// Skip an unknown of a running swarm, leaving it undecided
+#[utoipa::path(
+    post,
+    path = "/api/v1/swarm/unknowns/{id}/skip",
+    tag = "swarm",
+    params(
+        ("id" = String, Path, description = "Unknown ID"),
+        ("run_id" = Option<String>, Query, description = "Run of the unknown (default: the latest active run)")
+    ),
+    responses(
+        (status = 200, description = "Unknown skipped", body = ApiResponse)
+    )
+)]
+async fn skip_unknown(
+    State(state): State<SharedState>,
+    axum::extract::Path(id): axum::extract::Path<String>,
+    axum::extract::Query(query): axum::extract::Query<RunTargetQuery>,
+) -> Json<ApiResponse> {
+    use catalyst_core::swarm::CoordinatorCommand;
 
-    Json(ApiResponse {
-        success: true,
-        message: format!("Swarm started with goal: {}", req.goal),
-    })
+    let message = format!("Unknown {} skipped", id);
+    send_control(
+        &state,
+        query.run_id.as_deref(),
+        CoordinatorCommand::SkipUnknown(id),
+        None,
+        message,
+    )
+    .await
 }
 
-/// Stop/abort the running swarm
+/// Stop building a feature of a running swarm and remove its worktree
 #[utoipa::path(
     post,
-    path = "/api/v1/swarm/stop",
+    path = "/api/v1/swarm/features/{id}/cancel",
     tag = "swarm",
-    request_body = StopSwarmRequest,
+    params(
+        ("id" = String, Path, description = "Feature ID"),
+        ("run_id" = Option<String>, Query, description = "Run building the feature (default: the latest active run)")
+    ),
     responses(
-        (status = 200, description = "Swarm stopped", body = ApiResponse)
+        (status = 200, description = "Feature cancelled", body = ApiResponse)
     )
 )]
-async fn stop_swarm(
+async fn cancel_feature(
     State(state): State<SharedState>,
-    Json(req): Json<StopSwarmRequest>,
+    axum::extract::Path(id): axum::extract::Path<String>,
+    axum::extract::Query(query): axum::extract::Query<RunTargetQuery>,
 ) -> Json<ApiResponse> {
-    // Send abort command if coordinator is running
-    if let Some(tx) = state.coordinator_tx.read().await.as_ref() {
-        let _ = tx
-            .send(catalyst_core::swarm::CoordinatorCommand::Abort)
-            .await;
-    }
-    // Update status
-    let mut status = state.swarm_status.write().await;
-    status.status = "stopped".to_string();
-    status.active_agent = None;
+    use catalyst_core::swarm::CoordinatorCommand;
 
-    Json(ApiResponse {
-        success: true,
-        message: req.reason.unwrap_or_else(|| "Swarm stopped".to_string()),
-    })
+    let message = format!("Feature {} cancelled", id);
+    send_control(
+        &state,
+        query.run_id.as_deref(),
+        CoordinatorCommand::CancelFeature(id),
+        None,
+        message,
+    )
+    .await
 }
 
 /// Handle approval/rejection of pending decisions
@@ -868,11 +2040,14 @@ async fn handle_approval(
     State(state): State<SharedState>,
     Json(req): Json<ApprovalApiRequest>,
 ) -> Json<ApiResponse> {
-    let responder = state
-        .pending_approvals
-        .write()
-        .await
-        .remove(&req.decision_id);
+    let responder = {
+        let mut queue = state.runs.write().await;
+        queue
+            .runs
+            .iter_mut()
+            .filter(|run| req.run_id.as_deref().is_none_or(|id| run.id == id))
+            .find_map(|run| run.approvals.remove(&req.decision_id))
+    };
 
     if let Some(tx) = responder {
         let response = ApprovalResponse {
@@ -903,6 +2078,140 @@ async fn handle_approval(
     }
 }
 
+// === Run Queue API Handlers ===
+
+/// List queued, running and recently finished runs
+#[utoipa::path(
+    get,
+    path = "/api/v1/queue",
+    tag = "queue",
+    responses(
+        (status = 200, description = "Runs known to the server", body = RunQueueResponse)
+    )
+)]
+async fn list_queue(State(state): State<SharedState>) -> Json<RunQueueResponse> {
+    let max_concurrent_runs = PersistedConfig::load()
+        .await
+        .max_concurrent_runs
+        .unwrap_or(DEFAULT_MAX_CONCURRENT_RUNS)
+        .max(1);
+    let queue = state.runs.read().await;
+
+    let mut runs: Vec<QueuedRunResponse> = queue
+        .active()
+        .map(|run| QueuedRunResponse::new(run, None))
+        .collect();
+    runs.extend(
+        queue
+            .queued()
+            .into_iter()
+            .enumerate()
+            .map(|(i, run)| QueuedRunResponse::new(run, Some(i + 1))),
+    );
+    runs.extend(
+        queue
+            .runs
+            .iter()
+            .rev()
+            .filter(|run| run.is_finished())
+            .map(|run| QueuedRunResponse::new(run, None)),
+    );
+
+    Json(RunQueueResponse {
+        max_concurrent_runs,
+        runs,
+    })
+}
+
+/// Inspect a run: its status, pending approvals and events
+#[utoipa::path(
+    get,
+    path = "/api/v1/queue/{id}",
+    tag = "queue",
+    params(("id" = String, Path, description = "Run ID")),
+    responses(
+        (status = 200, description = "Run details", body = QueuedRunDetailResponse),
+        (status = 404, description = "Run not found")
+    )
+)]
+async fn get_queued_run(
+    State(state): State<SharedState>,
+    axum::extract::Path(id): axum::extract::Path<String>,
+) -> Result<Json<QueuedRunDetailResponse>, StatusCode> {
+    let queue = state.runs.read().await;
+    let run = queue.get(&id).ok_or(StatusCode::NOT_FOUND)?;
+    let position = queue
+        .queued()
+        .iter()
+        .position(|queued| queued.id == id)
+        .map(|i| i + 1);
+
+    Ok(Json(QueuedRunDetailResponse {
+        run: QueuedRunResponse::new(run, position),
+        events: run.events.iter().cloned().collect(),
+    }))
+}
+
+/// Change the priority of a queued run
+#[utoipa::path(
+    post,
+    path = "/api/v1/queue/{id}/priority",
+    tag = "queue",
+    params(("id" = String, Path, description = "Run ID")),
+    request_body = RunPriorityRequest,
+    responses(
+        (status = 200, description = "Run reordered", body = ApiResponse)
+    )
+)]
+async fn set_run_priority(
+    State(state): State<SharedState>,
+    axum::extract::Path(id): axum::extract::Path<String>,
+    Json(req): Json<RunPriorityRequest>,
+) -> Json<ApiResponse> {
+    let mut queue = state.runs.write().await;
+    let response = match queue.get_mut(&id) {
+        Some(run) if run.is_queued() => {
+            run.priority = req.priority;
+            let position = queue
+                .queued()
+                .iter()
+                .position(|queued| queued.id == id)
+                .map_or(0, |i| i + 1);
+            ApiResponse {
+                success: true,
+                message: format!("Run {} is now #{} in the queue", id, position),
+            }
+        }
+        Some(_) => ApiResponse {
+            success: false,
+            message: format!("Run {} is no longer queued", id),
+        },
+        None => ApiResponse {
+            success: false,
+            message: format!("Run {} not found", id),
+        },
+    };
+    Json(response)
+}
+
+/// Cancel a run: queued runs are dropped, running ones aborted
+#[utoipa::path(
+    post,
+    path = "/api/v1/queue/{id}/cancel",
+    tag = "queue",
+    params(("id" = String, Path, description = "Run ID")),
+    responses(
+        (status = 200, description = "Run cancelled", body = ApiResponse)
+    )
+)]
+async fn cancel_queued_run(
+    State(state): State<SharedState>,
+    axum::extract::Path(id): axum::extract::Path<String>,
+) -> Json<ApiResponse> {
+    let message = format!("Run {} cancelled", id);
+    stop_run(&state, &id, message).await
+}
+
 /// Search memory
 #[utoipa::path(
     post,
@@ -939,36 +2248,36 @@ async fn search_memory(
 }
 
 /// SSE endpoint for real-time events with heartbeat
+///
+/// `?run_id=` restricts the stream to one run's events.
 async fn events(
     State(state): State<SharedState>,
+    axum::extract::Query(query): axum::extract::Query<RunTargetQuery>,
 ) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
     let rx = state.event_tx.subscribe();
+    let run_id = query.run_id;
 
     // Use timeout-based stream with heartbeat every 15 seconds
-    let stream = stream::unfold(
-        (rx, tokio::time::Instant::now()),
-        |(mut rx, _last_event)| async move {
+    let stream = stream::unfold((rx, run_id), |(mut rx, run_id)| async move {
+        loop {
             let timeout = tokio::time::timeout(std::time::Duration::from_secs(15), rx.recv()).await;
 
-            match timeout {
+            return match timeout {
                 Ok(Ok(event)) => {
+                    if run_id.is_some() && event.run_id != run_id {
+                        continue; // Another run's event
+                    }
                     let json = serde_json::to_string(&event).unwrap_or_default();
-                    Some((
-                        Ok(Event::default().data(json)),
-                        (rx, tokio::time::Instant::now()),
-                    ))
+                    Some((Ok(Event::default().data(json)), (rx, run_id)))
                 }
                 Ok(Err(_)) => None, // Channel closed
                 Err(_) => {
                     // Timeout - send heartbeat comment
-                    Some((
-                        Ok(Event::default().comment("heartbeat")),
-                        (rx, tokio::time::Instant::now()),
-                    ))
+                    Some((Ok(Event::default().comment("heartbeat")), (rx, run_id)))
                 }
-            }
-        },
-    );
+            };
+        }
+    });
 
     Sse::new(stream).keep_alive(KeepAlive::default())
 }
@@ -1037,6 +2346,11 @@ async fn save_api_keys(Json(req): Json<ApiKeysRequest>) -> Json<ApiResponse> {
             env_content.push_str(&format!("DEEPSEEK_API_KEY={}\n", key));
         }
     }
+    if let Some(key) = &req.local {
+        if !key.is_empty() {
+            env_content.push_str(&format!("{}={}\n", local::LOCAL_API_KEY_ENV, key));
+        }
+    }
 
     // Write .env file
     let env_path = catalyst_dir.join(".env");
@@ -1152,6 +2466,8 @@ async fn list_features(State(state): State<SharedState>) -> Json<Vec<FeatureResp
                     title: f.title,
                     stage: format!("{:?}", f.stage),
                     description: f.description,
+                    depends_on: f.depends_on,
+                    error: f.error,
                     created_at: f.created_at.to_rfc3339(),
                 })
                 .collect(),
@@ -1211,6 +2527,20 @@ async fn ignite_feature(
     // Load idea and create feature
     match cm.load_idea(&req.idea_id) {
         Ok(idea) => match fm.create(&idea.content) {
+            Ok(feature) if !req.depends_on.is_empty() => {
+                match fm.set_depends_on(&feature.id, &req.depends_on) {
+                    Ok(()) => Json(IgniteResponse {
+                        success: true,
+                        feature_id: Some(feature.id),
+                        error: None,
+                    }),
+                    Err(e) => Json(IgniteResponse {
+                        success: false,
+                        feature_id: Some(feature.id),
+                        error: Some(e.to_string()),
+                    }),
+                }
+            }
             Ok(feature) => Json(IgniteResponse {
                 success: true,
                 feature_id: Some(feature.id),
@@ -1230,6 +2560,75 @@ async fn ignite_feature(
     }
 }
 
+/// Sweep the project for maintenance work and file it
+#[utoipa::path(
+    post,
+    path = "/api/v1/reactor/garden",
+    tag = "reactor",
+    request_body = GardenRequest,
+    responses(
+        (status = 200, description = "Gardener report and what was filed", body = GardenResponse)
+    )
+)]
+async fn garden_project(
+    State(state): State<SharedState>,
+    Json(req): Json<GardenRequest>,
+) -> Json<GardenResponse> {
+    Json(match garden(&state.db, req.dry_run).await {
+        Ok((report, harvest)) => GardenResponse {
+            success: true,
+            report: Some(report),
+            harvest,
+            error: None,
+        },
+        Err(e) => GardenResponse {
+            success: false,
+            report: None,
+            harvest: None,
+            error: Some(format!("{:#}", e)),
+        },
+    })
+}
+
+/// Run the Gardener over the current project, filing its issues unless `dry_run`
+async fn garden(
+    db: &CatalystDb,
+    dry_run: bool,
+) -> anyhow::Result<(GardenReport, Option<GardenHarvest>)> {
+    let root = std::env::current_dir()?;
+    let gardener = GardenerSkill::default();
+    let report = gardener.analyze(&root).await?;
+    let harvest = if dry_run {
+        None
+    } else {
+        Some(gardener.plant(&report, db)?)
+    };
+    Ok((report, harvest))
+}
+
+/// Sweep the project every `garden_interval_mins` while the server runs
+async fn run_gardener(state: SharedState) {
+    loop {
+        let interval = PersistedConfig::load().await.garden_interval_mins;
+        let Some(mins) = interval.filter(|m| *m > 0) else {
+            // Not scheduled; check the config again later
+            tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+            continue;
+        };
+        tokio::time::sleep(std::time::Duration::from_secs(mins * 60)).await;
+        match garden(&state.db, false).await {
+            Ok((report, Some(harvest))) => println!(
+                "🌱 Gardener: {} issues, {} features and {} ideas filed",
+                report.issues.len(),
+                harvest.features.len(),
+                harvest.ideas.len()
+            ),
+            Ok(_) => {}
+            Err(e) => eprintln!("⚠️ Gardener failed: {:#}", e),
+        }
+    }
+}
+
 // === Snapshot API Handlers ===
 
 /// List all snapshots
@@ -1412,9 +2811,12 @@ async fn reply_to_inbox(
         });
     }
 
-    // 2. Wake coordinator if channel available
-    if let Some(tx) = state.coordinator_tx.read().await.as_ref() {
-        let _ = tx.send(CoordinatorCommand::Resume(id.clone())).await;
+    // 2. Wake the coordinators (each ignores interactions it did not ask)
+    for run in state.runs.read().await.active() {
+        let _ = run
+            .commands
+            .send(CoordinatorCommand::InteractionResolved(id.clone()))
+            .await;
     }
 
     Json(InboxReplyResponse {
@@ -1463,6 +2865,54 @@ struct ProjectStatusResponse {
     project_type: Option<String>,
     total_files: Option<u32>,
     total_loc: Option<u32>,
+    /// Policy of the running swarm's mode, or of the configured mode when idle
+    policy: ModePolicyResponse,
+}
+
+/// What a mode changes about the pipeline
+#[derive(Serialize, ToSchema)]
+struct ModePolicyResponse {
+    /// speed_run, lab or fortress
+    mode: String,
+    /// Unknowns Parser, research and decisions run before planning
+    parse_unknowns: bool,
+    /// Length limits block merges
+    enforce_constraints: bool,
+    /// `unwrap()` blocks merges
+    forbid_unwrap: bool,
+    /// Red Team tests are written before the build and block merges until they pass
+    red_team_tests: bool,
+    /// Critic reviews the code diff before merging
+    critic_reviews_diffs: bool,
+}
+
+impl From<ModePolicy> for ModePolicyResponse {
+    fn from(policy: ModePolicy) -> Self {
+        Self {
+            mode: policy.mode.to_string(),
+            parse_unknowns: policy.parse_unknowns,
+            enforce_constraints: policy.enforce_constraints,
+            forbid_unwrap: policy.forbid_unwrap,
+            red_team_tests: policy.red_team_tests,
+            critic_reviews_diffs: policy.critic_reviews_diffs,
+        }
+    }
+}
+
+/// Mode of the running swarm, or the configured mode when idle
+async fn active_mode(state: &SharedState) -> ProjectMode {
+    use catalyst_core::state::ProjectState;
+
+    if state.runs.read().await.active().next().is_some() {
+        if let Some(mode) = ProjectState::load(&state.db).ok().and_then(|s| s.mode) {
+            return mode;
+        }
+    }
+    PersistedConfig::load()
+        .await
+        .mode
+        .and_then(|mode| mode.parse().ok())
+        .unwrap_or_default()
 }
 
 /// Get project initialization status
@@ -1477,31 +2927,71 @@ struct ProjectStatusResponse {
 async fn get_project_status(State(state): State<SharedState>) -> Json<ProjectStatusResponse> {
     use catalyst_core::state::CodebaseProfile;
 
-    if CodebaseProfile::exists(&state.db) {
-        match CodebaseProfile::load(&state.db) {
-            Ok(profile) => Json(ProjectStatusResponse {
-                initialized: true,
-                project_type: Some(format!("{:?}", profile.project_type)),
-                total_files: Some(profile.total_files),
-                total_loc: Some(profile.total_loc),
-            }),
-            Err(_) => Json(ProjectStatusResponse {
-                initialized: false,
-                project_type: None,
-                total_files: None,
-                total_loc: None,
-            }),
-        }
+    let policy = ModePolicy::for_mode(active_mode(&state).await).into();
+    let profile = if CodebaseProfile::exists(&state.db) {
+        CodebaseProfile::load(&state.db).ok()
     } else {
-        Json(ProjectStatusResponse {
+        None
+    };
+
+    match profile {
+        Some(profile) => Json(ProjectStatusResponse {
+            initialized: true,
+            project_type: Some(format!("{:?}", profile.project_type)),
+            total_files: Some(profile.total_files),
+            total_loc: Some(profile.total_loc),
+            policy,
+        }),
+        None => Json(ProjectStatusResponse {
             initialized: false,
             project_type: None,
             total_files: None,
             total_loc: None,
-        })
+            policy,
+        }),
     }
 }
 
+#[derive(Serialize, ToSchema)]
+struct PipelineResponse {
+    /// "project" (from .catalyst/pipeline.toml) or "standard"
+    source: String,
+    /// Stage graph runs will follow (absent when the project's file is invalid)
+    #[schema(value_type = Object)]
+    graph: Option<PipelineGraph>,
+    /// Why the project's pipeline was rejected
+    error: Option<String>,
+}
+
+/// Get the stage graph runs follow
+#[utoipa::path(
+    get,
+    path = "/api/v1/project/pipeline",
+    tag = "project",
+    responses(
+        (status = 200, description = "Active pipeline", body = PipelineResponse)
+    )
+)]
+async fn get_project_pipeline() -> Json<PipelineResponse> {
+    Json(match PipelineGraph::load_project() {
+        Ok(Some(graph)) => PipelineResponse {
+            source: "project".to_string(),
+            graph: Some(graph),
+            error: None,
+        },
+        Ok(None) => PipelineResponse {
+            source: "standard".to_string(),
+            graph: Some(PipelineGraph::default()),
+            error: None,
+        },
+        Err(e) => PipelineResponse {
+            source: "project".to_string(),
+            graph: None,
+            error: Some(format!("{:#}", e)),
+        },
+    })
+}
+
 /// Get detailed codebase profile
 #[utoipa::path(
     get,
@@ -1706,7 +3196,7 @@ async fn list_documents(State(state): State<SharedState>) -> Json<DocumentListRe
     }
 }
 
-/// Get a document by slug
+/// Get a document by slug, with the decisions that edited it
 #[utoipa::path(
     get,
     path = "/api/v1/documents/{slug}",
@@ -1720,16 +3210,24 @@ async fn get_document(
     State(state): State<SharedState>,
     axum::extract::Path(slug): axum::extract::Path<String>,
 ) -> Json<DocumentResponse> {
+    let edits = SpecEditManager::new(&state.db)
+        .for_document(&slug)
+        .unwrap_or_default()
+        .into_iter()
+        .map(Into::into)
+        .collect();
     match state.db.get_document(&slug) {
         Ok((title, content)) => Json(DocumentResponse {
             slug,
             title,
             content,
+            edits,
         }),
         Err(_) => Json(DocumentResponse {
             slug,
             title: "".to_string(),
             content: "".to_string(),
+            edits: vec![],
         }),
     }
 }
@@ -1762,6 +3260,109 @@ async fn update_document(
     }
 }
 
+// === Usage Handlers ===
+
+/// Token usage and estimated cost, per agent, feature and run
+#[utoipa::path(
+    get,
+    path = "/api/v1/usage",
+    tag = "usage",
+    params(
+        ("run_id" = Option<String>, Query, description = "Restrict to one run"),
+        ("limit" = Option<usize>, Query, description = "Number of recent calls to include (default 50)")
+    ),
+    responses(
+        (status = 200, description = "Usage summary", body = UsageResponse)
+    )
+)]
+async fn get_usage(
+    State(state): State<SharedState>,
+    axum::extract::Query(query): axum::extract::Query<UsageQuery>,
+) -> Json<UsageResponse> {
+    let ledger = UsageLedger::new(&state.db);
+    let run_id = query.run_id.as_deref();
+    let summary = ledger.summary(run_id).unwrap_or_default();
+    let recent = ledger
+        .list(run_id, query.limit.unwrap_or(50))
+        .unwrap_or_default();
+
+    Json(UsageResponse {
+        run_id: query.run_id.clone(),
+        total: summary.total.into(),
+        by_agent: summary.by_agent.into_iter().map(Into::into).collect(),
+        by_feature: summary.by_feature.into_iter().map(Into::into).collect(),
+        by_run: summary.by_run.into_iter().map(Into::into).collect(),
+        recent: recent
+            .into_iter()
+            .map(|e| UsageEntryResponse {
+                run_id: e.run_id,
+                feature_id: e.feature_id,
+                agent_id: e.agent_id,
+                provider: e.provider,
+                model: e.model,
+                input_tokens: e.input_tokens,
+                output_tokens: e.output_tokens,
+                cost_usd: e.cost_usd,
+                created_at: e.created_at,
+            })
+            .collect(),
+    })
+}
+
+// === Transcript Handlers ===
+
+/// List recorded LLM calls, newest first
+#[utoipa::path(
+    get,
+    path = "/api/v1/transcripts",
+    tag = "transcripts",
+    params(
+        ("run_id" = Option<String>, Query, description = "Restrict to one run"),
+        ("agent_id" = Option<String>, Query, description = "Restrict to one agent"),
+        ("limit" = Option<usize>, Query, description = "Maximum number of calls (default 100)")
+    ),
+    responses(
+        (status = 200, description = "Recorded calls", body = TranscriptListResponse)
+    )
+)]
+async fn list_transcripts(
+    State(state): State<SharedState>,
+    axum::extract::Query(query): axum::extract::Query<TranscriptQuery>,
+) -> Json<TranscriptListResponse> {
+    let transcripts = TranscriptManager::new(&state.db)
+        .list(
+            query.run_id.as_deref(),
+            query.agent_id.as_deref(),
+            query.limit.unwrap_or(100),
+        )
+        .unwrap_or_default();
+
+    Json(TranscriptListResponse {
+        transcripts: transcripts.into_iter().map(Into::into).collect(),
+    })
+}
+
+/// Get one recorded LLM call with its full content
+#[utoipa::path(
+    get,
+    path = "/api/v1/transcripts/{id}",
+    tag = "transcripts",
+    params(("id" = i64, Path, description = "Transcript ID")),
+    responses(
+        (status = 200, description = "Transcript entry", body = TranscriptResponse)
+    )
+)]
+async fn get_transcript(
+    State(state): State<SharedState>,
+    axum::extract::Path(id): axum::extract::Path<i64>,
+) -> Json<TranscriptResponse> {
+    let transcript = TranscriptManager::new(&state.db).get(id).ok().flatten();
+    Json(TranscriptResponse {
+        found: transcript.is_some(),
+        transcript,
+    })
+}
+
 // === PTY WebSocket Handler ===
 
 async fn pty_websocket(ws: WebSocketUpgrade) -> impl IntoResponse {
@@ -2026,13 +3627,19 @@ async fn get_config() -> Json<ConfigResponse> {
         (status = 200, description = "Updated configuration", body = ConfigResponse)
     )
 )]
-async fn update_config(Json(updates): Json<PersistedConfig>) -> Json<ConfigResponse> {
+async fn update_config(
+    State(state): State<SharedState>,
+    Json(updates): Json<PersistedConfig>,
+) -> Json<ConfigResponse> {
     let mut config = PersistedConfig::load().await;
     config.merge(updates);
 
     if let Err(e) = config.save().await {
         eprintln!("Failed to save config: {}", e);
     }
+    config.apply_rate_limits();
+    // A raised run limit may let queued runs start
+    state.scheduler.notify_one();
 
     // Update env var for search_tools to pick up
     if let Some(ref url) = config.searxng_url {
@@ -2060,6 +3667,44 @@ async fn get_providers() -> Json<ProvidersResponse> {
     })
 }
 
+/// Discover models served by a local (OpenAI-compatible) endpoint
+///
+/// Uses `base_url` from the query, then the persisted `local_base_url`, then
+/// the Ollama default.
+#[utoipa::path(
+    get,
+    path = "/api/v1/providers/local/models",
+    tag = "providers",
+    params(("base_url" = Option<String>, Query, description = "Endpoint to query")),
+    responses(
+        (status = 200, description = "Models available on the endpoint", body = ProviderModelsResponse)
+    )
+)]
+async fn list_local_models(
+    axum::extract::Query(query): axum::extract::Query<ProviderModelsQuery>,
+) -> Json<ProviderModelsResponse> {
+    let base_url = match query.base_url.filter(|u| !u.is_empty()) {
+        Some(url) => url,
+        None => PersistedConfig::load()
+            .await
+            .local_base_url
+            .unwrap_or_else(|| local::DEFAULT_LOCAL_BASE_URL.to_string()),
+    };
+
+    match local::list_models(&base_url, local::api_key().as_deref()).await {
+        Ok(models) => Json(ProviderModelsResponse {
+            base_url,
+            models,
+            error: None,
+        }),
+        Err(e) => Json(ProviderModelsResponse {
+            base_url,
+            models: vec![],
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
 // === Static File Serving ===
 
 async fn proxy_frontend(uri: Uri) -> impl IntoResponse {
@@ -2134,6 +3779,26 @@ pub async fn run_server() -> anyhow::Result<()> {
         Err(e) => eprintln!("⚠️ Failed to seed prompts: {}", e),
     }
 
+    // Report runs cut off by a previous shutdown
+    match RunManager::new(&db).list_interrupted() {
+        Ok(runs) if !runs.is_empty() => {
+            println!("⏸️  {} interrupted run(s) can be resumed:", runs.len());
+            for run in &runs {
+                println!(
+                    "   {} ({} unknowns done): {}",
+                    run.id,
+                    run.completed_unknowns,
+                    run.goal.chars().take(60).collect::<String>()
+                );
+            }
+        }
+        Ok(_) => {}
+        Err(e) => eprintln!("⚠️ Failed to list interrupted runs: {}", e),
+    }
+
+    // Provider limits are shared by every run; config updates replace them
+    PersistedConfig::load().await.apply_rate_limits();
+
     // Initialize memory service with the shared database
     let memory = CatalystMemory::new_with_db(&db, MemoryConfig::default());
 
@@ -2144,21 +3809,34 @@ pub async fn run_server() -> anyhow::Result<()> {
     }
 
     let state: SharedState = Arc::new(AppState {
-        swarm_status: RwLock::new(SwarmStatus::default()),
+        runs: RwLock::new(RunQueue::default()),
+        scheduler: Notify::new(),
         event_tx,
         memory,
         db,
-        pending_approvals: RwLock::new(HashMap::new()),
-        coordinator_tx: RwLock::new(None),
     });
+    tokio::spawn(run_scheduler(state.clone()));
+    tokio::spawn(run_gardener(state.clone()));
 
     let swarm_routes = Router::new()
         .route("/status", get(get_status))
         .route("/start", post(start_swarm))
+        .route("/execute", post(execute_swarm))
         .route("/stop", post(stop_swarm))
+        .route("/pause", post(pause_swarm))
+        .route("/resume", post(resume_swarm))
+        .route("/unknowns/:id/skip", post(skip_unknown))
+        .route("/features/:id/cancel", post(cancel_feature))
         .route("/approve", post(handle_approval))
         .route("/events", get(events));
 
+    // Run queue routes
+    let queue_routes = Router::new()
+        .route("/", get(list_queue))
+        .route("/:id", get(get_queued_run))
+        .route("/:id/priority", post(set_run_priority))
+        .route("/:id/cancel", post(cancel_queued_run));
+
     let memory_routes = Router::new().route("/search", post(search_memory));
 
     // Braindump routes (Factory Tab)
@@ -2171,6 +3849,7 @@ pub async fn run_server() -> anyhow::Result<()> {
         .route("/features", get(list_features))
         .route("/features/:id", delete(delete_feature))
         .route("/ignite", post(ignite_feature))
+        .route("/garden", post(garden_project))
         .route("/snapshots", get(list_snapshots))
         .route("/snapshots/:id", delete(delete_snapshot))
         .route("/rollback", post(rollback_to_snapshot));
@@ -2184,6 +3863,7 @@ pub async fn run_server() -> anyhow::Result<()> {
     // Project routes (brownfield init)
     let project_routes = Router::new()
         .route("/status", get(get_project_status))
+        .route("/pipeline", get(get_project_pipeline))
         .route("/profile", get(get_project_profile))
         .route("/init", post(init_project));
 
@@ -2257,13 +3937,105 @@ pub async fn run_server() -> anyhow::Result<()> {
             println!("\n🚀 Run `catalyst serve` to start the server");
             return Ok(());
         }
-        Some(CliCommand::Run { goal }) => {
+        Some(CliCommand::Run {
+            goal,
+            record,
+            replay,
+            cache,
+            refresh_cache,
+            execute,
+            mode,
+        }) => {
             // Run swarm directly without server
             println!("🚀 Running swarm with goal: {}", goal);
             let db = Arc::new(CatalystDb::open().expect("Failed to open CatalystDb"));
-            let config = CoordinatorConfig::default();
+            let mut config = CoordinatorConfig::default();
+            let persisted = PersistedConfig::load().await;
+            persisted.apply_generation(&mut config);
+            persisted.apply_mode(&mut config);
+            persisted.apply_review_panel(&mut config);
+            persisted.apply_rate_limits();
+            if let Some(mode) = mode {
+                config.mode = mode;
+            }
+            println!("   Mode: {}", config.mode);
+            config.pipeline = PipelineGraph::load_project()?;
+            if config.pipeline.is_some() {
+                println!("   Pipeline: .catalyst/pipeline.toml");
+            }
+            if let Some(name) = replay {
+                println!("   📼 Replaying cassette: {}", name);
+                config.global_provider = LlmProvider::Mock;
+                config.cassette = Some(CassetteConfig::named(&name, CassetteMode::Replay));
+            } else if let Some(name) = record {
+                println!("   📼 Recording cassette: {}", name);
+                config.cassette = Some(CassetteConfig::named(&name, CassetteMode::Record));
+            }
+            config.response_cache = cache || refresh_cache;
+            config.cache_bypass = refresh_cache;
             let mut coordinator = Coordinator::new(config, db).with_research_agent();
+            println!("   Run ID: {}", coordinator.run_id());
+            cancel_on_ctrl_c(coordinator.control());
+            if execute {
+                match coordinator.execute(&goal).await {
+                    Ok(result) => {
+                        println!("✅ Execution finished! Merged: {}", result.merged);
+                        println!("   Files drafted: {}", result.files_drafted.len());
+                        if let Some(id) = &result.swarm.feature_id {
+                            println!("   Feature: {}", id);
+                        }
+                        if let Some(error) = &result.error {
+                            eprintln!("   Stopped: {}", error);
+                        }
+                    }
+                    Err(e) => {
+                        eprintln!("❌ Execution failed: {}", e);
+                    }
+                }
+                return Ok(());
+            }
             match coordinator.run(&goal).await {
+                Ok(result) => {
+                    println!("✅ Swarm completed! Success: {}", result.success);
+                    println!("   Decisions: {}", result.decisions.len());
+                    if let (Some(id), Some(mission)) = (&result.feature_id, &result.mission) {
+                        println!(
+                            "   Feature {}: {} modules, {} drafting missions",
+                            id,
+                            result.plan.as_ref().map_or(0, |p| p.modules.len()),
+                            mission.drafting_missions.len()
+                        );
+                    }
+                }
+                Err(e) => {
+                    eprintln!("❌ Swarm failed: {}", e);
+                }
+            }
+            return Ok(());
+        }
+        Some(CliCommand::Resume { run_id }) => {
+            let db = Arc::new(CatalystDb::open().expect("Failed to open CatalystDb"));
+            let run_id = match run_id {
+                Some(id) => id,
+                None => match RunManager::new(&db).list_interrupted()?.into_iter().next() {
+                    Some(run) => run.id,
+                    None => {
+                        println!("No interrupted runs");
+                        return Ok(());
+                    }
+                },
+            };
+            println!("⏯️  Resuming run {}", run_id);
+            let mut config = CoordinatorConfig::default();
+            let persisted = PersistedConfig::load().await;
+            persisted.apply_generation(&mut config);
+            persisted.apply_mode(&mut config);
+            persisted.apply_review_panel(&mut config);
+            persisted.apply_rate_limits();
+            config.pipeline = PipelineGraph::load_project()?;
+            let mut coordinator = Coordinator::new(config, db).with_research_agent();
+            cancel_on_ctrl_c(coordinator.control());
+            match coordinator.resume(&run_id).await {
                 Ok(result) => {
                     println!("✅ Swarm completed! Success: {}", result.success);
                     println!("   Decisions: {}", result.decisions.len());
@@ -2274,6 +4046,68 @@ pub async fn run_server() -> anyhow::Result<()> {
             }
             return Ok(());
         }
+        Some(CliCommand::Garden { dry_run }) => {
+            let db = CatalystDb::open().expect("Failed to open CatalystDb");
+            let (report, harvest) = garden(&db, dry_run).await?;
+            println!(
+                "🌱 Scanned {} files, found {} issues",
+                report.files_scanned,
+                report.issues.len()
+            );
+            for issue in &report.issues {
+                let location = match issue.line {
+                    Some(line) => format!("{}:{}", issue.file, line),
+                    None => issue.file.clone(),
+                };
+                println!(
+                    "   [{:?}] {}: {}",
+                    issue.priority, location, issue.description
+                );
+            }
+            if let Some(harvest) = harvest {
+                println!(
+                    "   Filed {} features and {} ideas ({} already filed)",
+                    harvest.features.len(),
+                    harvest.ideas.len(),
+                    harvest.skipped
+                );
+            }
+            return Ok(());
+        }
+        Some(CliCommand::Transcript {
+            run_id,
+            agent,
+            output,
+        }) => {
+            let db = CatalystDb::open().expect("Failed to open CatalystDb");
+            let manager = TranscriptManager::new(&db);
+            let run_id = match run_id {
+                Some(id) => id,
+                None => match manager.list(None, None, 1)?.pop() {
+                    Some(latest) => latest.run_id,
+                    None => {
+                        eprintln!("❌ No transcripts recorded yet");
+                        return Ok(());
+                    }
+                },
+            };
+
+            let entries = manager.for_run(&run_id, agent.as_deref())?;
+            let markdown = transcripts::to_markdown(&run_id, &entries);
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, markdown)?;
+                    println!(
+                        "📝 Wrote {} calls of {} to {}",
+                        entries.len(),
+                        run_id,
+                        path.display()
+                    );
+                }
+                None => print!("{}", markdown),
+            }
+            return Ok(());
+        }
         Some(CliCommand::Serve { dev, port }) => {
             // Start server with explicit dev mode and port
             if dev {
@@ -2303,6 +4137,7 @@ pub async fn run_server() -> anyhow::Result<()> {
     let app = Router::new()
         // v1 API routes
         .nest("/api/v1/swarm", swarm_routes)
+        .nest("/api/v1/queue", queue_routes)
         .nest("/api/v1/memory", memory_routes)
         .nest("/api/v1/braindump", braindump_routes)
         .nest("/api/v1/reactor", reactor_routes)
@@ -2313,6 +4148,12 @@ pub async fn run_server() -> anyhow::Result<()> {
         .nest("/api/v1/documents", document_routes)
         .route("/api/v1/config", get(get_config).patch(update_config))
         .route("/api/v1/providers", get(get_providers))
+        .route("/api/v1/usage", get(get_usage))
+        .route("/api/v1/transcripts", get(list_transcripts))
+        .route("/api/v1/transcripts/:id", get(get_transcript))
+        .route("/api/v1/runs/interrupted", get(list_interrupted_runs))
+        .route("/api/v1/runs/:id/resume", post(resume_run))
+        .route("/api/v1/providers/local/models", get(list_local_models))
         .route("/api/v1/openapi.json", get(serve_openapi))
         // A2A Discovery endpoint
         .route("/.well-known/agent-card.json", get(serve_agent_card))
@@ -2336,7 +4177,10 @@ pub async fn run_server() -> anyhow::Result<()> {
     println!("   Reactor:   /api/v1/reactor/features, /ignite");
     println!("   Project:   /api/v1/project/status, /init");
     println!("   Config:    /api/v1/config (GET, PATCH)");
-    println!("   Providers: /api/v1/providers (GET)");
+    println!("   Providers: /api/v1/providers (GET), /local/models");
+    println!("   Usage:     /api/v1/usage (GET)");
+    println!("   Transcripts: /api/v1/transcripts (GET), /:id");
+    println!("   Runs:      /api/v1/runs/interrupted (GET), /:id/resume (POST)");
     println!("   Terminal:  /api/pty (WebSocket)");
 
     let listener = TcpListener::bind(addr).await?;